@@ -0,0 +1,72 @@
+//! Heap allocations per `batch` record: `cargo bench --bench allocations` (numbers in the README).
+//!
+//! Counts the allocations of a single-threaded `batch::run` over the same seeds as the
+//! `batch` group of `throughput`, against drawing each fortune and serializing it to its
+//! own string.
+
+use hash_omikuji::{batch, Fortune};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static BYTES: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(new_size, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+const SEEDS: usize = 1024;
+
+/// Allocations and bytes allocated per seed while running `f`
+fn measure(name: &str, f: impl FnOnce()) {
+    let (allocations, bytes) = (ALLOCATIONS.load(Ordering::Relaxed), BYTES.load(Ordering::Relaxed));
+    f();
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - allocations;
+    let bytes = BYTES.load(Ordering::Relaxed) - bytes;
+    println!("{:<24} {:>8.1} allocations {:>10.0} bytes per fortune", name, allocations as f64 / SEEDS as f64, bytes as f64 / SEEDS as f64);
+}
+
+fn main() {
+    let builder = Fortune::builder().year(2026);
+    let input: String = (0..SEEDS).map(|i| format!("user-{}\n", i)).collect();
+    let mut out = Vec::with_capacity(SEEDS * 4096);
+
+    // Warm up lazily initialized tables before counting
+    batch::run(&builder, input.as_bytes(), None, &mut out).unwrap();
+
+    out.clear();
+    measure("batch::run", || {
+        batch::run(&builder, input.as_bytes(), None, &mut out).unwrap();
+    });
+    let mut lines = Vec::with_capacity(SEEDS);
+    measure("draw + to_string", || {
+        for seed in input.lines() {
+            lines.push(serde_json::to_string(&builder.clone().seed(seed).draw()).unwrap());
+        }
+    });
+    measure("draw only", || {
+        for seed in input.lines() {
+            black_box(builder.clone().seed(seed).draw());
+        }
+    });
+}
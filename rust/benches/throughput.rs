@@ -0,0 +1,77 @@
+//! Throughput of the hot paths: `cargo bench --bench throughput` (baseline in the README).
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+use hash_omikuji::hash::SeedHasher;
+use hash_omikuji::{batch, Algo, ArtMode, ArtOptions, Fortune, HashBits};
+use std::hint::black_box;
+
+/// Seeds of one batch run: a quarter block, so an iteration stays short
+const BATCH_SEEDS: usize = 1024;
+
+fn seed_derivation(c: &mut Criterion) {
+    let mut group = c.benchmark_group("seed");
+    group.throughput(Throughput::Elements(1));
+    for algo in [Algo::Sha256, Algo::Blake3] {
+        group.bench_with_input(BenchmarkId::new("from_seed", format!("{:?}", algo)), &algo, |b, &algo| {
+            b.iter(|| HashBits::from_seeds_with(2026, &[black_box("alice")], algo))
+        });
+    }
+    let mut hasher = SeedHasher::new(2026, Algo::Sha256);
+    group.bench_function("seed_hasher", |b| b.iter(|| hasher.hash(black_box("alice"))));
+    group.finish();
+}
+
+fn field_extraction(c: &mut Criterion) {
+    let hash = HashBits::from_seed(2026, "alice");
+    let builder = Fortune::builder().year(2026).seed("alice");
+    let fortune = builder.draw();
+    let mut group = c.benchmark_group("fields");
+    group.throughput(Throughput::Elements(1));
+    group.bench_function("grade", |b| b.iter(|| black_box(&hash).grade_bits()));
+    group.bench_function("from_hash", |b| b.iter(|| Fortune::from_hash(black_box(&hash), 2026, "alice")));
+    group.bench_function("draw", |b| b.iter(|| black_box(&builder).draw()));
+    group.bench_function("to_json", |b| {
+        let mut buffer = Vec::new();
+        b.iter(|| {
+            buffer.clear();
+            serde_json::to_writer(&mut buffer, black_box(&fortune)).unwrap();
+        })
+    });
+    group.finish();
+}
+
+fn art_generation(c: &mut Criterion) {
+    let hash = HashBits::from_seed(2026, "alice");
+    let mut group = c.benchmark_group("art");
+    group.throughput(Throughput::Elements(1));
+    for mode in [ArtMode::Randomart, ArtMode::Emoji, ArtMode::Maze, ArtMode::Mandala] {
+        let options = ArtOptions { mode, ..ArtOptions::default() };
+        group.bench_with_input(BenchmarkId::from_parameter(format!("{:?}", mode)), &options, |b, options| {
+            b.iter(|| black_box(&hash).art(options))
+        });
+    }
+    group.finish();
+}
+
+fn batch_pipeline(c: &mut Criterion) {
+    let builder = Fortune::builder().year(2026);
+    let input: String = (0..BATCH_SEEDS).map(|i| format!("user-{}\n", i)).collect();
+    let mut group = c.benchmark_group("batch");
+    group.throughput(Throughput::Elements(BATCH_SEEDS as u64));
+    group.sample_size(10);
+    for jobs in [1, 0] {
+        let pool = batch::pool(Some(jobs));
+        let name = if jobs == 1 { "1 thread".to_string() } else { "all cores".to_string() };
+        group.bench_function(name, |b| {
+            b.iter_batched_ref(
+                || Vec::with_capacity(BATCH_SEEDS * 2048),
+                |out| batch::run(&builder, input.as_bytes(), pool.as_ref(), out).unwrap(),
+                BatchSize::LargeInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, seed_derivation, field_extraction, art_generation, batch_pipeline);
+criterion_main!(benches);
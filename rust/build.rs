@@ -0,0 +1,32 @@
+fn main() {
+    #[cfg(feature = "ffi")]
+    generate_header();
+    #[cfg(feature = "node")]
+    napi_build::setup();
+    #[cfg(feature = "grpc")]
+    compile_protos();
+}
+
+/// Generate the `grpc` module's messages and service from proto/omikuji.proto,
+/// with the vendored protoc so no system install is needed
+#[cfg(feature = "grpc")]
+fn compile_protos() {
+    println!("cargo:rerun-if-changed=proto/omikuji.proto");
+    std::env::set_var("PROTOC", protoc_bin_vendored::protoc_bin_path().unwrap());
+    tonic_prost_build::configure()
+        .build_client(false)
+        .compile_protos(&["proto/omikuji.proto"], &["proto"])
+        .expect("Unable to compile proto/omikuji.proto");
+}
+
+/// Write the C header for the `ffi` module to include/hash_omikuji.h
+#[cfg(feature = "ffi")]
+fn generate_header() {
+    let crate_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+    println!("cargo:rerun-if-changed=src/ffi.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    let config = cbindgen::Config::from_file(format!("{}/cbindgen.toml", crate_dir)).unwrap();
+    cbindgen::generate_with_config(&crate_dir, config)
+        .expect("Unable to generate C header")
+        .write_to_file(format!("{}/include/hash_omikuji.h", crate_dir));
+}
@@ -0,0 +1,28 @@
+/* Generated by cbindgen from src/ffi.rs; do not edit. */
+
+#ifndef HASH_OMIKUJI_H
+#define HASH_OMIKUJI_H
+
+#include <stdint.h>
+
+/**
+ * Draw the fortune for `year` and the NUL-terminated UTF-8 `seed` and return it as
+ * JSON (the `--json` document). Returns NULL if `seed` is NULL or not valid UTF-8.
+ * Release the result with `omikuji_string_free`.
+ *
+ * # Safety
+ *
+ * `seed` must be NULL or point to a NUL-terminated string.
+ */
+char *omikuji_draw_json(uint32_t year, const char *seed);
+
+/**
+ * Release a string returned by `omikuji_draw_json`; NULL is ignored.
+ *
+ * # Safety
+ *
+ * `s` must be NULL or a pointer returned by this library that has not been freed yet.
+ */
+void omikuji_string_free(char *s);
+
+#endif  /* HASH_OMIKUJI_H */
@@ -0,0 +1,7 @@
+// Copy the cdylib built by `cargo rustc` next to index.js as hash-omikuji.node
+const fs = require('fs')
+const path = require('path')
+
+const names = { darwin: 'libhash_omikuji.dylib', win32: 'hash_omikuji.dll' }
+const library = path.join(__dirname, '..', 'target', 'release', names[process.platform] || 'libhash_omikuji.so')
+fs.copyFileSync(library, path.join(__dirname, 'hash-omikuji.node'))
@@ -0,0 +1,84 @@
+// Types for the napi-rs addon (src/node.rs)
+
+export interface LuckScore {
+  luck_type: string
+  label: string
+  raw_value: number
+  score: number
+  rank: 'Excellent' | 'Good' | 'Normal' | 'Bad' | 'Terrible'
+}
+
+export interface TopicVerdict {
+  topic: string
+  name: string
+  text: string
+}
+
+export interface Poem {
+  text: string
+  romaji: string
+  gloss: string
+  author: string
+  author_romaji: string
+}
+
+/** The `--json` document */
+export interface Fortune {
+  year: number
+  grade: 'Daikichi' | 'Chukichi' | 'Shokichi' | 'Kichi' | 'Suekichi' | 'Kyo' | 'Daikyo'
+  overall_score: number
+  star_rating: string
+  rarity_one_in: number
+  layout: 'v1' | 'v2'
+  algo: 'sha256' | 'blake3'
+  seed?: string
+  lucky_number: number
+  lucky_hex: string
+  lucky_color: string
+  lucky_bits: string
+  lucky_day: string
+  lucky_day_number: number
+  lucky_date: string
+  lucky_date_label: string
+  lucky_time: string
+  lucky_power_of_2: number
+  lucky_ascii: string
+  lucky_logic_gate: string
+  lucky_emoji: string
+  lucky_direction: string
+  lucky_element: string
+  lucky_percent: number
+  lucky_latitude: number
+  lucky_longitude: number
+  lucky_item: string
+  lucky_action: string
+  luck_scores: LuckScore[]
+  lang: 'en' | 'ja'
+  topics: TopicVerdict[]
+  advice: string
+  poem: Poem
+  entropy_check: string
+  art: string
+  art_options: { width: number; height: number; palette: string; mode: string }
+  art_walk: { width: number; height: number; steps: number; visits: number[]; start: number; end: number }
+  field_strips: { lucky_number: string; lucky_day: string; grade: string }
+  fingerprint: string
+  fingerprint_sha256: string
+  fingerprint_emoji: string
+  fingerprint_words: string
+  effort?: { level: number; algorithm: string; memory_kib: number; iterations: number; parallelism: number; rounds: number }
+  signature?: { algorithm: string; public_key: string; value: string }
+}
+
+export interface Verification {
+  /** The document carried a layout v2 checksum and it matched */
+  checksum: boolean
+  /** Public key (hex) of the valid signature, if the document was signed */
+  signer?: string | null
+}
+
+/** Draw the fortune for a year and seed */
+export function draw(year: number, seed: string): Fortune
+
+/** Check the layout v2 checksum and/or signature of a JSON fortune; throws on failure */
+export function verify(json: string, pubkey?: string | null): Verification
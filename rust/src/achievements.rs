@@ -0,0 +1,190 @@
+use crate::history::{self, HistoryEntry};
+use crate::lang::Lang;
+use crate::luck::Grade;
+use crate::streak;
+use chrono::{Datelike, NaiveDate};
+
+/// Badges that can be earned across years of draws
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Achievement {
+    FirstDaikichi,
+    Survivor,
+    LuckyDayOnDrawDate,
+    FullLadder,
+    Veteran,
+    /// Consecutive years of real draws (not --force-year previews)
+    Streak3,
+    Streak5,
+    Streak10,
+}
+
+impl Achievement {
+    pub fn name(&self, lang: Lang) -> &'static str {
+        match (self, lang) {
+            (Achievement::FirstDaikichi, Lang::Ja) => "初めての大吉",
+            (Achievement::FirstDaikichi, Lang::En) => "First 大吉",
+            (Achievement::Survivor, Lang::Ja) => "三年連続凶 — 生還者",
+            (Achievement::Survivor, Lang::En) => "Three 凶 in a row — survivor",
+            (Achievement::LuckyDayOnDrawDate, Lang::Ja) => "引いた日がラッキーデー",
+            (Achievement::LuckyDayOnDrawDate, Lang::En) => "Lucky day landed on your draw date",
+            (Achievement::FullLadder, Lang::Ja) => "七段すべて制覇",
+            (Achievement::FullLadder, Lang::En) => "Drew all seven grades",
+            (Achievement::Veteran, Lang::Ja) => "五年の参拝",
+            (Achievement::Veteran, Lang::En) => "Five years of draws",
+            (Achievement::Streak3, Lang::Ja) => "三年連続の参拝",
+            (Achievement::Streak3, Lang::En) => "3-year streak",
+            (Achievement::Streak5, Lang::Ja) => "五年連続の参拝",
+            (Achievement::Streak5, Lang::En) => "5-year streak",
+            (Achievement::Streak10, Lang::Ja) => "十年連続の参拝",
+            (Achievement::Streak10, Lang::En) => "10-year streak",
+        }
+    }
+}
+
+/// An achievement with the year it was first earned
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Earned {
+    pub achievement: Achievement,
+    pub year: u32,
+}
+
+fn lucky_date(entry: &HistoryEntry) -> Option<NaiveDate> {
+    NaiveDate::from_yo_opt(entry.year as i32, entry.lucky_day_number as u32)
+}
+
+/// Achievements earned by the stored draws, in the order they were earned.
+/// Uses the latest draw per year, so re-drawing a year never double counts.
+pub fn evaluate(entries: &[HistoryEntry]) -> Vec<Earned> {
+    let years = history::by_year(entries);
+    let streaks = streak::lengths(&streak::drawn_years(entries));
+    let mut earned: Vec<Earned> = Vec::new();
+    let mut grant = |achievement: Achievement, year: u32| {
+        if !earned.iter().any(|e| e.achievement == achievement) {
+            earned.push(Earned { achievement, year });
+        }
+    };
+
+    let mut bad_streak = 0;
+    let mut previous_year = None;
+    let mut grades_seen: Vec<Grade> = Vec::new();
+    for (index, (&year, entry)) in years.iter().enumerate() {
+        if entry.grade == Grade::Daikichi {
+            grant(Achievement::FirstDaikichi, year);
+        }
+
+        let consecutive = previous_year == Some(year - 1);
+        bad_streak = match (entry.grade <= Grade::Kyo, consecutive) {
+            (false, _) => 0,
+            (true, true) => bad_streak + 1,
+            (true, false) => 1,
+        };
+        if bad_streak >= 3 {
+            grant(Achievement::Survivor, year);
+        }
+        previous_year = Some(year);
+
+        if lucky_date(entry) == Some(entry.drawn_on) && entry.drawn_on.year() as u32 == year {
+            grant(Achievement::LuckyDayOnDrawDate, year);
+        }
+
+        if !grades_seen.contains(&entry.grade) {
+            grades_seen.push(entry.grade);
+        }
+        if grades_seen.len() == Grade::ALL.len() {
+            grant(Achievement::FullLadder, year);
+        }
+
+        if index + 1 == 5 {
+            grant(Achievement::Veteran, year);
+        }
+
+        let streak = streaks.get(&year).copied().unwrap_or(0);
+        for (achievement, length) in [(Achievement::Streak3, 3), (Achievement::Streak5, 5), (Achievement::Streak10, 10)] {
+            if streak >= length {
+                grant(achievement, year);
+            }
+        }
+    }
+    earned
+}
+
+pub fn format_achievements(earned: &[Earned], lang: Lang) -> String {
+    if earned.is_empty() {
+        return match lang {
+            Lang::Ja => "まだ実績はありません\n".to_string(),
+            Lang::En => "No achievements yet\n".to_string(),
+        };
+    }
+    earned
+        .iter()
+        .map(|e| format!("🏅 {} ({})\n", e.achievement.name(lang), e.year))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(year: u32, grade: Grade) -> HistoryEntry {
+        HistoryEntry {
+            year,
+            drawn_on: NaiveDate::from_ymd_opt(year as i32, 1, 1).unwrap(),
+            forced: false,
+            grade,
+            overall_score: 50,
+            lucky_day_number: 100,
+            fingerprint: "00".repeat(32),
+        }
+    }
+
+    fn achievements(entries: &[HistoryEntry]) -> Vec<(Achievement, u32)> {
+        evaluate(entries).iter().map(|e| (e.achievement, e.year)).collect()
+    }
+
+    #[test]
+    fn test_first_daikichi() {
+        let entries = [entry(2024, Grade::Kichi), entry(2025, Grade::Daikichi), entry(2026, Grade::Daikichi)];
+        assert_eq!(achievements(&entries), vec![(Achievement::FirstDaikichi, 2025), (Achievement::Streak3, 2026)]);
+    }
+
+    #[test]
+    fn test_survivor_needs_consecutive_years() {
+        let entries = [entry(2020, Grade::Kyo), entry(2021, Grade::Daikyo), entry(2023, Grade::Kyo)];
+        assert!(achievements(&entries).is_empty());
+
+        let entries = [entry(2021, Grade::Kyo), entry(2022, Grade::Daikyo), entry(2023, Grade::Kyo)];
+        assert_eq!(achievements(&entries), vec![(Achievement::Survivor, 2023), (Achievement::Streak3, 2023)]);
+    }
+
+    #[test]
+    fn test_lucky_day_on_draw_date() {
+        let mut lucky = entry(2026, Grade::Kichi);
+        lucky.lucky_day_number = 1;
+        assert_eq!(achievements(&[lucky]), vec![(Achievement::LuckyDayOnDrawDate, 2026)]);
+    }
+
+    #[test]
+    fn test_full_ladder_and_veteran() {
+        let entries: Vec<_> = Grade::ALL.iter().enumerate().map(|(i, &g)| entry(2020 + i as u32, g)).collect();
+        let earned = achievements(&entries);
+        assert!(earned.contains(&(Achievement::FullLadder, 2026)));
+        assert!(earned.contains(&(Achievement::Veteran, 2024)));
+        assert!(earned.contains(&(Achievement::Streak3, 2022)));
+        assert!(earned.contains(&(Achievement::Streak5, 2024)));
+        assert!(!earned.iter().any(|e| e.0 == Achievement::Streak10));
+    }
+
+    #[test]
+    fn test_streak_skips_previews() {
+        let mut entries: Vec<_> = (2020..2025).map(|year| entry(year, Grade::Kichi)).collect();
+        entries[2].forced = true;
+        let earned = achievements(&entries);
+        assert!(earned.contains(&(Achievement::Veteran, 2024)));
+        assert!(!earned.iter().any(|e| matches!(e.0, Achievement::Streak3 | Achievement::Streak5)));
+    }
+
+    #[test]
+    fn test_format_empty() {
+        assert_eq!(format_achievements(&[], Lang::En), "No achievements yet\n");
+    }
+}
@@ -0,0 +1,186 @@
+use crate::lang::Lang;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+const DEFAULT_EN: &str = include_str!("grammar/advice_en.toml");
+const DEFAULT_JA: &str = include_str!("grammar/advice_ja.toml");
+
+/// Nested `{slot}` references deeper than this are left unexpanded
+const MAX_DEPTH: usize = 8;
+
+/// Template grammar: a template is picked, then each `{slot}` is replaced by a
+/// hash-chosen entry of that slot (entries may reference further slots)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Grammar {
+    pub templates: Vec<String>,
+    #[serde(default)]
+    pub slots: BTreeMap<String, Vec<String>>,
+}
+
+impl Grammar {
+    /// The embedded grammar of `lang`, parsed on first use
+    pub fn embedded(lang: Lang) -> &'static Grammar {
+        static EN: OnceLock<Grammar> = OnceLock::new();
+        static JA: OnceLock<Grammar> = OnceLock::new();
+        let (cell, text) = match lang {
+            Lang::En => (&EN, DEFAULT_EN),
+            Lang::Ja => (&JA, DEFAULT_JA),
+        };
+        cell.get_or_init(|| Self::parse(text).expect("embedded grammar is valid"))
+    }
+
+    pub fn parse(text: &str) -> Result<Grammar, String> {
+        let grammar: Grammar = toml::from_str(text).map_err(|e| e.message().to_string())?;
+        grammar.validate()
+    }
+
+    /// At least one template, and no empty slot
+    pub fn validate(self) -> Result<Grammar, String> {
+        if self.templates.is_empty() {
+            return Err("Grammar needs at least one template".to_string());
+        }
+        if let Some((name, _)) = self.slots.iter().find(|(_, entries)| entries.is_empty()) {
+            return Err(format!("Grammar slot '{}' has no entries", name));
+        }
+        Ok(self)
+    }
+
+    /// Add the templates and slot entries of `other` to this grammar
+    pub fn extend(&mut self, other: &Grammar) {
+        self.templates.extend(other.templates.iter().cloned());
+        for (name, entries) in &other.slots {
+            self.slots.entry(name.clone()).or_default().extend(entries.iter().cloned());
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Grammar, String> {
+        let text = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        Self::parse(&text).map_err(|e| format!("Invalid grammar {}: {}", path.display(), e))
+    }
+
+    /// Compose advice, consuming 16 bits of `entropy` per choice (wrapping around)
+    pub fn generate(&self, entropy: &[u8; 32]) -> String {
+        let mut choices = Choices { entropy, cursor: 0 };
+        let template = &self.templates[choices.next(self.templates.len())];
+        let mut text = String::with_capacity(template.len() * 2);
+        self.expand(template, &mut choices, 0, &mut text);
+        capitalize_sentences(&text)
+    }
+
+    fn expand(&self, template: &str, choices: &mut Choices, depth: usize, output: &mut String) {
+        let mut rest = template;
+        while let Some(open) = rest.find('{') {
+            output.push_str(&rest[..open]);
+            let Some(close) = rest[open..].find('}') else {
+                break;
+            };
+            let name = &rest[open + 1..open + close];
+            match self.slots.get(name) {
+                Some(entries) if depth < MAX_DEPTH => {
+                    let entry = &entries[choices.next(entries.len())];
+                    self.expand(entry, choices, depth + 1, output);
+                }
+                _ => output.push_str(&rest[open..open + close + 1]),
+            }
+            rest = &rest[open + close + 1..];
+        }
+        output.push_str(rest);
+    }
+}
+
+struct Choices<'a> {
+    entropy: &'a [u8; 32],
+    cursor: usize,
+}
+
+impl Choices<'_> {
+    fn next(&mut self, len: usize) -> usize {
+        let hi = self.entropy[self.cursor % 32] as usize;
+        let lo = self.entropy[(self.cursor + 1) % 32] as usize;
+        self.cursor += 2;
+        ((hi << 8) | lo) % len
+    }
+}
+
+/// Uppercase the first ASCII letter of each sentence (no-op for Japanese)
+fn capitalize_sentences(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut at_start = true;
+    for ch in text.chars() {
+        if at_start && ch.is_ascii_alphabetic() {
+            output.push(ch.to_ascii_uppercase());
+            at_start = false;
+        } else {
+            if !ch.is_whitespace() {
+                at_start = false;
+            }
+            output.push(ch);
+        }
+        if ch == '.' || ch == '!' || ch == '?' {
+            at_start = true;
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_embedded_grammars_parse() {
+        assert!(!Grammar::embedded(Lang::En).templates.is_empty());
+        assert!(!Grammar::embedded(Lang::Ja).templates.is_empty());
+        assert!(std::ptr::eq(Grammar::embedded(Lang::En), Grammar::embedded(Lang::En)));
+    }
+
+    #[test]
+    fn test_generate_deterministic_and_complete() {
+        let grammar = Grammar::embedded(Lang::En);
+        for seed in 0..64u8 {
+            let entropy = [seed.wrapping_mul(37); 32];
+            let advice = grammar.generate(&entropy);
+            assert_eq!(advice, grammar.generate(&entropy));
+            assert!(!advice.contains('{'), "unexpanded slot in {}", advice);
+            assert!(advice.chars().next().unwrap().is_uppercase());
+            assert!(advice.ends_with('.'));
+        }
+    }
+
+    #[test]
+    fn test_generate_nested_slots() {
+        let grammar = Grammar::parse(
+            "templates = [\"{a}.\"]\n[slots]\na = [\"x {b}\"]\nb = [\"y\"]\n",
+        )
+        .unwrap();
+        assert_eq!(grammar.generate(&[0; 32]), "X y.");
+    }
+
+    #[test]
+    fn test_unknown_slot_left_verbatim() {
+        let grammar = Grammar::parse("templates = [\"keep {missing}.\"]\n").unwrap();
+        assert_eq!(grammar.generate(&[0; 32]), "Keep {missing}.");
+    }
+
+    #[test]
+    fn test_recursion_is_bounded() {
+        let grammar = Grammar::parse("templates = [\"{a}\"]\n[slots]\na = [\"{a}\"]\n").unwrap();
+        assert_eq!(grammar.generate(&[0; 32]), "{a}");
+    }
+
+    #[test]
+    fn test_parse_rejects_empty() {
+        assert!(Grammar::parse("templates = []\n").is_err());
+        assert!(Grammar::parse("templates = [\"{a}\"]\n[slots]\na = []\n").is_err());
+    }
+
+    #[test]
+    fn test_capitalize_sentences() {
+        assert_eq!(capitalize_sentences("one. two? three"), "One. Two? Three");
+        assert_eq!(capitalize_sentences("今年は。"), "今年は。");
+    }
+}
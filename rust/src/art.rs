@@ -0,0 +1,538 @@
+use crate::lang::display_width;
+use crate::luck::Grade;
+use crate::theme::rgb;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloc::{format, vec};
+
+/// Default art size: a single row of 16 cells
+pub const DEFAULT_WIDTH: usize = 16;
+pub const DEFAULT_HEIGHT: usize = 1;
+
+/// Glyph sets for the randomart, from unvisited to most visited
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "serve", derive(utoipa::ToSchema))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
+#[cfg_attr(feature = "std", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[serde(rename_all = "lowercase")]
+pub enum ArtPalette {
+    /// ` .+#` with S (start), E (end) and X (start and end on the same cell)
+    #[default]
+    Simple,
+    /// OpenSSH randomart glyphs ` .o+=*BOX@%&#/^`
+    Classic,
+    /// Shade blocks ` ░▒▓█`
+    Blocks,
+}
+
+impl ArtPalette {
+    /// Glyph per visit count; counts past the end use the last glyph
+    pub fn symbols(self) -> &'static [char] {
+        match self {
+            ArtPalette::Simple => &[' ', '.', '+', '#'],
+            ArtPalette::Classic => &[' ', '.', 'o', '+', '=', '*', 'B', 'O', 'X', '@', '%', '&', '#', '/', '^'],
+            ArtPalette::Blocks => &[' ', '░', '▒', '▓', '█'],
+        }
+    }
+
+    /// Same palette restricted to ASCII glyphs (shade blocks fall back to `Simple`)
+    pub fn ascii(self) -> Self {
+        match self {
+            ArtPalette::Blocks => ArtPalette::Simple,
+            other => other,
+        }
+    }
+
+    /// Markers for the start cell, the end cell, and both on one cell
+    pub fn markers(self) -> (char, char, char) {
+        match self {
+            ArtPalette::Simple | ArtPalette::Blocks => ('S', 'E', 'X'),
+            ArtPalette::Classic => ('S', 'E', 'E'),
+        }
+    }
+}
+
+/// How the walk is drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "serve", derive(utoipa::ToSchema))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
+#[cfg_attr(feature = "std", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[serde(rename_all = "lowercase")]
+pub enum ArtMode {
+    /// Glyphs from the palette, framed
+    #[default]
+    Randomart,
+    /// Emoji tiles ⬜🟨🟧🟥 with 🎌 (start) and ⛩️ (end), unframed for chat apps
+    Emoji,
+    /// Maze grown from the digest, exiting towards the lucky direction (15x9 unless sized)
+    Maze,
+    /// Walk mirrored four ways into a symmetric kamon-like pattern (17x9 unless sized)
+    Mandala,
+}
+
+/// Board size, glyphs and mode of the art
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "serve", derive(utoipa::ToSchema))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject), graphql(rename_fields = "snake_case"))]
+#[cfg_attr(feature = "std", derive(schemars::JsonSchema))]
+pub struct ArtOptions {
+    pub width: usize,
+    pub height: usize,
+    pub palette: ArtPalette,
+    pub mode: ArtMode,
+}
+
+impl Default for ArtOptions {
+    fn default() -> Self {
+        Self {
+            width: DEFAULT_WIDTH,
+            height: DEFAULT_HEIGHT,
+            palette: ArtPalette::default(),
+            mode: ArtMode::default(),
+        }
+    }
+}
+
+impl ArtOptions {
+    /// ASCII-only variant: ASCII palette, emoji tiles fall back to randomart
+    pub fn ascii(self) -> Self {
+        Self {
+            palette: self.palette.ascii(),
+            mode: if self.mode == ArtMode::Emoji { ArtMode::Randomart } else { self.mode },
+            ..self
+        }
+    }
+}
+
+/// Visit counts of the drunken-bishop walk over the digest, row-major.
+/// `start` and `end` are cell indices (`y * width + x`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "serve", derive(utoipa::ToSchema))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject), graphql(rename_fields = "snake_case"))]
+#[cfg_attr(feature = "std", derive(schemars::JsonSchema))]
+pub struct Walk {
+    pub width: usize,
+    pub height: usize,
+    pub steps: usize,
+    pub visits: Vec<u16>,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Walk from the center, two bits per step (x then y, low bits of each byte first).
+/// A single-row board only moves left/right and uses every bit as a step.
+pub fn walk(bytes: &[u8; 32], width: usize, height: usize) -> Walk {
+    let (width, height) = (width.max(1), height.max(1));
+    let (mut x, mut y) = (width / 2, height / 2);
+    let mut visits = vec![0u16; width * height];
+    let start = y * width + x;
+    let bits_per_step = if height == 1 { 1 } else { 2 };
+
+    for byte in bytes {
+        for step in 0..(8 / bits_per_step) {
+            let bits = byte >> (step * bits_per_step);
+            x = if bits & 1 == 1 { (x + 1).min(width - 1) } else { x.saturating_sub(1) };
+            if height > 1 {
+                y = if bits & 2 == 2 { (y + 1).min(height - 1) } else { y.saturating_sub(1) };
+            }
+            let cell = &mut visits[y * width + x];
+            *cell = cell.saturating_add(1);
+        }
+    }
+    Walk { width, height, steps: 256 / bits_per_step, visits, start, end: y * width + x }
+}
+
+/// Draw the digest in the requested mode; `exit` is the lucky direction (0 = ↑, clockwise)
+pub fn render(bytes: &[u8; 32], options: &ArtOptions, exit: u8) -> String {
+    match options.mode {
+        ArtMode::Randomart => generate_omikuji_art(&walk(bytes, options.width, options.height), options.palette),
+        ArtMode::Emoji => emoji_art(&walk(bytes, options.width, options.height)),
+        ArtMode::Maze => {
+            let (width, height) = if options.height > 1 { (options.width, options.height) } else { MAZE_SIZE };
+            maze(bytes, width, height, exit)
+        }
+        ArtMode::Mandala => {
+            let (width, height) = if options.height > 1 { (options.width, options.height) } else { MANDALA_SIZE };
+            mandala(bytes, width, height, options.palette)
+        }
+    }
+}
+
+/// Default mandala size in characters, used when the art board is a single row
+pub const MANDALA_SIZE: (usize, usize) = (17, 9);
+
+/// Walk on the top-left quadrant, mirrored horizontally and vertically so the
+/// pattern is symmetric about both center lines. No start/end markers.
+pub fn mandala(bytes: &[u8; 32], width: usize, height: usize, palette: ArtPalette) -> String {
+    let (width, height) = (width.max(2), height.max(2));
+    let (qw, qh) = (width.div_ceil(2), height.div_ceil(2));
+    let quadrant = walk(bytes, qw, qh);
+    let symbols = palette.symbols();
+    let mirror = |i: usize, size: usize, half: usize| if i < half { i } else { size - 1 - i };
+    (0..height)
+        .map(|y| {
+            (0..width)
+                .map(|x| {
+                    let count = quadrant.visits[mirror(y, height, qh) * qw + mirror(x, width, qw)];
+                    symbols[(count as usize).min(symbols.len() - 1)]
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Default maze size in characters, used when the art board is a single row
+pub const MAZE_SIZE: (usize, usize) = (15, 9);
+
+/// Endless bit source: SHA-256(digest || counter) blocks
+struct BitStream {
+    seed: [u8; 32],
+    counter: u32,
+    block: [u8; 32],
+    used: usize,
+}
+
+impl BitStream {
+    fn new(seed: &[u8; 32]) -> Self {
+        Self { seed: *seed, counter: 0, block: [0; 32], used: 32 }
+    }
+
+    /// Uniform-enough choice in 0..n from the next 16 bits
+    fn choose(&mut self, n: usize) -> usize {
+        if self.used + 2 > self.block.len() {
+            let mut hasher = Sha256::new();
+            hasher.update(self.seed);
+            hasher.update(self.counter.to_be_bytes());
+            self.block.copy_from_slice(&hasher.finalize());
+            self.counter += 1;
+            self.used = 0;
+        }
+        let value = u16::from_be_bytes([self.block[self.used], self.block[self.used + 1]]);
+        self.used += 2;
+        value as usize % n
+    }
+}
+
+/// Maze carved by a randomized depth-first walk from the center cell. Sizes are
+/// rounded down to odd (minimum 5); walls are `#`, the start `S`, the exit `E`
+/// is an opening in the outer wall on the side the lucky direction points to.
+pub fn maze(bytes: &[u8; 32], width: usize, height: usize, exit: u8) -> String {
+    let odd = |n: usize| if n.is_multiple_of(2) { n - 1 } else { n }.max(5);
+    let (width, height) = (odd(width.max(5)), odd(height.max(5)));
+    let (cols, rows) = ((width - 1) / 2, (height - 1) / 2);
+    let mut grid = vec![vec!['#'; width]; height];
+    let mut visited = vec![vec![false; cols]; rows];
+    let mut stream = BitStream::new(bytes);
+
+    let start = (cols / 2, rows / 2);
+    let mut stack = vec![start];
+    visited[start.1][start.0] = true;
+    grid[start.1 * 2 + 1][start.0 * 2 + 1] = ' ';
+    while let Some(&(x, y)) = stack.last() {
+        let neighbors: Vec<(usize, usize)> = [(0, -1), (1, 0), (0, 1), (-1, 0)]
+            .iter()
+            .map(|(dx, dy)| (x as i64 + dx, y as i64 + dy))
+            .filter(|&(nx, ny)| nx >= 0 && ny >= 0 && (nx as usize) < cols && (ny as usize) < rows)
+            .map(|(nx, ny)| (nx as usize, ny as usize))
+            .filter(|&(nx, ny)| !visited[ny][nx])
+            .collect();
+        if neighbors.is_empty() {
+            stack.pop();
+            continue;
+        }
+        let (nx, ny) = neighbors[stream.choose(neighbors.len())];
+        visited[ny][nx] = true;
+        grid[ny * 2 + 1][nx * 2 + 1] = ' ';
+        grid[y + ny + 1][x + nx + 1] = ' ';
+        stack.push((nx, ny));
+    }
+
+    grid[start.1 * 2 + 1][start.0 * 2 + 1] = 'S';
+    let (mid_x, mid_y) = (cols / 2 * 2 + 1, rows / 2 * 2 + 1);
+    let right = (cols - 1) * 2 + 1;
+    let (ex, ey) = match exit % 8 {
+        0 => (mid_x, 0),
+        1 => (right, 0),
+        2 => (width - 1, mid_y),
+        3 => (right, height - 1),
+        4 => (mid_x, height - 1),
+        5 => (1, height - 1),
+        6 => (0, mid_y),
+        _ => (1, 0),
+    };
+    grid[ey][ex] = 'E';
+
+    grid.iter().map(|row| row.iter().collect::<String>()).collect::<Vec<_>>().join("\n")
+}
+
+/// One line explaining the glyphs of the given art settings
+pub fn legend(options: &ArtOptions) -> String {
+    let counts = |symbols: &[char]| {
+        let last = symbols.len() - 1;
+        symbols
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(i, c)| format!("{} {}{}", c, i, if i == last { "+" } else { "" }))
+            .collect::<Vec<_>>()
+            .join("  ")
+    };
+    match options.mode {
+        ArtMode::Randomart => {
+            let (start, end, both) = options.palette.markers();
+            let markers = if both == end {
+                format!("{} start  {} end", start, end)
+            } else {
+                format!("{} start  {} end  {} start=end", start, end, both)
+            };
+            format!("{}  |  visits: {}", markers, counts(options.palette.symbols()))
+        }
+        ArtMode::Emoji => "🎌 start  ⛩️ end  |  visits: ⬜ 0  🟨 1  🟧 2  🟥 3+".to_string(),
+        ArtMode::Maze => "S start  E exit (lucky direction)  # wall".to_string(),
+        ArtMode::Mandala => format!("visits (mirrored four ways): {}", counts(options.palette.symbols())),
+    }
+}
+
+/// Cells of a per-field mini-art strip
+pub const STRIP_WIDTH: usize = 8;
+
+/// 8-cell single-row walk over the low `num_bits` bits of a field, lowest bit first
+pub fn strip(value: u64, num_bits: usize) -> Walk {
+    let mut x = STRIP_WIDTH / 2;
+    let mut visits = vec![0u16; STRIP_WIDTH];
+    let start = x;
+    for bit in 0..num_bits {
+        x = if (value >> bit) & 1 == 1 { (x + 1).min(STRIP_WIDTH - 1) } else { x.saturating_sub(1) };
+        visits[x] = visits[x].saturating_add(1);
+    }
+    Walk { width: STRIP_WIDTH, height: 1, steps: num_bits, visits, start, end: x }
+}
+
+/// Randomart glyphs of a walk, rows joined by newlines
+pub fn generate_omikuji_art(walk: &Walk, palette: ArtPalette) -> String {
+    let symbols = palette.symbols();
+    let (start, end, both) = palette.markers();
+    let mut art = String::with_capacity(walk.visits.len() * 4);
+    for (i, &count) in walk.visits.iter().enumerate() {
+        if i > 0 && i % walk.width == 0 {
+            art.push('\n');
+        }
+        art.push(match (i == walk.start, i == walk.end) {
+            (true, true) => both,
+            (true, false) => start,
+            (false, true) => end,
+            _ => symbols[(count as usize).min(symbols.len() - 1)],
+        });
+    }
+    art
+}
+
+/// Emoji tiles per visit count (0, 1, 2, 3+) with 🎌 start and ⛩️ end markers
+pub fn emoji_art(walk: &Walk) -> String {
+    const TILES: [&str; 4] = ["⬜", "🟨", "🟧", "🟥"];
+    walk.visits
+        .chunks(walk.width)
+        .enumerate()
+        .map(|(y, row)| {
+            row.iter()
+                .enumerate()
+                .map(|(x, &count)| match y * walk.width + x {
+                    i if i == walk.end => "⛩️",
+                    i if i == walk.start => "🎌",
+                    _ => TILES[(count as usize).min(TILES.len() - 1)],
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Color the glyphs of `art` with ANSI 24-bit backgrounds: visit count 1..=5+ picks
+/// `ramp[0..5]`, unvisited cells stay plain
+pub fn heatmap(art: &str, walk: &Walk, ramp: &[&str; 5]) -> String {
+    art.lines()
+        .enumerate()
+        .map(|(y, row)| {
+            row.chars()
+                .enumerate()
+                .map(|(x, glyph)| match walk.visits[y * walk.width + x] {
+                    0 => glyph.to_string(),
+                    count => {
+                        let (r, g, b) = rgb(ramp[(count as usize).min(ramp.len()) - 1]);
+                        format!("\x1b[48;2;{};{};{}m{}\x1b[0m", r, g, b, glyph)
+                    }
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// `+---[label]---+` border line `inner` columns wide, label centered
+fn border(label: &str, inner: usize) -> String {
+    let dashes = inner - display_width(label);
+    let left = dashes / 2;
+    format!("+{}{}{}+\n", "-".repeat(left), label, "-".repeat(dashes - left))
+}
+
+/// OpenSSH-style frame: `+---[hash-omikuji 2026]---+` on top, the grade in the footer.
+/// The frame widens past the art when the labels need the room (the art stays
+/// centered); ASCII mode writes the grade in romaji.
+pub fn framed(art: &str, year: u32, grade: Grade, ascii: bool) -> String {
+    let title = format!("[hash-omikuji {}]", year);
+    let footer = format!("[{}]", if ascii { grade.romaji() } else { grade.kanji() });
+    let art_width = art.lines().map(display_width).max().unwrap_or(0);
+    let inner = art_width.max(display_width(&title) + 2).max(display_width(&footer) + 2);
+
+    let mut output = border(&title, inner);
+    let left = (inner - art_width) / 2;
+    for row in art.lines() {
+        let right = inner - left - display_width(row);
+        output.push_str(&format!("|{}{}{}|\n", " ".repeat(left), row, " ".repeat(right)));
+    }
+    output.push_str(&border(&footer, inner));
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_single_row() {
+        let art = generate_omikuji_art(&walk(&[0xAB; 32], DEFAULT_WIDTH, DEFAULT_HEIGHT), ArtPalette::Simple);
+        assert_eq!(art.chars().count(), 16);
+        assert!(!art.contains('\n'));
+        assert_eq!(art.matches('S').count() + art.matches('X').count(), 1);
+    }
+
+    #[test]
+    fn test_dimensions() {
+        let art = generate_omikuji_art(&walk(&[0x5A; 32], 17, 9), ArtPalette::Classic);
+        let rows: Vec<&str> = art.lines().collect();
+        assert_eq!(rows.len(), 9);
+        assert!(rows.iter().all(|row| row.chars().count() == 17));
+    }
+
+    #[test]
+    fn test_walk_clamps_to_left_edge() {
+        // all-zero bits always step left (and up): the walk ends in the corner
+        let art = generate_omikuji_art(&walk(&[0; 32], 5, 3), ArtPalette::Simple);
+        assert!(art.starts_with('E'));
+        assert_eq!(art.lines().nth(1).unwrap(), "  S  ");
+    }
+
+    #[test]
+    fn test_framed() {
+        let art = generate_omikuji_art(&walk(&[0x5A; 32], 17, 3), ArtPalette::Classic);
+        let frame = framed(&art, 2026, Grade::Daikichi, false);
+        let lines: Vec<&str> = frame.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[0], "+-[hash-omikuji 2026]-+");
+        assert_eq!(lines[4], "+-------[大吉]--------+");
+        assert!(lines[1..4].iter().all(|l| l.starts_with('|') && l.ends_with('|') && display_width(l) == 23));
+
+        let wide = generate_omikuji_art(&walk(&[0x5A; 32], 31, 2), ArtPalette::Simple);
+        let frame = framed(&wide, 2026, Grade::Kyo, true);
+        assert!(frame.starts_with("+------[hash-omikuji 2026]------+\n"));
+        assert!(frame.ends_with("+-------------[kyo]-------------+\n"));
+        assert!(frame.is_ascii());
+    }
+
+    #[test]
+    fn test_emoji_art() {
+        let board = walk(&[0; 32], 5, 3);
+        let art = emoji_art(&board);
+        assert_eq!(art, "⛩️🟨⬜⬜⬜\n⬜⬜🎌⬜⬜\n⬜⬜⬜⬜⬜");
+
+        let options = ArtOptions { width: 5, height: 3, mode: ArtMode::Emoji, ..Default::default() };
+        assert_eq!(render(&[0; 32], &options, 0), art);
+        assert_eq!(options.ascii().mode, ArtMode::Randomart);
+    }
+
+    #[test]
+    fn test_strip() {
+        let strip = strip(0b1111, 4);
+        assert_eq!(strip.visits, vec![0, 0, 0, 0, 0, 1, 1, 2]);
+        assert_eq!(generate_omikuji_art(&strip, ArtPalette::Simple), "    S..E");
+        assert_eq!(generate_omikuji_art(&super::strip(0, 12), ArtPalette::Simple).chars().count(), STRIP_WIDTH);
+    }
+
+    #[test]
+    fn test_maze() {
+        let maze = maze(&[7; 32], 15, 9, 2);
+        let rows: Vec<Vec<char>> = maze.lines().map(|row| row.chars().collect()).collect();
+        assert_eq!(rows.len(), 9);
+        assert!(rows.iter().all(|row| row.len() == 15));
+        assert_eq!(rows[5][7], 'S');
+        // lucky direction → opens the right wall level with the start row
+        assert_eq!(rows[5][14], 'E');
+        // a perfect maze over 7x4 cells has every cell open and 27 carved passages
+        let open = maze.chars().filter(|&c| c == ' ' || c == 'S').count();
+        assert_eq!(open, 7 * 4 + 27);
+    }
+
+    #[test]
+    fn test_maze_default_size() {
+        let options = ArtOptions { mode: ArtMode::Maze, ..Default::default() };
+        let maze = render(&[1; 32], &options, 0);
+        assert_eq!(maze.lines().count(), MAZE_SIZE.1);
+        assert!(maze.lines().next().unwrap().contains('E'));
+        assert_ne!(maze, render(&[2; 32], &options, 0));
+    }
+
+    #[test]
+    fn test_mandala_symmetric() {
+        let art = mandala(&[0x9C; 32], 17, 9, ArtPalette::Classic);
+        let rows: Vec<Vec<char>> = art.lines().map(|row| row.chars().collect()).collect();
+        assert_eq!(rows.len(), 9);
+        for row in &rows {
+            assert_eq!(row.len(), 17);
+            assert!(row.iter().eq(row.iter().rev()));
+        }
+        assert!(rows.iter().eq(rows.iter().rev()));
+
+        let options = ArtOptions { mode: ArtMode::Mandala, ..Default::default() };
+        assert_eq!(render(&[0x9C; 32], &options, 0), mandala(&[0x9C; 32], 17, 9, ArtPalette::Simple));
+    }
+
+    #[test]
+    fn test_legend() {
+        assert_eq!(legend(&ArtOptions::default()), "S start  E end  X start=end  |  visits: . 1  + 2  # 3+");
+        let classic = ArtOptions { palette: ArtPalette::Classic, ..Default::default() };
+        assert!(legend(&classic).starts_with("S start  E end  |  visits: . 1  o 2"));
+        assert!(legend(&classic).ends_with("^ 14+"));
+    }
+
+    #[test]
+    fn test_walk_steps() {
+        assert_eq!(walk(&[0; 32], 16, 1).steps, 256);
+        assert_eq!(walk(&[0; 32], 17, 9).steps, 128);
+        assert_eq!(walk(&[0xFF; 32], 17, 9).visits.iter().map(|&v| v as usize).sum::<usize>(), 128);
+    }
+
+    #[test]
+    fn test_heatmap() {
+        let board = walk(&[0; 32], 5, 3);
+        let art = generate_omikuji_art(&board, ArtPalette::Simple);
+        let ramp = ["#000001", "#000002", "#000003", "#000004", "#0000FF"];
+        let colored = heatmap(&art, &board, &ramp);
+        // the corner is visited 127 times and takes the last ramp color; the start cell is plain
+        assert!(colored.starts_with("\x1b[48;2;0;0;255mE\x1b[0m"));
+        assert_eq!(colored.lines().nth(1).unwrap(), "  S  ");
+        assert!(colored.contains("\x1b[48;2;0;0;1m.\x1b[0m"));
+        assert_eq!(display_width(colored.lines().next().unwrap()), 5);
+    }
+
+    #[test]
+    fn test_palette_glyphs_only() {
+        let art = generate_omikuji_art(&walk(&[0x3C; 32], 8, 4), ArtPalette::Blocks);
+        let allowed: Vec<char> = ArtPalette::Blocks.symbols().iter().copied().chain(['S', 'E', 'X', '\n']).collect();
+        assert!(art.chars().all(|c| allowed.contains(&c)));
+    }
+}
@@ -0,0 +1,236 @@
+//! `batch`: draw a stream of seeds and write one JSON fortune per line, in input order,
+//! spreading the hashing over all cores with rayon. Input is read one block at a time,
+//! so memory stays bounded however long the stream is, and a slow reader downstream
+//! stalls the reading upstream. Files are memory-mapped and their seeds drawn in place.
+
+use crate::builder::FortuneBuilder;
+use crate::error::Error;
+use crate::hash::SeedHasher;
+use crate::output::Fortune;
+use crate::team;
+use rayon::prelude::*;
+use rayon::{ThreadPool, ThreadPoolBuilder};
+use std::fs::File;
+use std::io::{BufRead, BufReader, ErrorKind, Write};
+use std::path::Path;
+use std::time::Duration;
+
+/// Seeds drawn per block: lines are written and flushed as each block finishes, and
+/// only one block of seeds and fortunes is held in memory
+pub const BLOCK: usize = 4096;
+
+/// A pool of `jobs` threads (default: one per core); None where threads cannot be
+/// started (e.g. WASI), in which case drawing runs on the calling thread
+pub fn pool(jobs: Option<usize>) -> Option<ThreadPool> {
+    ThreadPoolBuilder::new().num_threads(jobs.unwrap_or(0)).build().ok()
+}
+
+/// `Speed   : 1.52M hashes/s (2000000 in 1.31s, 8 threads)`
+pub fn rate(count: u64, elapsed: Duration, unit: &str, threads: usize) -> String {
+    let per_second = count as f64 / elapsed.as_secs_f64().max(1e-9);
+    let per_second = match per_second {
+        r if r >= 1e6 => format!("{:.2}M", r / 1e6),
+        r if r >= 1e3 => format!("{:.1}k", r / 1e3),
+        r => format!("{:.0}", r),
+    };
+    let threads = if threads == 1 { "1 thread".to_string() } else { format!("{} threads", threads) };
+    format!("Speed   : {} {}/s ({} in {:.2}s, {})", per_second, unit, count, elapsed.as_secs_f64(), threads)
+}
+
+/// Fortunes of `seeds`, in the same order
+pub fn draw_all(builder: &FortuneBuilder, seeds: &[String], pool: Option<&ThreadPool>) -> Vec<Fortune> {
+    let draw = |hasher: &mut SeedHasher, seed: &String| builder.draw_seed(hasher, seed);
+    match pool {
+        Some(pool) => pool.install(|| seeds.par_iter().map_init(|| builder.hasher(), draw).collect()),
+        None => {
+            let mut hasher = builder.hasher();
+            seeds.iter().map(|seed| draw(&mut hasher, seed)).collect()
+        }
+    }
+}
+
+/// Append the JSON line of every seed's fortune to `buffer`, serialized straight into
+/// it without an intermediate string per record; seeds are hashed through one reused buffer
+fn render<S: AsRef<str>>(builder: &FortuneBuilder, seeds: &[S], buffer: &mut Vec<u8>) {
+    buffer.clear();
+    let mut hasher = builder.hasher();
+    for seed in seeds {
+        serde_json::to_writer(&mut *buffer, &builder.draw_seed(&mut hasher, seed.as_ref())).unwrap();
+        buffer.push(b'\n');
+    }
+}
+
+/// Render `seeds` into `buffers` (one per chunk, reused between blocks), in parallel
+/// when there is a pool; the concatenated buffers hold the lines in input order
+fn render_all<S: AsRef<str> + Sync>(builder: &FortuneBuilder, seeds: &[S], pool: Option<&ThreadPool>, buffers: &mut Vec<Vec<u8>>) {
+    let Some(pool) = pool else {
+        buffers.resize_with(1, Vec::new);
+        return render(builder, seeds, &mut buffers[0]);
+    };
+    let chunks = pool.current_num_threads() * 4;
+    buffers.resize_with(chunks, Vec::new);
+    let size = seeds.len().div_ceil(chunks).max(1);
+    pool.install(|| {
+        buffers.par_iter_mut().enumerate().for_each(|(i, buffer)| {
+            let start = (i * size).min(seeds.len());
+            render(builder, &seeds[start..(start + size).min(seeds.len())], buffer)
+        })
+    });
+}
+
+/// Read up to BLOCK seeds from `input` into `seeds` (blank lines and `#` comments
+/// skipped); false at the end of the input
+fn read_block(input: &mut impl BufRead, seeds: &mut Vec<String>, line: &mut String) -> Result<bool, Error> {
+    seeds.clear();
+    while seeds.len() < BLOCK {
+        line.clear();
+        let read = input.read_line(line).map_err(|e| match e.kind() {
+            ErrorKind::InvalidData => Error::InvalidSeed("a line of the input is not valid UTF-8".to_string()),
+            _ => Error::Io(format!("Failed to read the input: {}", e)),
+        })?;
+        if read == 0 {
+            return Ok(false);
+        }
+        if let Some(seed) = team::parse_seed(line) {
+            seeds.push(seed.to_string());
+        }
+    }
+    Ok(true)
+}
+
+/// Draw `seeds` and write their lines to `out`, flushed; false when the reader has
+/// closed the pipe
+fn write_block<S: AsRef<str> + Sync>(
+    builder: &FortuneBuilder,
+    seeds: &[S],
+    pool: Option<&ThreadPool>,
+    buffers: &mut Vec<Vec<u8>>,
+    out: &mut impl Write,
+) -> Result<bool, Error> {
+    render_all(builder, seeds, pool, buffers);
+    match buffers.iter().try_for_each(|buffer| out.write_all(buffer)).and_then(|()| out.flush()) {
+        Ok(()) => Ok(true),
+        Err(e) if e.kind() == ErrorKind::BrokenPipe => Ok(false),
+        Err(e) => Err(Error::Io(format!("Failed to write the output: {}", e))),
+    }
+}
+
+/// Write the JSON line of every seed of `input` to `out`, flushing after each block;
+/// returns the number of fortunes written. A closed pipe (`| head`) ends the output
+/// quietly.
+pub fn run(builder: &FortuneBuilder, mut input: impl BufRead, pool: Option<&ThreadPool>, out: &mut impl Write) -> Result<u64, Error> {
+    let (mut seeds, mut line, mut buffers) = (Vec::with_capacity(BLOCK), String::new(), Vec::new());
+    let mut count = 0;
+    loop {
+        let more = read_block(&mut input, &mut seeds, &mut line)?;
+        if !write_block(builder, &seeds, pool, &mut buffers, out)? {
+            return Ok(count);
+        }
+        count += seeds.len() as u64;
+        if !more {
+            return Ok(count);
+        }
+    }
+}
+
+/// [`run`] over input already in memory: the seeds are borrowed from `bytes`, never copied
+pub fn run_mapped(builder: &FortuneBuilder, bytes: &[u8], pool: Option<&ThreadPool>, out: &mut impl Write) -> Result<u64, Error> {
+    let (mut seeds, mut buffers) = (Vec::with_capacity(BLOCK), Vec::new());
+    let mut count = 0;
+    let mut lines = bytes.split(|&b| b == b'\n').enumerate().peekable();
+    while lines.peek().is_some() {
+        seeds.clear();
+        for (number, line) in lines.by_ref() {
+            let line = std::str::from_utf8(line).map_err(|_| Error::InvalidSeed(format!("line {} is not valid UTF-8", number + 1)))?;
+            if let Some(seed) = team::parse_seed(line) {
+                seeds.push(seed);
+                if seeds.len() == BLOCK {
+                    break;
+                }
+            }
+        }
+        if !write_block(builder, &seeds, pool, &mut buffers, out)? {
+            return Ok(count);
+        }
+        count += seeds.len() as u64;
+    }
+    Ok(count)
+}
+
+/// [`run`] over a file, memory-mapped so that a list larger than RAM is paged in as it
+/// is drawn instead of being loaded; files that cannot be mapped (pipes, WASI) are read
+/// through a buffer
+pub fn run_file(builder: &FortuneBuilder, path: &Path, pool: Option<&ThreadPool>, out: &mut impl Write) -> Result<u64, Error> {
+    let file = File::open(path).map_err(|e| Error::Io(format!("Failed to read {}: {}", path.display(), e)))?;
+    #[cfg(not(target_os = "wasi"))]
+    {
+        // SAFETY: the map is only read; as with any mmap reader, truncating the file
+        // while it is drawn ends the process with SIGBUS instead of an error
+        if let Ok(map) = unsafe { memmap2::Mmap::map(&file) } {
+            #[cfg(unix)]
+            let _ = map.advise(memmap2::Advice::Sequential);
+            return run_mapped(builder, &map, pool, out);
+        }
+    }
+    run(builder, BufReader::new(file), pool, out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parallel_draws_keep_order() {
+        let builder = Fortune::builder().year(2026);
+        let seeds: Vec<String> = (0..50).map(|i| format!("seed-{}", i)).collect();
+        let sequential = draw_all(&builder, &seeds, None);
+        let parallel = draw_all(&builder, &seeds, pool(Some(4)).as_ref());
+        assert_eq!(parallel, sequential);
+        assert_eq!(parallel[7], builder.clone().seed("seed-7").draw());
+
+        let expected: String = sequential.iter().map(|f| serde_json::to_string(f).unwrap() + "\n").collect();
+        let mut buffers = Vec::new();
+        for pool in [None, pool(Some(4))] {
+            render_all(&builder, &seeds, pool.as_ref(), &mut buffers);
+            assert_eq!(buffers.concat(), expected.as_bytes());
+        }
+        render_all(&builder, &seeds[..3], pool(Some(4)).as_ref(), &mut buffers);
+        assert_eq!(buffers.concat().split(|&b| b == b'\n').count(), 4);
+    }
+
+    #[test]
+    fn test_run_reads_blocks() {
+        let input: String = (0..=BLOCK).map(|i| format!("# {}\nseed-{}\n\n", i, i)).collect();
+        let (mut reader, mut seeds, mut line) = (input.as_bytes(), Vec::new(), String::new());
+        assert!(read_block(&mut reader, &mut seeds, &mut line).unwrap());
+        assert_eq!((seeds.len(), seeds[BLOCK - 1].as_str()), (BLOCK, "seed-4095"));
+        assert!(!read_block(&mut reader, &mut seeds, &mut line).unwrap());
+        assert_eq!(seeds, ["seed-4096"]);
+
+        let builder = Fortune::builder().year(2026);
+        let mut out = Vec::new();
+        let count = run(&builder, "alice\n# skip\n\n  bob  \n".as_bytes(), pool(Some(2)).as_ref(), &mut out).unwrap();
+        let lines: Vec<&str> = std::str::from_utf8(&out).unwrap().lines().collect();
+        assert_eq!((count, lines.len()), (2, 2));
+        let last: Fortune = serde_json::from_str(lines[1]).unwrap();
+        assert_eq!(last, builder.clone().seed("bob").draw());
+
+        let mut mapped = Vec::new();
+        assert_eq!(run_mapped(&builder, b"alice\r\n# skip\n\n  bob  ", None, &mut mapped), Ok(2));
+        assert_eq!(mapped, out);
+        assert_eq!(run_mapped(&builder, b"alice\n\xff\n", None, &mut mapped), Err(Error::InvalidSeed("line 2 is not valid UTF-8".to_string())));
+
+        let path = std::env::temp_dir().join(format!("hash-omikuji-batch-{}.txt", std::process::id()));
+        std::fs::write(&path, "alice\nbob\n").unwrap();
+        let mut from_file = Vec::new();
+        assert_eq!(run_file(&builder, &path, pool(Some(2)).as_ref(), &mut from_file), Ok(2));
+        assert_eq!(from_file, out);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_rate() {
+        assert_eq!(rate(2_000_000, Duration::from_millis(1250), "hashes", 8), "Speed   : 1.60M hashes/s (2000000 in 1.25s, 8 threads)");
+        assert_eq!(rate(300, Duration::from_secs(2), "fortunes", 1), "Speed   : 150 fortunes/s (300 in 2.00s, 1 thread)");
+    }
+}
@@ -0,0 +1,71 @@
+//! `cargo omikuji`: the year's fortune for the current crate.
+
+use chrono::{Datelike, Local};
+use clap::Parser;
+use hash_omikuji::project::{self, Package};
+use hash_omikuji::Fortune;
+use std::path::PathBuf;
+
+#[derive(Parser)]
+#[command(name = "cargo", bin_name = "cargo")]
+enum Cargo {
+    /// Draw this year's fortune for the current crate
+    Omikuji(OmikujiArgs),
+}
+
+#[derive(clap::Args)]
+#[command(version)]
+struct OmikujiArgs {
+    /// Path to Cargo.toml (default: nearest one above the current directory)
+    #[arg(long, value_name = "PATH")]
+    manifest_path: Option<PathBuf>,
+
+    /// Seed from the git HEAD commit instead of the package version
+    #[arg(long, default_value_t = false)]
+    git: bool,
+
+    /// Year to draw for (default: the current year)
+    #[arg(long, value_name = "YYYY")]
+    year: Option<u32>,
+
+    /// Output as JSON
+    #[arg(long, default_value_t = false)]
+    json: bool,
+}
+
+fn run(args: &OmikujiArgs) -> Result<(), String> {
+    let manifest = match args.manifest_path {
+        Some(ref path) => path.clone(),
+        None => std::env::current_dir()
+            .ok()
+            .and_then(|dir| project::find_manifest(&dir))
+            .ok_or("Could not find Cargo.toml in this directory or any parent; use --manifest-path")?,
+    };
+    let package = Package::from_manifest(&manifest)?;
+    let head = if args.git {
+        Some(project::git_head(manifest.parent().unwrap_or(&manifest))?)
+    } else {
+        None
+    };
+    let year = args.year.unwrap_or_else(|| Local::now().year() as u32);
+    let fortune = Fortune::builder()
+        .year(year)
+        .seed(&package.seed(head.as_deref()))
+        .draw()
+        .with_category_labels(&project::project_labels());
+    if args.json {
+        println!("{}", fortune.format_json());
+    } else {
+        print!("{}", project::format_report(&fortune, &package));
+    }
+    Ok(())
+}
+
+fn main() {
+    hash_omikuji::console::init();
+    let Cargo::Omikuji(args) = Cargo::parse();
+    if let Err(msg) = run(&args) {
+        eprintln!("{}", msg);
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,81 @@
+//! `--birthday MM-DD`: a personal fortune drawn on the user's birthday. The birthday is
+//! mixed into the derivation, so the birthday draw never repeats the New Year one.
+
+use chrono::{Datelike, NaiveDate};
+use serde::Deserialize;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct Birthday {
+    month: u32,
+    day: u32,
+}
+
+impl FromStr for Birthday {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, String> {
+        let invalid = || format!("invalid birthday {:?} (expected MM-DD, e.g. 03-14)", text);
+        let (month, day) = text.trim().split_once('-').ok_or_else(invalid)?;
+        let (month, day) = (month.parse().map_err(|_| invalid())?, day.parse().map_err(|_| invalid())?);
+        // 2000 is a leap year, so 02-29 is accepted
+        NaiveDate::from_ymd_opt(2000, month, day).ok_or_else(invalid)?;
+        Ok(Self { month, day })
+    }
+}
+
+impl TryFrom<String> for Birthday {
+    type Error = String;
+
+    fn try_from(text: String) -> Result<Self, String> {
+        text.parse()
+    }
+}
+
+impl fmt::Display for Birthday {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:02}-{:02}", self.month, self.day)
+    }
+}
+
+impl Birthday {
+    /// Whether `date` is the birthday; a February 29th birthday falls on the 28th in
+    /// common years
+    pub fn matches(self, date: NaiveDate) -> bool {
+        let leap = NaiveDate::from_ymd_opt(date.year(), 2, 29).is_some();
+        (date.month(), date.day()) == (self.month, self.day) || (!leap && (self.month, self.day) == (2, 29) && (date.month(), date.day()) == (2, 28))
+    }
+
+    /// Salt of the birthday draw's derivation
+    pub fn domain(self) -> String {
+        format!("birthday-{}", self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(text: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(text, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_match() {
+        let birthday: Birthday = "3-14".parse().unwrap();
+        assert_eq!(birthday.to_string(), "03-14");
+        assert_eq!(birthday.domain(), "birthday-03-14");
+        assert!(birthday.matches(date("2026-03-14")));
+        assert!(!birthday.matches(date("2026-03-15")));
+        assert!("13-01".parse::<Birthday>().is_err());
+        assert!("02-30".parse::<Birthday>().is_err());
+        assert!("0314".parse::<Birthday>().is_err());
+
+        let leap_day: Birthday = "02-29".parse().unwrap();
+        assert!(leap_day.matches(date("2028-02-29")));
+        assert!(!leap_day.matches(date("2028-02-28")));
+        assert!(leap_day.matches(date("2026-02-28")));
+    }
+}
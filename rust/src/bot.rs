@@ -0,0 +1,222 @@
+//! `bot`: a Matrix bot that answers `!omikuji [name]` in one room with a compact fortune.
+//! Each sender gets one draw per year, tracked in a state file that stores only
+//! fingerprints of the user ids.
+
+use crate::builder::FortuneBuilder;
+use crate::output::{seed_fingerprint, Fortune};
+use chrono::Datelike;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashSet;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+/// Environment variable holding the bot account's access token
+pub const TOKEN_ENV: &str = "HASH_OMIKUJI_MATRIX_TOKEN";
+
+/// Long-poll timeout of /sync
+const SYNC_TIMEOUT_MS: u32 = 30_000;
+
+/// `!omikuji` gives `Some(None)`, `!omikuji alice` gives `Some(Some("alice"))`, anything else `None`
+pub fn parse_command(body: &str) -> Option<Option<&str>> {
+    let rest = body.trim().strip_prefix("!omikuji")?;
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    Some(Some(rest.trim()).filter(|name| !name.is_empty()))
+}
+
+/// `alice: 🎍 2026 中吉 🎉 lucky number 165 · 60/100 · <advice>`
+pub fn reply(name: &str, fortune: &Fortune) -> String {
+    format!("{}: {} · {}/100 · {}", name, crate::hook::fortune_line(fortune), fortune.overall_score, fortune.advice)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+struct Draw {
+    year: u32,
+    user: String,
+}
+
+/// Who already drew this year: JSON lines of year and user fingerprint
+pub struct DrawLog {
+    path: PathBuf,
+    draws: HashSet<Draw>,
+}
+
+impl DrawLog {
+    /// Default store: `~/.local/share/hash-omikuji/bot.jsonl` (platform data dir)
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::data_dir().map(|dir| dir.join("hash-omikuji").join("bot.jsonl"))
+    }
+
+    /// Read the log; a missing file is empty
+    pub fn open(path: &Path) -> Result<Self, String> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+        };
+        let draws = text
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| serde_json::from_str(line).map_err(|e| format!("Invalid entry in {}: {}", path.display(), e)))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { path: path.to_path_buf(), draws })
+    }
+
+    /// Record a draw by `user` in `year`; false if they already drew that year
+    pub fn record(&mut self, year: u32, user: &str) -> Result<bool, String> {
+        let draw = Draw { year, user: seed_fingerprint(user) };
+        if self.draws.contains(&draw) {
+            return Ok(false);
+        }
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(|e| format!("Failed to open {}: {}", self.path.display(), e))?;
+        writeln!(file, "{}", serde_json::to_string(&draw).unwrap())
+            .map_err(|e| format!("Failed to write {}: {}", self.path.display(), e))?;
+        self.draws.insert(draw);
+        Ok(true)
+    }
+}
+
+/// Percent-encode a URL path segment (room ids contain `!` and `:`)
+fn encode(segment: &str) -> String {
+    segment
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// Client-server API calls with the bot's access token
+struct Client {
+    homeserver: String,
+    token: String,
+}
+
+impl Client {
+    fn url(&self, path: &str) -> String {
+        format!("{}/_matrix/client/v3{}", self.homeserver.trim_end_matches('/'), path)
+    }
+
+    fn call(&self, request: ureq::Request, body: Option<Value>) -> Result<Value, String> {
+        let request = request.set("Authorization", &format!("Bearer {}", self.token));
+        let response = match body {
+            Some(body) => request.set("Content-Type", "application/json").send_string(&body.to_string()),
+            None => request.call(),
+        };
+        match response {
+            Ok(response) => {
+                let text = response.into_string().map_err(|e| format!("Failed to read the homeserver's reply: {}", e))?;
+                serde_json::from_str(&text).map_err(|e| format!("Invalid reply from the homeserver: {}", e))
+            }
+            Err(ureq::Error::Status(code, response)) => {
+                Err(format!("Homeserver answered {}: {}", code, response.into_string().unwrap_or_default().trim()))
+            }
+            Err(ureq::Error::Transport(e)) => Err(format!("Failed to reach the homeserver: {}", e.kind())),
+        }
+    }
+
+    fn sync(&self, filter: &str, since: Option<&str>) -> Result<Value, String> {
+        let mut request = ureq::get(&self.url("/sync")).query("filter", filter);
+        if let Some(since) = since {
+            request = request.query("since", since).query("timeout", &SYNC_TIMEOUT_MS.to_string());
+        }
+        self.call(request.timeout(Duration::from_millis(SYNC_TIMEOUT_MS as u64 + 10_000)), None)
+    }
+}
+
+/// Join `room` and answer commands until an API call fails. Messages sent before
+/// the bot started are ignored; the year is the current one unless `year` is given.
+pub fn run(homeserver: &str, room: &str, token: &str, builder: &FortuneBuilder, year: Option<u32>, log: &mut DrawLog) -> Result<(), String> {
+    let client = Client { homeserver: homeserver.to_string(), token: token.to_string() };
+    let me = client.call(ureq::get(&client.url("/account/whoami")), None)?["user_id"].as_str().unwrap_or_default().to_string();
+    let joined = client.call(ureq::post(&client.url(&format!("/join/{}", encode(room)))), Some(json!({})))?;
+    let room_id = joined["room_id"].as_str().ok_or("Homeserver did not return the joined room id")?.to_string();
+    let filter = json!({ "room": { "rooms": [room_id], "timeline": { "limit": 50 } }, "presence": { "types": [] } }).to_string();
+
+    let mut since = client.sync(&filter, None)?["next_batch"].as_str().map(str::to_string);
+    let mut txn = 0u64;
+    loop {
+        let batch = match client.sync(&filter, since.as_deref()) {
+            Ok(batch) => batch,
+            Err(msg) if msg.starts_with("Failed to reach") => {
+                eprintln!("WARNING: {}", msg);
+                thread::sleep(Duration::from_secs(5));
+                continue;
+            }
+            Err(msg) => return Err(msg),
+        };
+        since = batch["next_batch"].as_str().map(str::to_string).or(since);
+        let events = batch["rooms"]["join"][&room_id]["timeline"]["events"].as_array().cloned().unwrap_or_default();
+        for event in events {
+            let sender = event["sender"].as_str().unwrap_or_default();
+            if event["type"] != "m.room.message" || sender == me {
+                continue;
+            }
+            let Some(name) = event["content"]["body"].as_str().and_then(parse_command) else { continue };
+            let year = year.unwrap_or_else(|| chrono::Local::now().year() as u32);
+            let text = if log.record(year, sender)? {
+                let name = name.unwrap_or(sender);
+                reply(name, &builder.clone().year(year).seed(name).draw())
+            } else {
+                format!("{}: you already drew your {} fortune; see you on January 1st, {}", sender, year, year + 1)
+            };
+            txn += 1;
+            let path = format!("/rooms/{}/send/m.room.message/omikuji-{}-{}", encode(&room_id), std::process::id(), txn);
+            client.call(ureq::put(&client.url(&path)), Some(json!({ "msgtype": "m.text", "body": text })))?;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_command() {
+        assert_eq!(parse_command("!omikuji"), Some(None));
+        assert_eq!(parse_command("  !omikuji  alice "), Some(Some("alice")));
+        assert_eq!(parse_command("!omikujis"), None);
+        assert_eq!(parse_command("hello !omikuji"), None);
+    }
+
+    #[test]
+    fn test_reply() {
+        let fortune = Fortune::builder().year(2026).seed("alice").draw();
+        let text = reply("alice", &fortune);
+        assert!(text.starts_with(&format!("alice: 🎍 2026 {}", fortune.grade)));
+        assert!(text.ends_with(&fortune.advice));
+    }
+
+    #[test]
+    fn test_draw_log_once_per_year() {
+        let path = std::env::temp_dir().join(format!("hash-omikuji-bot-{}.jsonl", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let mut log = DrawLog::open(&path).unwrap();
+        assert!(log.record(2026, "@alice:example.org").unwrap());
+        assert!(!log.record(2026, "@alice:example.org").unwrap());
+        assert!(log.record(2027, "@alice:example.org").unwrap());
+
+        let mut reopened = DrawLog::open(&path).unwrap();
+        assert!(!reopened.record(2026, "@alice:example.org").unwrap());
+        assert!(!fs::read_to_string(&path).unwrap().contains("alice"));
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_encode() {
+        assert_eq!(encode("!abc:example.org"), "%21abc%3Aexample.org");
+    }
+}
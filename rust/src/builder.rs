@@ -0,0 +1,179 @@
+//! Fluent configuration of a draw for library users, with the binary's defaults.
+
+use crate::art::ArtOptions;
+use crate::fingerprint::FingerprintFormat;
+use crate::hash::{Algo, HashBits, Layout, SeedHasher};
+use crate::lang::Lang;
+use crate::luck::{CategoryWeights, GradeWeights};
+use crate::output::Fortune;
+use chrono::{Datelike, Local};
+use zeroize::Zeroizing;
+
+/// Seed used when none is given (the binary falls back to `username@hostname`)
+pub const DEFAULT_SEED: &str = "anonymous";
+
+/// Settings for one draw; see [`Fortune::builder`]
+#[derive(Debug, Clone, Default)]
+pub struct FortuneBuilder {
+    year: Option<u32>,
+    seeds: Vec<Zeroizing<String>>,
+    layout: Layout,
+    algo: Algo,
+    lang: Lang,
+    grade_weights: GradeWeights,
+    category_weights: CategoryWeights,
+    art: ArtOptions,
+    fingerprint_format: FingerprintFormat,
+    salt: Option<Zeroizing<String>>,
+}
+
+impl Fortune {
+    /// Start configuring a draw: current year, SHA-256, layout v1, English
+    pub fn builder() -> FortuneBuilder {
+        FortuneBuilder::default()
+    }
+}
+
+impl FortuneBuilder {
+    pub fn year(mut self, year: u32) -> Self {
+        self.year = Some(year);
+        self
+    }
+
+    /// Replace the seed (and any shared seeds added with [`with`](Self::with))
+    pub fn seed(mut self, seed: &str) -> Self {
+        self.seeds = vec![Zeroizing::new(seed.to_string())];
+        self
+    }
+
+    /// Add another seed for a shared fortune, like `--with`
+    pub fn with(mut self, seed: &str) -> Self {
+        if self.seeds.is_empty() {
+            self.seeds.push(Zeroizing::new(DEFAULT_SEED.to_string()));
+        }
+        self.seeds.push(Zeroizing::new(seed.to_string()));
+        self
+    }
+
+    pub fn layout(mut self, layout: Layout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    pub fn algo(mut self, algo: Algo) -> Self {
+        self.algo = algo;
+        self
+    }
+
+    pub fn lang(mut self, lang: Lang) -> Self {
+        self.lang = lang;
+        self
+    }
+
+    pub fn grade_weights(mut self, weights: GradeWeights) -> Self {
+        self.grade_weights = weights;
+        self
+    }
+
+    pub fn category_weights(mut self, weights: CategoryWeights) -> Self {
+        self.category_weights = weights;
+        self
+    }
+
+    pub fn art(mut self, options: ArtOptions) -> Self {
+        self.art = options;
+        self
+    }
+
+    pub fn fingerprint_format(mut self, format: FingerprintFormat) -> Self {
+        self.fingerprint_format = format;
+        self
+    }
+
+    /// Mix a secret into the derivation, giving a separate fortune universe
+    pub fn salt(mut self, salt: &str) -> Self {
+        self.salt = Some(Zeroizing::new(salt.to_string()));
+        self
+    }
+
+    pub fn draw(&self) -> Fortune {
+        let year = self.year.unwrap_or_else(|| Local::now().year() as u32);
+        let seeds: Vec<&str> = match self.seeds.as_slice() {
+            [] => vec![DEFAULT_SEED],
+            seeds => seeds.iter().map(|s| s.as_str()).collect(),
+        };
+        let hash = HashBits::from_seeds_salted(year, &seeds, self.algo, self.salt.as_ref().map(|s| s.as_str()));
+        let combined = Zeroizing::new(crate::hash::combine_seeds(&seeds));
+        self.finish(&hash, year, &combined)
+    }
+
+    /// A hasher for drawing many single seeds with these settings, see [`draw_seed`](Self::draw_seed)
+    pub fn hasher(&self) -> SeedHasher {
+        let year = self.year.unwrap_or_else(|| Local::now().year() as u32);
+        SeedHasher::salted(year, self.algo, self.salt.as_ref().map(|s| s.as_str()))
+    }
+
+    /// The same fortune as `self.clone().seed(seed).draw()`, hashed through `hasher`
+    /// (from [`hasher`](Self::hasher)) without cloning the builder
+    pub fn draw_seed(&self, hasher: &mut SeedHasher, seed: &str) -> Fortune {
+        self.finish(&hasher.hash(seed), hasher.year(), seed)
+    }
+
+    fn finish(&self, hash: &HashBits, year: u32, seed: &str) -> Fortune {
+        Fortune::from_hash(hash, year, seed)
+            .with_grade_weights(hash, &self.grade_weights)
+            .with_category_weights(&self.category_weights)
+            .with_lang(hash, self.lang)
+            .with_art(hash, &self.art)
+            .with_algo(self.algo)
+            .with_layout(hash, self.layout)
+            .with_fingerprint_format(hash, self.fingerprint_format)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults_match_binary() {
+        let hash = HashBits::from_seed(2026, "alice");
+        let expected = Fortune::from_hash(&hash, 2026, "alice");
+        assert_eq!(Fortune::builder().year(2026).seed("alice").draw(), expected);
+    }
+
+    #[test]
+    fn test_configured_draw() {
+        let fortune = Fortune::builder().year(2026).seed("alice").layout(Layout::V2).algo(Algo::Blake3).draw();
+        let hash = HashBits::from_seeds_with(2026, &["alice"], Algo::Blake3);
+        assert_eq!(fortune.fingerprint, hash.hex_string());
+        assert_eq!(fortune.algo, Algo::Blake3);
+        assert_eq!(fortune.layout, Layout::V2);
+    }
+
+    #[test]
+    fn test_shared_seeds() {
+        let ab = Fortune::builder().year(2026).seed("alice").with("bob").draw();
+        let ba = Fortune::builder().year(2026).seed("bob").with("alice").draw();
+        assert_eq!(ab.fingerprint, ba.fingerprint);
+    }
+
+    #[test]
+    fn test_draw_seed_matches_draw() {
+        for builder in [Fortune::builder().year(2026), Fortune::builder().year(2026).algo(Algo::Blake3).salt("rustaceans").seed("ignored")] {
+            let mut hasher = builder.hasher();
+            for seed in ["alice", "bob", ""] {
+                assert_eq!(builder.draw_seed(&mut hasher, seed), builder.clone().seed(seed).draw());
+            }
+        }
+    }
+
+    #[test]
+    fn test_salt_separates_universes() {
+        let plain = Fortune::builder().year(2026).seed("alice").draw();
+        let salted = Fortune::builder().year(2026).seed("alice").salt("rustaceans").draw();
+        assert_ne!(salted.fingerprint, plain.fingerprint);
+        assert_eq!(salted, Fortune::builder().year(2026).salt("rustaceans").seed("alice").draw());
+        assert_ne!(salted.fingerprint, Fortune::builder().year(2026).seed("alice").salt("gophers").draw().fingerprint);
+    }
+}
@@ -0,0 +1,102 @@
+//! Cache of drawn fortunes, so repeated draws (prompt and MOTD hooks, hourly jobs,
+//! Argon2 `--effort`) skip the derivation. One canonical JSON file per seed, year,
+//! layout, algo, effort level and salt (`--birthday`); the options that only change the presentation are
+//! re-applied to the cached digest.
+
+use crate::effort::EffortParams;
+use crate::hash::{Algo, HashBits, Layout};
+use crate::output::{seed_fingerprint, Fortune};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Default directory: `~/.cache/hash-omikuji/fortunes` (`$XDG_CACHE_HOME` on Linux)
+pub fn cache_dir() -> Option<PathBuf> {
+    dirs::cache_dir().map(|dir| dir.join("hash-omikuji").join("fortunes"))
+}
+
+/// File name of a draw: `2026-v2-sha256-e3-<seed digest>.json`; the seed itself is never written to disk
+pub fn key(seed: &str, year: u32, layout: Layout, algo: Algo, effort: Option<&EffortParams>, salt: Option<&str>) -> String {
+    let layout = if layout == Layout::V2 { "v2" } else { "v1" };
+    let algo = if algo == Algo::Blake3 { "blake3" } else { "sha256" };
+    let effort = effort.map_or(String::new(), |params| format!("-e{}", params.level));
+    let input = match salt {
+        Some(salt) => format!("{}-{}-{}", year, seed, salt),
+        None => format!("{}-{}", year, seed),
+    };
+    let digest = Sha256::new().chain_update(input).finalize();
+    format!("{}-{}-{}{}-{}.json", year, layout, algo, effort, crate::fingerprint::hex(&digest[..8]))
+}
+
+/// The fortune stored for a digest: default presentation, hex fingerprint
+pub fn canonical(hash: &HashBits, year: u32, seed: &str, layout: Layout, algo: Algo, effort: Option<&EffortParams>) -> Fortune {
+    let fortune = Fortune::from_hash(hash, year, seed).with_algo(algo).with_layout(hash, layout);
+    match effort {
+        Some(params) => fortune.with_effort(params.clone()),
+        None => fortune,
+    }
+}
+
+/// The digest of the cached draw, if there is one and it was drawn from `seed`
+/// with the same settings
+pub fn load(dir: &Path, key: &str, seed: &str, year: u32, layout: Layout, algo: Algo) -> Option<HashBits> {
+    let text = fs::read_to_string(dir.join(key)).ok()?;
+    let fortune: Fortune = serde_json::from_str(&text).ok()?;
+    let matches = fortune.year == year
+        && fortune.layout == layout
+        && fortune.algo == algo
+        && fortune.seed.strip_prefix("device:") == Some(seed_fingerprint(seed).as_str());
+    matches.then(|| HashBits::from_hex(&fortune.fingerprint)).flatten()
+}
+
+/// Best effort: a draw must never fail because the cache is not writable
+pub fn store(dir: &Path, key: &str, fortune: &Fortune) {
+    let _ = fs::create_dir_all(dir);
+    let _ = fs::write(dir.join(key), serde_json::to_string(fortune).unwrap());
+}
+
+/// Remove every cached fortune; returns how many there were
+pub fn clear(dir: &Path) -> Result<usize, String> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(format!("Failed to read {}: {}", dir.display(), e)),
+    };
+    let mut removed = 0;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().is_some_and(|ext| ext == "json") {
+            fs::remove_file(&path).map_err(|e| format!("Failed to remove {}: {}", path.display(), e))?;
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip_and_clear() {
+        let dir = std::env::temp_dir().join(format!("hash-omikuji-cache-{}", std::process::id()));
+        let hash = HashBits::from_seed(2026, "alice");
+        let key = key("alice", 2026, Layout::V2, Algo::Sha256, None, None);
+        assert!(key.starts_with("2026-v2-sha256-") && key.ends_with(".json"));
+        assert!(!key.contains("alice"));
+        assert_ne!(key, super::key("alice", 2026, Layout::V2, Algo::Sha256, Some(&EffortParams::for_level(3)), None));
+        assert_ne!(key, super::key("alice", 2026, Layout::V2, Algo::Sha256, None, Some("birthday-03-14")));
+
+        assert!(load(&dir, &key, "alice", 2026, Layout::V2, Algo::Sha256).is_none());
+        store(&dir, &key, &canonical(&hash, 2026, "alice", Layout::V2, Algo::Sha256, None));
+        let cached = load(&dir, &key, "alice", 2026, Layout::V2, Algo::Sha256).unwrap();
+        assert_eq!(cached.hex_string(), hash.hex_string());
+        assert!(load(&dir, &key, "bob", 2026, Layout::V2, Algo::Sha256).is_none());
+        assert!(load(&dir, &key, "alice", 2026, Layout::V1, Algo::Sha256).is_none());
+
+        assert_eq!(clear(&dir), Ok(1));
+        assert!(load(&dir, &key, "alice", 2026, Layout::V2, Algo::Sha256).is_none());
+        fs::remove_dir(&dir).unwrap();
+        assert_eq!(clear(&dir), Ok(0));
+    }
+}
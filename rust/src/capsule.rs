@@ -0,0 +1,178 @@
+//! `capsule`: seal next year's fortune now and open it on New Year's Day. Only a
+//! commitment is shown when sealing, SHA-256 over a random nonce and the canonical JSON
+//! of what decides the fortune (digest, year, layout and algo); published in advance, it
+//! proves the opened fortune was fixed all along. The committed fields are versioned, so
+//! fields later added to `Fortune` do not break capsules sealed before them.
+
+use crate::error::Error;
+use crate::fingerprint::{decode_hex, hex};
+use crate::hash::{Algo, HashBits, Layout};
+use crate::output::Fortune;
+use crate::sign::canonical_json;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Version of the committed document sealed now
+pub const VERSION: u32 = 1;
+
+/// A sealed capsule, stored until it is opened; the fortune itself is not stored
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Capsule {
+    /// Committed document: 1 = digest, year, layout and algo; 0 (capsules without the
+    /// field) = the whole fortune of the release that sealed it
+    #[serde(default)]
+    pub version: u32,
+    pub year: u32,
+    pub sealed_on: NaiveDate,
+    pub layout: Layout,
+    pub algo: Algo,
+    /// 32 random bytes, hex
+    pub nonce: String,
+    /// SHA-256 of the nonce followed by the canonical JSON of the committed document, hex
+    pub commitment: String,
+}
+
+/// Default store: `~/.local/share/hash-omikuji/capsules/<year>.json` (platform data dir)
+pub fn default_path(year: u32) -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("hash-omikuji").join("capsules").join(format!("{}.json", year)))
+}
+
+/// The fortune a capsule commits to: default presentation, so only the seed, year,
+/// layout and algo decide it
+pub fn fortune(seed: &str, year: u32, layout: Layout, algo: Algo) -> Fortune {
+    let hash = HashBits::from_seeds_with(year, &[seed], algo);
+    Fortune::from_hash(&hash, year, seed).with_algo(algo).with_layout(&hash, layout)
+}
+
+/// The committed document of `version`; None for an unknown version
+fn document(version: u32, seed: &str, year: u32, layout: Layout, algo: Algo) -> Option<serde_json::Value> {
+    match version {
+        0 => Some(serde_json::to_value(fortune(seed, year, layout, algo)).unwrap()),
+        1 => {
+            let digest = HashBits::from_seeds_with(year, &[seed], algo).hex_string();
+            Some(json!({ "version": 1, "digest": digest, "year": year, "layout": layout, "algo": algo }))
+        }
+        _ => None,
+    }
+}
+
+fn commit(nonce: &[u8], document: &serde_json::Value) -> String {
+    hex(&Sha256::new().chain_update(nonce).chain_update(canonical_json(document)).finalize())
+}
+
+/// A capsule for `year` could be opened: the year can be drawn (1-9999)
+pub fn check_year(year: u32) -> Result<(), Error> {
+    if crate::date::YEARS.contains(&year) {
+        return Ok(());
+    }
+    Err(Error::Other(format!("Cannot seal a capsule for {}: it is outside the years that can be drawn (1-9999)", year)))
+}
+
+impl Capsule {
+    /// Commit to the `year` fortune of `seed`; see [`check_year`]
+    pub fn seal(seed: &str, year: u32, layout: Layout, algo: Algo, today: NaiveDate) -> Result<Self, Error> {
+        check_year(year)?;
+        let mut nonce = [0u8; 32];
+        getrandom::getrandom(&mut nonce).map_err(|e| Error::Io(format!("Failed to gather randomness: {}", e)))?;
+        let document = document(VERSION, seed, year, layout, algo).expect("the current version is known");
+        let commitment = commit(&nonce, &document);
+        Ok(Self { version: VERSION, year, sealed_on: today, layout, algo, nonce: hex(&nonce), commitment })
+    }
+
+    /// The fortune, if `seed` redraws the committed one
+    pub fn open(&self, seed: &str) -> Result<Fortune, Error> {
+        let nonce = decode_hex(&self.nonce).ok_or_else(|| Error::Format("Invalid capsule nonce".to_string()))?;
+        let document = document(self.version, seed, self.year, self.layout, self.algo)
+            .ok_or_else(|| Error::Format(format!("Capsule version {} is newer than this release", self.version)))?;
+        if commit(&nonce, &document) != self.commitment {
+            return Err(Error::Verify(format!(
+                "The {} fortune does not match the capsule's commitment (a different seed, or an altered capsule)",
+                self.year
+            )));
+        }
+        Ok(fortune(seed, self.year, self.layout, self.algo))
+    }
+
+    pub fn load(path: &Path) -> Result<Option<Self>, Error> {
+        match fs::read_to_string(path) {
+            Ok(text) => serde_json::from_str(&text).map(Some).map_err(|e| Error::Format(format!("Invalid capsule {}: {}", path.display(), e))),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(Error::Io(format!("Failed to read {}: {}", path.display(), e))),
+        }
+    }
+
+    pub fn store(&self, path: &Path) -> Result<(), Error> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| Error::Io(format!("Failed to create {}: {}", dir.display(), e)))?;
+        }
+        crate::sink::write(path, serde_json::to_string_pretty(self).unwrap() + "\n").map_err(Error::Io)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seal_and_open() {
+        let today = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+        let capsule = Capsule::seal("alice", 2027, Layout::V2, Algo::Sha256, today).unwrap();
+        assert_eq!(capsule.commitment.len(), 64);
+        assert_ne!(capsule, Capsule::seal("alice", 2027, Layout::V2, Algo::Sha256, today).unwrap());
+
+        let opened = capsule.open("alice").unwrap();
+        assert_eq!(opened, fortune("alice", 2027, Layout::V2, Algo::Sha256));
+        assert!(capsule.open("bob").is_err());
+
+        let path = std::env::temp_dir().join(format!("hash-omikuji-capsule-{}.json", std::process::id()));
+        assert_eq!(Capsule::load(&path), Ok(None));
+        capsule.store(&path).unwrap();
+        assert_eq!(Capsule::load(&path), Ok(Some(capsule.clone())));
+        fs::remove_file(&path).unwrap();
+
+        let tampered = Capsule { year: 2028, ..capsule.clone() };
+        assert!(tampered.open("alice").is_err());
+        let unknown = Capsule { version: VERSION + 1, ..capsule };
+        assert!(matches!(unknown.open("alice"), Err(Error::Format(message)) if message.contains("newer")));
+
+        assert!(Capsule::seal("alice", 9999, Layout::V2, Algo::Sha256, today).is_ok());
+        assert!(Capsule::seal("alice", 10000, Layout::V2, Algo::Sha256, today).unwrap_err().to_string().contains("10000"));
+    }
+
+    #[test]
+    fn test_commitment_is_stable() {
+        // Pinned: fields added to Fortune must not change what a capsule commits to
+        let capsule = Capsule {
+            version: 1,
+            year: 2027,
+            sealed_on: NaiveDate::from_ymd_opt(2026, 10, 16).unwrap(),
+            layout: Layout::V2,
+            algo: Algo::Sha256,
+            nonce: "00".repeat(32),
+            commitment: commit(&[0; 32], &document(1, "alice", 2027, Layout::V2, Algo::Sha256).unwrap()),
+        };
+        let document = canonical_json(&document(1, "alice", 2027, Layout::V2, Algo::Sha256).unwrap());
+        assert_eq!(
+            document,
+            format!(
+                r#"{{"algo":"sha256","digest":"{}","layout":"v2","version":1,"year":2027}}"#,
+                HashBits::from_seeds_with(2027, &["alice"], Algo::Sha256).hex_string()
+            )
+        );
+        assert!(capsule.open("alice").is_ok());
+
+        // Capsules sealed before the version field commit to the whole fortune
+        let legacy: Capsule = serde_json::from_str(&format!(
+            r#"{{"year":2027,"sealed_on":"2026-10-16","layout":"v2","algo":"sha256","nonce":"{}","commitment":"{}"}}"#,
+            "00".repeat(32),
+            commit(&[0; 32], &serde_json::to_value(fortune("alice", 2027, Layout::V2, Algo::Sha256)).unwrap())
+        ))
+        .unwrap();
+        assert_eq!(legacy.version, 0);
+        assert!(legacy.open("alice").is_ok());
+    }
+}
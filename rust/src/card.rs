@@ -0,0 +1,517 @@
+//! Raster images: the PNG fortune card (key fields in an embedded 5x7 bitmap font
+//! plus the art board), the 1200x630 Open Graph share image and the wallpaper poster
+//! scaled from it, and the art and radar chart for inline terminal graphics.
+
+use crate::art::Walk;
+use crate::luck::{Grade, LuckScore};
+use crate::output::{seed_fingerprint, Fortune};
+use crate::theme::{rgb, Palette};
+use std::f64::consts::PI;
+
+/// Font pixels are drawn as SCALE x SCALE squares
+const SCALE: usize = 2;
+const GLYPH_WIDTH: usize = 5;
+const GLYPH_HEIGHT: usize = 7;
+/// Advance per character and per line, in font pixels
+const ADVANCE: usize = GLYPH_WIDTH + 1;
+const LINE_HEIGHT: usize = GLYPH_HEIGHT + 3;
+/// Side of one art cell, in image pixels
+const CELL: usize = 12;
+const MARGIN: usize = 16;
+
+/// Open Graph image size (the 1.91:1 preview social sites crop to)
+pub const OG_WIDTH: usize = 1200;
+pub const OG_HEIGHT: usize = 630;
+
+/// 5x7 glyphs, one row per byte with the leftmost pixel in bit 4.
+/// Lowercase letters are drawn as uppercase; anything else uses `?`.
+const FONT: [(char, [u8; 7]); 49] = [
+    ('A', [0x0E, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11]),
+    ('B', [0x1E, 0x11, 0x11, 0x1E, 0x11, 0x11, 0x1E]),
+    ('C', [0x0E, 0x11, 0x10, 0x10, 0x10, 0x11, 0x0E]),
+    ('D', [0x1E, 0x11, 0x11, 0x11, 0x11, 0x11, 0x1E]),
+    ('E', [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x1F]),
+    ('F', [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x10]),
+    ('G', [0x0E, 0x11, 0x10, 0x17, 0x11, 0x11, 0x0F]),
+    ('H', [0x11, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11]),
+    ('I', [0x0E, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0E]),
+    ('J', [0x07, 0x02, 0x02, 0x02, 0x02, 0x12, 0x0C]),
+    ('K', [0x11, 0x12, 0x14, 0x18, 0x14, 0x12, 0x11]),
+    ('L', [0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x1F]),
+    ('M', [0x11, 0x1B, 0x15, 0x15, 0x11, 0x11, 0x11]),
+    ('N', [0x11, 0x11, 0x19, 0x15, 0x13, 0x11, 0x11]),
+    ('O', [0x0E, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E]),
+    ('P', [0x1E, 0x11, 0x11, 0x1E, 0x10, 0x10, 0x10]),
+    ('Q', [0x0E, 0x11, 0x11, 0x11, 0x15, 0x12, 0x0D]),
+    ('R', [0x1E, 0x11, 0x11, 0x1E, 0x14, 0x12, 0x11]),
+    ('S', [0x0F, 0x10, 0x10, 0x0E, 0x01, 0x01, 0x1E]),
+    ('T', [0x1F, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04]),
+    ('U', [0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E]),
+    ('V', [0x11, 0x11, 0x11, 0x11, 0x11, 0x0A, 0x04]),
+    ('W', [0x11, 0x11, 0x11, 0x15, 0x15, 0x15, 0x0A]),
+    ('X', [0x11, 0x11, 0x0A, 0x04, 0x0A, 0x11, 0x11]),
+    ('Y', [0x11, 0x11, 0x11, 0x0A, 0x04, 0x04, 0x04]),
+    ('Z', [0x1F, 0x01, 0x02, 0x04, 0x08, 0x10, 0x1F]),
+    ('0', [0x0E, 0x11, 0x13, 0x15, 0x19, 0x11, 0x0E]),
+    ('1', [0x04, 0x0C, 0x04, 0x04, 0x04, 0x04, 0x0E]),
+    ('2', [0x0E, 0x11, 0x01, 0x02, 0x04, 0x08, 0x1F]),
+    ('3', [0x1F, 0x02, 0x04, 0x02, 0x01, 0x11, 0x0E]),
+    ('4', [0x02, 0x06, 0x0A, 0x12, 0x1F, 0x02, 0x02]),
+    ('5', [0x1F, 0x10, 0x1E, 0x01, 0x01, 0x11, 0x0E]),
+    ('6', [0x06, 0x08, 0x10, 0x1E, 0x11, 0x11, 0x0E]),
+    ('7', [0x1F, 0x01, 0x02, 0x04, 0x08, 0x08, 0x08]),
+    ('8', [0x0E, 0x11, 0x11, 0x0E, 0x11, 0x11, 0x0E]),
+    ('9', [0x0E, 0x11, 0x11, 0x0F, 0x01, 0x02, 0x0C]),
+    (' ', [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]),
+    ('-', [0x00, 0x00, 0x00, 0x1F, 0x00, 0x00, 0x00]),
+    (':', [0x00, 0x0C, 0x0C, 0x00, 0x0C, 0x0C, 0x00]),
+    ('.', [0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x0C]),
+    ('#', [0x0A, 0x0A, 0x1F, 0x0A, 0x1F, 0x0A, 0x0A]),
+    ('%', [0x18, 0x19, 0x02, 0x04, 0x08, 0x13, 0x03]),
+    ('/', [0x00, 0x01, 0x02, 0x04, 0x08, 0x10, 0x00]),
+    ('(', [0x02, 0x04, 0x08, 0x08, 0x08, 0x04, 0x02]),
+    (')', [0x08, 0x04, 0x02, 0x02, 0x02, 0x04, 0x08]),
+    ('+', [0x00, 0x04, 0x04, 0x1F, 0x04, 0x04, 0x00]),
+    ('\'', [0x04, 0x04, 0x08, 0x00, 0x00, 0x00, 0x00]),
+    ('*', [0x00, 0x04, 0x15, 0x0E, 0x15, 0x04, 0x00]),
+    ('?', [0x0E, 0x11, 0x01, 0x02, 0x04, 0x00, 0x04]),
+];
+
+/// 16x16 glyphs of the grade kanji, one row per u16 with the leftmost pixel in bit 15
+const KANJI: [(char, [u16; 16]); 6] = [
+    ('大', [0x0000, 0x0180, 0x0180, 0x0180, 0x7FFE, 0x0180, 0x0180, 0x0180, 0x03C0, 0x0240, 0x0660, 0x0C30, 0x1818, 0x300C, 0x6006, 0x0000]),
+    ('中', [0x0000, 0x0180, 0x0180, 0x7FFE, 0x6186, 0x6186, 0x6186, 0x6186, 0x7FFE, 0x0180, 0x0180, 0x0180, 0x0180, 0x0180, 0x0180, 0x0000]),
+    ('小', [0x0000, 0x0180, 0x0180, 0x0180, 0x1998, 0x1998, 0x318C, 0x318C, 0x6186, 0x6186, 0x0180, 0x0180, 0x0180, 0x0780, 0x0700, 0x0000]),
+    ('吉', [0x0000, 0x0180, 0x0180, 0x7FFE, 0x0180, 0x0180, 0x1FF8, 0x0000, 0x3FFC, 0x300C, 0x300C, 0x300C, 0x300C, 0x3FFC, 0x300C, 0x0000]),
+    ('末', [0x0000, 0x0180, 0x7FFE, 0x0180, 0x1FF8, 0x0180, 0x03C0, 0x07E0, 0x0DB0, 0x1998, 0x318C, 0x6186, 0x0180, 0x0180, 0x0180, 0x0000]),
+    ('凶', [0x0000, 0x0000, 0x6006, 0x6006, 0x6C36, 0x6666, 0x63C6, 0x6186, 0x63C6, 0x6666, 0x6C36, 0x6006, 0x7FFE, 0x7FFE, 0x0000, 0x0000]),
+];
+
+fn glyph(c: char) -> &'static [u8; 7] {
+    let c = c.to_ascii_uppercase();
+    FONT.iter()
+        .find(|(ch, _)| *ch == c)
+        .or_else(|| FONT.iter().find(|(ch, _)| *ch == '?'))
+        .map(|(_, rows)| rows)
+        .unwrap()
+}
+
+/// RGB pixel buffer
+pub struct Canvas {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<u8>,
+}
+
+impl Canvas {
+    pub(crate) fn new(width: usize, height: usize, background: (u8, u8, u8)) -> Self {
+        let pixels = [background.0, background.1, background.2].repeat(width * height);
+        Self { width, height, pixels }
+    }
+
+    pub(crate) fn fill_rect(&mut self, x: usize, y: usize, w: usize, h: usize, color: (u8, u8, u8)) {
+        for py in y..(y + h).min(self.height) {
+            for px in x..(x + w).min(self.width) {
+                let i = (py * self.width + px) * 3;
+                self.pixels[i..i + 3].copy_from_slice(&[color.0, color.1, color.2]);
+            }
+        }
+    }
+
+    fn draw_text(&mut self, x: usize, y: usize, text: &str, scale: usize, color: (u8, u8, u8)) {
+        for (n, c) in text.chars().enumerate() {
+            let left = x + n * ADVANCE * scale;
+            for (row, bits) in glyph(c).iter().enumerate() {
+                for col in 0..GLYPH_WIDTH {
+                    if bits & (0x10 >> col) != 0 {
+                        self.fill_rect(left + col * scale, y + row * scale, scale, scale, color);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Grade kanji, 16 * `scale` pixels square; characters outside [`KANJI`] are skipped
+    fn draw_kanji(&mut self, x: usize, y: usize, text: &str, scale: usize, color: (u8, u8, u8)) {
+        for (n, c) in text.chars().enumerate() {
+            let Some((_, rows)) = KANJI.iter().find(|(k, _)| *k == c) else { continue };
+            let left = x + n * 16 * scale;
+            for (row, bits) in rows.iter().enumerate() {
+                for col in 0..16 {
+                    if bits & (0x8000 >> col) != 0 {
+                        self.fill_rect(left + col * scale, y + row * scale, scale, scale, color);
+                    }
+                }
+            }
+        }
+    }
+
+    fn fill_circle(&mut self, center: (f64, f64), radius: f64, color: (u8, u8, u8)) {
+        let top = (center.1 - radius).max(0.0) as usize;
+        for py in top..=(center.1 + radius) as usize {
+            let dy = py as f64 + 0.5 - center.1;
+            let half = (radius * radius - dy * dy).max(0.0).sqrt();
+            let left = (center.0 - half).max(0.0).round() as usize;
+            self.fill_rect(left, py, (center.0 + half).round() as usize - left, 1, color);
+        }
+    }
+
+    /// Line of `thickness` pixels (Bresenham)
+    fn draw_line(&mut self, from: (f64, f64), to: (f64, f64), thickness: usize, color: (u8, u8, u8)) {
+        let (mut x0, mut y0) = (from.0.round() as i64, from.1.round() as i64);
+        let (x1, y1) = (to.0.round() as i64, to.1.round() as i64);
+        let (dx, dy) = ((x1 - x0).abs(), -(y1 - y0).abs());
+        let (sx, sy) = (if x0 < x1 { 1 } else { -1 }, if y0 < y1 { 1 } else { -1 });
+        let mut err = dx + dy;
+        loop {
+            if x0 >= 0 && y0 >= 0 {
+                self.fill_rect(x0 as usize, y0 as usize, thickness, thickness, color);
+            }
+            if x0 == x1 && y0 == y1 {
+                break;
+            }
+            let e2 = 2 * err;
+            if e2 >= dy {
+                err += dy;
+                x0 += sx;
+            }
+            if e2 <= dx {
+                err += dx;
+                y0 += sy;
+            }
+        }
+    }
+
+    pub fn encode_png(&self) -> Result<Vec<u8>, String> {
+        let mut bytes = Vec::new();
+        let mut encoder = png::Encoder::new(&mut bytes, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(|e| e.to_string())?;
+        writer.write_image_data(&self.pixels).map_err(|e| e.to_string())?;
+        writer.finish().map_err(|e| e.to_string())?;
+        Ok(bytes)
+    }
+}
+
+/// Art cells colored by visit count (theme ramp), start and end in the foreground color
+fn draw_board(canvas: &mut Canvas, walk: &Walk, palette: &Palette, left: usize, top: usize, cell: usize) {
+    for (i, &count) in walk.visits.iter().enumerate() {
+        let color = if i == walk.start || i == walk.end {
+            rgb(palette.foreground)
+        } else if count == 0 {
+            rgb(palette.grid)
+        } else {
+            rgb(palette.ramp[(count as usize).min(palette.ramp.len()) - 1])
+        };
+        let (x, y) = (left + (i % walk.width) * cell, top + (i / walk.width) * cell);
+        canvas.fill_rect(x + 1, y + 1, cell - 2, cell - 2, color);
+    }
+}
+
+/// The art board alone
+pub fn art_canvas(walk: &Walk, palette: &Palette) -> Canvas {
+    let rows = walk.visits.len() / walk.width;
+    let mut canvas = Canvas::new(walk.width * CELL + 8, rows * CELL + 8, rgb(palette.background));
+    draw_board(&mut canvas, walk, palette, 4, 4, CELL);
+    canvas
+}
+
+/// Radar chart of the luck scores around `center`: spokes and quarter rings in the
+/// grid color, the score polygon in the accent color
+fn draw_radar(canvas: &mut Canvas, center: (f64, f64), radius: f64, thickness: usize, scores: &[LuckScore], palette: &Palette) {
+    let count = scores.len();
+    let point = |i: usize, fraction: f64| {
+        let angle = 2.0 * PI * i as f64 / count as f64 - PI / 2.0;
+        (center.0 + radius * fraction * angle.cos(), center.1 + radius * fraction * angle.sin())
+    };
+    let grid = rgb(palette.grid);
+    for i in 0..count {
+        canvas.draw_line(center, point(i, 1.0), thickness, grid);
+        for ring in [0.25, 0.5, 0.75, 1.0] {
+            canvas.draw_line(point(i, ring), point((i + 1) % count, ring), thickness, grid);
+        }
+    }
+    let fraction = |i: usize| scores[i].score.min(100) as f64 / 100.0;
+    for i in 0..count {
+        let next = (i + 1) % count;
+        canvas.draw_line(point(i, fraction(i)), point(next, fraction(next)), thickness * 2, rgb(palette.accent));
+    }
+}
+
+/// Radar chart of the luck scores (same geometry as the SVG chart, without labels)
+pub fn radar_canvas(scores: &[LuckScore], palette: &Palette) -> Canvas {
+    const SIZE: usize = 240;
+    let center = SIZE as f64 / 2.0;
+    let mut canvas = Canvas::new(SIZE, SIZE, rgb(palette.background));
+    draw_radar(&mut canvas, (center, center), 100.0, 1, scores, palette);
+    canvas
+}
+
+/// Text lines of the card, ASCII only so the embedded font covers them
+fn card_lines(result: &Fortune) -> Vec<String> {
+    vec![
+        format!("HASH-OMIKUJI {}", result.year),
+        format!("{} ({:#})", result.grade.romaji(), result.grade),
+        format!("SCORE {}/100", result.overall_score),
+        format!("LUCKY NUMBER {}", result.lucky_number),
+        format!("LUCKY COLOR {}", result.lucky_color),
+    ]
+}
+
+/// Rasterize the fortune card: header lines, a lucky color swatch, the art board
+/// colored by visit count, and the short fingerprint
+pub fn render_png(result: &Fortune, palette: &Palette) -> Result<Vec<u8>, String> {
+    let lines = card_lines(result);
+    let footer = result.fingerprint.chars().take(16).collect::<String>();
+    let walk = &result.art_walk;
+    let rows = walk.visits.len() / walk.width;
+
+    let text_width = lines.iter().chain([&footer]).map(|l| l.chars().count()).max().unwrap_or(0) * ADVANCE * SCALE;
+    let swatch = LINE_HEIGHT * SCALE;
+    let width = MARGIN * 2 + (text_width + swatch).max(walk.width * CELL);
+    let text_height = (lines.len() + 1) * LINE_HEIGHT * SCALE;
+    let height = MARGIN * 3 + text_height + rows * CELL;
+
+    let foreground = rgb(palette.foreground);
+    let mut canvas = Canvas::new(width, height, rgb(palette.background));
+    for (i, line) in lines.iter().enumerate() {
+        let color = if i == 1 { rgb(palette.accent) } else { foreground };
+        canvas.draw_text(MARGIN, MARGIN + i * LINE_HEIGHT * SCALE, line, SCALE, color);
+    }
+    let swatch_y = MARGIN + (lines.len() - 1) * LINE_HEIGHT * SCALE;
+    let swatch_x = MARGIN + (lines[lines.len() - 1].chars().count() + 1) * ADVANCE * SCALE;
+    canvas.fill_rect(swatch_x, swatch_y, GLYPH_HEIGHT * SCALE, GLYPH_HEIGHT * SCALE, rgb(&result.lucky_color));
+
+    let board_y = MARGIN * 2 + lines.len() * LINE_HEIGHT * SCALE;
+    draw_board(&mut canvas, walk, palette, MARGIN, board_y, CELL);
+
+    canvas.draw_text(MARGIN, board_y + rows * CELL + MARGIN, &footer, SCALE, foreground);
+    canvas.encode_png()
+}
+
+/// Seed fingerprint with its second half masked, e.g. `2bd8****`
+pub fn masked_fingerprint(seed: &str) -> String {
+    format!("{}****", &seed_fingerprint(seed)[..4])
+}
+
+/// A smiley standing in for the lucky emoji (no color emoji font is embedded):
+/// it frowns for 凶 and 大凶
+fn draw_smiley(canvas: &mut Canvas, center: (f64, f64), radius: f64, grade: Grade, palette: &Palette) {
+    let ink = rgb(palette.foreground);
+    canvas.fill_circle(center, radius, (0xFF, 0xCC, 0x33));
+    for side in [-1.0, 1.0] {
+        canvas.fill_circle((center.0 + side * radius * 0.35, center.1 - radius * 0.25), radius * 0.12, ink);
+    }
+    let frown = grade.rank() <= 1;
+    let mouth = |t: f64| {
+        let angle = PI * (0.15 + 0.7 * t);
+        let curve = radius * 0.3 * angle.sin();
+        let y = if frown { center.1 + radius * 0.6 - curve } else { center.1 + radius * 0.15 + curve };
+        (center.0 - radius * 0.5 * angle.cos(), y)
+    };
+    for i in 0..16 {
+        canvas.draw_line(mouth(i as f64 / 16.0), mouth((i + 1) as f64 / 16.0), (radius / 12.0) as usize, ink);
+    }
+}
+
+/// What sits next to the grade kanji on a banner
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Emblem {
+    /// A smiley standing in for the lucky emoji
+    Smiley,
+    /// The luck score radar
+    Radar,
+}
+
+/// Banner layout designed at 1200x630 and scaled to fit any size: `unit` pixels per
+/// design pixel, with the design area stretched along the longer side. The headline
+/// column is centered vertically and the art box takes the extra width and height.
+struct Banner {
+    unit: f64,
+    /// Design area, at least 1200x630
+    width: f64,
+    height: f64,
+}
+
+impl Banner {
+    fn new(width: usize, height: usize) -> Self {
+        let unit = (width as f64 / OG_WIDTH as f64).min(height as f64 / OG_HEIGHT as f64);
+        Self { unit, width: width as f64 / unit, height: height as f64 / unit }
+    }
+
+    /// Pixel position of a design coordinate
+    fn px(&self, design: f64) -> usize {
+        (design * self.unit).round() as usize
+    }
+
+    /// Whole-pixel scale of a bitmap font drawn at `design` scale
+    fn scale(&self, design: usize) -> usize {
+        ((design as f64 * self.unit) as usize).max(1)
+    }
+
+    /// Top of the 630-high headline column
+    fn column_top(&self) -> f64 {
+        (self.height - OG_HEIGHT as f64) / 2.0
+    }
+}
+
+/// Rasterize a banner: the grade in large kanji with an emblem, the headline fields,
+/// the art board and the masked seed fingerprint, inside an accent border
+fn render_banner(result: &Fortune, seed: &str, palette: &Palette, width: usize, height: usize, emblem: Emblem) -> Result<Vec<u8>, String> {
+    const BORDER: f64 = 12.0;
+    const LEFT: f64 = 80.0;
+    const KANJI_SCALE: usize = 10;
+    const TEXT_SCALE: usize = 4;
+    // Box of the art board on the right, from ART_LEFT to the right margin
+    const ART_LEFT: f64 = 760.0;
+    const ART_MARGIN: f64 = 80.0;
+
+    let banner = Banner::new(width, height);
+    let top = banner.column_top();
+    let (left, text_scale) = (banner.px(LEFT), banner.scale(TEXT_SCALE));
+    let foreground = rgb(palette.foreground);
+    let accent = rgb(palette.accent);
+    let mut canvas = Canvas::new(width, height, rgb(palette.background));
+    let border = banner.px(BORDER);
+    canvas.fill_rect(0, 0, width, border, accent);
+    canvas.fill_rect(0, height - border, width, border, accent);
+    canvas.fill_rect(0, 0, border, height, accent);
+    canvas.fill_rect(width - border, 0, border, height, accent);
+
+    canvas.draw_text(left, banner.px(top + 64.0), &format!("HASH-OMIKUJI {}", result.year), banner.scale(5), foreground);
+    let kanji = result.grade.kanji();
+    let kanji_width = (kanji.chars().count() * 16 * KANJI_SCALE) as f64;
+    canvas.draw_kanji(left, banner.px(top + 130.0), kanji, banner.scale(KANJI_SCALE), accent);
+    let center = ((LEFT + kanji_width + 100.0) * banner.unit, (top + 210.0) * banner.unit);
+    match emblem {
+        Emblem::Smiley => draw_smiley(&mut canvas, center, 70.0 * banner.unit, result.grade, palette),
+        Emblem::Radar => draw_radar(&mut canvas, center, 80.0 * banner.unit, banner.scale(1), &result.luck_scores, palette),
+    }
+
+    let lines = [
+        format!("{} ({:#})", result.grade.romaji(), result.grade),
+        format!("SCORE {}/100", result.overall_score),
+        format!("LUCKY NUMBER {}", result.lucky_number),
+        format!("LUCKY COLOR {}", result.lucky_color),
+    ];
+    let line_height = (LINE_HEIGHT * TEXT_SCALE) as f64;
+    for (i, line) in lines.iter().enumerate() {
+        canvas.draw_text(left, banner.px(top + 320.0 + i as f64 * line_height), line, text_scale, foreground);
+    }
+    let swatch_x = left + (lines[3].chars().count() + 1) * ADVANCE * text_scale;
+    let swatch = GLYPH_HEIGHT * text_scale;
+    canvas.fill_rect(swatch_x, banner.px(top + 320.0 + 3.0 * line_height), swatch, swatch, rgb(&result.lucky_color));
+    let footer_y = banner.px(top + OG_HEIGHT as f64 - 90.0);
+    canvas.draw_text(left, footer_y, &format!("SEED {}", masked_fingerprint(seed)), text_scale, foreground);
+
+    let walk = &result.art_walk;
+    let rows = walk.visits.len() / walk.width;
+    let art_width = banner.px(banner.width - ART_LEFT - ART_MARGIN);
+    let art_height = banner.px(banner.height - 2.0 * ART_MARGIN);
+    let cell = (art_width / walk.width).min(art_height / rows).clamp(banner.scale(3), banner.scale(48));
+    let art_left = banner.px(ART_LEFT) + art_width.saturating_sub(walk.width * cell) / 2;
+    let art_top = banner.px(ART_MARGIN) + art_height.saturating_sub(rows * cell) / 2;
+    draw_board(&mut canvas, walk, palette, art_left, art_top, cell);
+    canvas.encode_png()
+}
+
+/// Rasterize the Open Graph share image: the grade in large kanji with a smiley,
+/// the headline fields, the art board and the masked seed fingerprint
+pub fn render_og(result: &Fortune, seed: &str, palette: &Palette) -> Result<Vec<u8>, String> {
+    render_banner(result, seed, palette, OG_WIDTH, OG_HEIGHT, Emblem::Smiley)
+}
+
+/// Rasterize a wallpaper of any size: the Open Graph layout scaled up, with the luck
+/// score radar next to the grade and the art board filling the rest of the screen
+pub fn render_poster(result: &Fortune, seed: &str, palette: &Palette, width: usize, height: usize) -> Result<Vec<u8>, String> {
+    render_banner(result, seed, palette, width, height, Emblem::Radar)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::HashBits;
+    use crate::theme::Theme;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_font_table() {
+        let chars: HashSet<char> = FONT.iter().map(|(c, _)| *c).collect();
+        assert_eq!(chars.len(), FONT.len());
+        assert!(FONT.iter().all(|(_, rows)| rows.iter().all(|&r| r < 0x20)));
+        assert_eq!(glyph('a'), glyph('A'));
+        assert_eq!(glyph('吉'), glyph('?'));
+    }
+
+    #[test]
+    fn test_render_png() {
+        let hash = HashBits::from_seed(2026, "test-user");
+        let result = Fortune::from_hash(&hash, 2026, "test-user");
+        let bytes = render_png(&result, &Theme::Shrine.palette()).unwrap();
+        assert_eq!(&bytes[..8], b"\x89PNG\r\n\x1a\n");
+
+        let decoder = png::Decoder::new(bytes.as_slice());
+        let reader = decoder.read_info().unwrap();
+        let info = reader.info();
+        assert!(info.width as usize >= MARGIN * 2 + 16 * CELL);
+        assert_eq!(info.color_type, png::ColorType::Rgb);
+    }
+
+    #[test]
+    fn test_art_and_radar_canvas() {
+        let palette = Theme::Ink.palette();
+        let walk = crate::art::walk(&[0; 32], 5, 3);
+        let canvas = art_canvas(&walk, &palette);
+        assert_eq!((canvas.width, canvas.height), (5 * CELL + 8, 3 * CELL + 8));
+        assert_eq!(canvas.pixels.len(), canvas.width * canvas.height * 3);
+
+        let scores = crate::luck::calculate_luck_scores(&[255; 16]);
+        let canvas = radar_canvas(&scores, &palette);
+        let accent = rgb(palette.accent);
+        // the full-score polygon passes through the top axis end
+        let i = ((120 - 100) * canvas.width + 120) * 3;
+        assert_eq!(&canvas.pixels[i..i + 3], &[accent.0, accent.1, accent.2]);
+    }
+
+    #[test]
+    fn test_render_og() {
+        let result = Fortune::builder().year(2026).seed("alice").draw();
+        let bytes = render_og(&result, "alice", &Theme::Shrine.palette()).unwrap();
+        let reader = png::Decoder::new(bytes.as_slice()).read_info().unwrap();
+        assert_eq!((reader.info().width, reader.info().height), (OG_WIDTH as u32, OG_HEIGHT as u32));
+        assert_eq!(masked_fingerprint("alice"), format!("{}****", &seed_fingerprint("alice")[..4]));
+    }
+
+    #[test]
+    fn test_render_poster() {
+        let result = Fortune::builder().year(2026).seed("alice").draw();
+        let palette = Theme::Shrine.palette();
+        for (width, height) in [(3840, 2160), (1080, 1920), (3440, 1440)] {
+            let bytes = render_poster(&result, "alice", &palette, width, height).unwrap();
+            let reader = png::Decoder::new(bytes.as_slice()).read_info().unwrap();
+            assert_eq!((reader.info().width, reader.info().height), (width as u32, height as u32));
+        }
+
+        // The Open Graph size is the design size
+        let banner = Banner::new(OG_WIDTH, OG_HEIGHT);
+        assert_eq!((banner.unit, banner.width, banner.height, banner.column_top()), (1.0, 1200.0, 630.0, 0.0));
+        let banner = Banner::new(3840, 2160);
+        assert_eq!((banner.unit, banner.width, banner.height), (3.2, 1200.0, 675.0));
+        assert_eq!((banner.px(80.0), banner.scale(4)), (256, 12));
+    }
+
+    #[test]
+    fn test_kanji_cover_grades() {
+        for grade in Grade::ALL {
+            assert!(grade.kanji().chars().all(|c| KANJI.iter().any(|(k, _)| *k == c)), "{}", grade);
+        }
+    }
+
+    #[test]
+    fn test_card_lines_ascii() {
+        let hash = HashBits::from_seed(2026, "test-user");
+        let result = Fortune::from_hash(&hash, 2026, "test-user");
+        assert!(card_lines(&result).iter().all(|line| line.is_ascii()));
+    }
+}
@@ -0,0 +1,115 @@
+use crate::luck::LuckScore;
+use crate::theme::Palette;
+use std::f64::consts::PI;
+
+const SIZE: f64 = 720.0;
+const CENTER: f64 = SIZE / 2.0;
+const RADIUS: f64 = 200.0;
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// Point on axis `index` of `count` at `fraction` of the radius (first axis points up, clockwise)
+fn point(index: usize, count: usize, fraction: f64) -> (f64, f64) {
+    let angle = 2.0 * PI * index as f64 / count as f64 - PI / 2.0;
+    (CENTER + RADIUS * fraction * angle.cos(), CENTER + RADIUS * fraction * angle.sin())
+}
+
+fn polygon_points(count: usize, fraction: impl Fn(usize) -> f64) -> String {
+    (0..count)
+        .map(|i| {
+            let (x, y) = point(i, count, fraction(i));
+            format!("{:.1},{:.1}", x, y)
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Radar (spider) chart of the luck scores, one axis per category in category order
+pub fn radar_svg(title: &str, scores: &[LuckScore], palette: &Palette) -> String {
+    let count = scores.len();
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{0}\" viewBox=\"0 0 {0} {0}\" font-family=\"sans-serif\">\n",
+        SIZE
+    );
+    svg.push_str(&format!("<rect width=\"100%\" height=\"100%\" fill=\"{}\"/>\n", palette.background));
+    svg.push_str(&format!(
+        "<text x=\"{}\" y=\"32\" text-anchor=\"middle\" font-size=\"20\" fill=\"{}\">{}</text>\n",
+        CENTER,
+        palette.foreground,
+        escape_xml(title)
+    ));
+
+    for ring in [0.25, 0.5, 0.75, 1.0] {
+        svg.push_str(&format!(
+            "<polygon points=\"{}\" fill=\"none\" stroke=\"{}\"/>\n",
+            polygon_points(count, |_| ring),
+            palette.grid
+        ));
+    }
+    for i in 0..count {
+        let (x, y) = point(i, count, 1.0);
+        svg.push_str(&format!(
+            "<line x1=\"{}\" y1=\"{}\" x2=\"{:.1}\" y2=\"{:.1}\" stroke=\"{}\"/>\n",
+            CENTER, CENTER, x, y, palette.grid
+        ));
+    }
+
+    svg.push_str(&format!(
+        "<polygon points=\"{}\" fill=\"{1}\" fill-opacity=\"0.35\" stroke=\"{1}\" stroke-width=\"2\"/>\n",
+        polygon_points(count, |i| scores[i].score.min(100) as f64 / 100.0),
+        palette.accent
+    ));
+
+    for (i, score) in scores.iter().enumerate() {
+        let (x, y) = point(i, count, 1.15);
+        let anchor = if (x - CENTER).abs() < 1.0 {
+            "middle"
+        } else if x > CENTER {
+            "start"
+        } else {
+            "end"
+        };
+        svg.push_str(&format!(
+            "<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"{}\" dominant-baseline=\"middle\" font-size=\"11\" fill=\"{}\">{} {}</text>\n",
+            x,
+            y,
+            anchor,
+            palette.foreground,
+            escape_xml(&score.label),
+            score.score
+        ));
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::luck::calculate_luck_scores;
+    use crate::theme::Theme;
+
+    #[test]
+    fn test_radar_svg() {
+        let mut scores = calculate_luck_scores(&[200; 16]);
+        scores[0].label = "R&D <Luck>".into();
+        let palette = Theme::Shrine.palette();
+        let svg = radar_svg("Hash-Omikuji 2026 大吉", &scores, &palette);
+
+        assert!(svg.starts_with("<svg ") && svg.ends_with("</svg>\n"));
+        assert!(svg.contains(palette.background) && svg.contains(palette.accent));
+        assert!(svg.contains("R&amp;D &lt;Luck&gt; "));
+        assert_eq!(svg.matches("<polygon").count(), 5);
+        assert_eq!(svg.matches("<line").count(), 16);
+        // first axis points straight up
+        assert!(svg.contains(&format!("x2=\"{:.1}\" y2=\"{:.1}\"", CENTER, CENTER - RADIUS)));
+    }
+
+    #[test]
+    fn test_themes_differ() {
+        assert_ne!(Theme::Shrine.palette(), Theme::Ink.palette());
+        assert_ne!(Theme::Ink.palette(), Theme::Sakura.palette());
+    }
+}
@@ -1,44 +1,640 @@
-use clap::Parser;
-use chrono::{Datelike, Local};
-use gethostname::gethostname;
+use crate::art::{self, ArtMode, ArtOptions, ArtPalette};
+use crate::birthday::Birthday;
+use crate::error::Error;
+use crate::fingerprint::FingerprintFormat;
+use crate::graphics::GraphicsMode;
+use crate::hash::{combine_seeds, Algo, Layout};
+use crate::lang::Lang;
+use crate::luck::{CategoryWeights, GradeWeights};
+use crate::qr::QrContent;
+use crate::theme::Theme;
+use crate::window::{self, Permit, Window};
+use clap::{Parser, Subcommand, ValueEnum};
+use chrono::{Datelike, Local, NaiveDate, Utc};
 use std::env;
+use std::io::IsTerminal;
+use std::path::PathBuf;
+use zeroize::Zeroize;
+
+#[cfg(not(target_os = "wasi"))]
+fn hostname() -> String {
+    gethostname::gethostname().to_string_lossy().to_string()
+}
+
+/// WASI has no host name; runtimes pass one in with e.g. `wasmtime --env HOSTNAME`
+#[cfg(target_os = "wasi")]
+fn hostname() -> String {
+    env::var("HOSTNAME").unwrap_or_else(|_| "wasi".to_string())
+}
+
+/// Days from the new year's day on which a draw is allowed (三が日)
+pub const SANGANICHI_DAYS: u64 = 3;
+
+fn parse_tz(name: &str) -> Result<chrono_tz::Tz, String> {
+    name.parse().map_err(|_| format!("unknown time zone {:?} (use an IANA name such as Asia/Tokyo)", name))
+}
+
+fn parse_year(text: &str) -> Result<u32, String> {
+    let year = text
+        .parse()
+        .ok()
+        .or_else(|| crate::wareki::parse(text))
+        .ok_or_else(|| format!("invalid year {:?} (expected e.g. 2026, or a Japanese era year such as R8 or 令和8年)", text))?;
+    let years = crate::date::YEARS;
+    if !years.contains(&year) {
+        return Err(format!("year {} is out of range ({}-{})", year, years.start(), years.end()));
+    }
+    Ok(year)
+}
+
+fn parse_date(text: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(text, "%Y-%m-%d").map_err(|e| format!("{} (expected YYYY-MM-DD, e.g. 2026-01-01)", e))
+}
+
+/// Login name from `USER`, or `USERNAME` as set on Windows
+fn username(var: impl Fn(&str) -> Option<String>) -> String {
+    var("USER").or_else(|| var("USERNAME")).unwrap_or_else(|| "anonymous".to_string())
+}
 
 fn get_default_seed() -> String {
-    let hostname = gethostname().to_string_lossy().to_string();
-    let username = env::var("USER").unwrap_or_else(|_| "anonymous".to_string());
+    let hostname = hostname();
+    let username = username(|name| env::var(name).ok());
     format!("{}@{}", username, hostname)
 }
 
-#[derive(Parser, Debug)]
+fn parse_path(text: &str) -> Result<PathBuf, String> {
+    Ok(crate::config::expand_home(std::path::Path::new(text)))
+}
+
+/// Poster sizes `WIDTHxHEIGHT`, each side 320 to 8192 pixels
+fn parse_size(text: &str) -> Result<(usize, usize), String> {
+    let invalid = || format!("Invalid poster size '{}': expected WIDTHxHEIGHT, e.g. 3840x2160", text);
+    let (width, height) = text.split_once(['x', 'X']).ok_or_else(invalid)?;
+    let (width, height) = (width.parse::<usize>().map_err(|_| invalid())?, height.parse::<usize>().map_err(|_| invalid())?);
+    if !(320..=8192).contains(&width) || !(320..=8192).contains(&height) {
+        return Err(format!("Poster size {}x{} out of range: each side must be 320 to 8192 pixels", width, height));
+    }
+    Ok((width, height))
+}
+
+#[derive(Parser, Debug, Default)]
 #[command(name = "hash-omikuji")]
 #[command(author = "elzup")]
 #[command(version = "0.1.0")]
 #[command(about = "SHA-256 based deterministic fortune telling CLI")]
 #[command(long_about = "A deterministic 'omikuji' (fortune slip) generator using SHA-256.\nThis command can only be executed on January 1st.\nSame input always produces the same result.")]
 pub struct Args {
-    /// Force execution for a specific year (bypasses January 1st restriction)
-    #[arg(long)]
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Force execution for a specific year (bypasses the January 1st-3rd restriction); 2026 or an era year such as R8
+    #[arg(long, value_name = "YYYY", value_parser = parse_year)]
     pub force_year: Option<u32>,
 
     /// Custom seed string (defaults to username@hostname)
     #[arg(long, short)]
     pub seed: Option<String>,
 
-    /// Output as JSON
+    /// Draw a shared fortune combining the seed with another one (repeatable, order-independent)
+    #[arg(long = "with", value_name = "SEED")]
+    pub with_seeds: Vec<String>,
+
+    /// Output as JSON (same as --output-format json)
     #[arg(long, default_value_t = false)]
     pub json: bool,
 
+    /// Write the output to this file instead of stdout, replacing it only once complete
+    #[arg(long, short, value_name = "PATH")]
+    pub output: Option<PathBuf>,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    pub output_format: OutputFormat,
+
     /// Show only top 5 luck scores
     #[arg(long, default_value_t = false)]
     pub short: bool,
 
+    /// Draw luck scores as a horizontal bar chart
+    #[arg(long, default_value_t = false)]
+    pub chart: bool,
+
+    /// Randomart width in cells
+    #[arg(long, value_name = "N", default_value_t = art::DEFAULT_WIDTH as u8, value_parser = clap::value_parser!(u8).range(1..=64))]
+    pub art_width: u8,
+
+    /// Randomart height in rows (1 draws a single-row strip, more switches to a 2D board)
+    #[arg(long, value_name = "N", default_value_t = art::DEFAULT_HEIGHT as u8, value_parser = clap::value_parser!(u8).range(1..=32))]
+    pub art_height: u8,
+
+    /// Art style: framed randomart, emoji tiles, a maze or a mandala
+    #[arg(long, value_enum, default_value_t = ArtMode::Randomart)]
+    pub art: ArtMode,
+
+    /// Glyph palette for the randomart
+    #[arg(long, value_enum, default_value_t = ArtPalette::Simple)]
+    pub art_palette: ArtPalette,
+
+    /// Print a legend explaining the art glyphs
+    #[arg(long, default_value_t = false)]
+    pub legend: bool,
+
+    /// Leave out the zodiac animal banner above the text output on a terminal
+    #[arg(long, default_value_t = false)]
+    pub no_banner: bool,
+
+    /// Draw the art and its frame with ASCII characters only
+    #[arg(long, default_value_t = false)]
+    pub ascii: bool,
+
+    /// Color the art as a heatmap (auto: only on a terminal without NO_COLOR)
+    #[arg(long, value_enum, default_value_t = ColorChoice::Auto)]
+    pub color: ColorChoice,
+
+    /// Draw the art and a radar chart as inline images (sixel or Kitty graphics protocol)
+    #[arg(long, value_enum, default_value_t = GraphicsMode::Off)]
+    pub graphics: GraphicsMode,
+
+    /// Also write a radar chart of the luck scores as SVG
+    #[arg(long, value_name = "PATH")]
+    pub chart_svg: Option<PathBuf>,
+
+    /// Also write a PNG fortune card (key fields and the art)
+    #[arg(long, value_name = "PATH")]
+    pub png: Option<PathBuf>,
+
+    /// Also write a wallpaper PNG of the given size, e.g. `--poster 3840x2160 omikuji.png`
+    #[arg(long, num_args = 2, value_names = ["WxH", "PATH"])]
+    pub poster: Option<Vec<String>>,
+
+    /// Also write the lucky melody as a MIDI file
+    #[arg(long, value_name = "PATH")]
+    pub midi: Option<PathBuf>,
+
+    /// Tap out the lucky melody's rhythm on the terminal bell after the output
+    #[arg(long, default_value_t = false)]
+    pub bell: bool,
+
+    /// Print the fortune as a QR code instead of the text output
+    #[arg(long, default_value_t = false)]
+    pub qr: bool,
+
+    /// What the QR code carries
+    #[arg(long, value_enum, default_value_t = QrContent::Json)]
+    pub qr_content: QrContent,
+
+    /// Also write the QR code as a PNG
+    #[arg(long, value_name = "PATH")]
+    pub qr_png: Option<PathBuf>,
+
+    /// Print a link to the web version that reproduces this draw instead of the text output
+    #[arg(long, default_value_t = false)]
+    pub share_url: bool,
+
+    /// Color theme for rendered charts (overrides config; default: shrine)
+    #[arg(long, value_enum)]
+    pub theme: Option<Theme>,
+
+    /// Wide layout: 8-cell mini-art strips next to the lucky number, lucky day and grade
+    #[arg(long, default_value_t = false)]
+    pub wide: bool,
+
     /// Show seed and fingerprint in output
     #[arg(long, default_value_t = false)]
     pub show_seed: bool,
 
+    /// Never show anything derived from the seed itself, only the fingerprint
+    #[arg(long, default_value_t = false)]
+    pub anonymize: bool,
+
     /// Override current date for testing (format: YYYY-MM-DD)
-    #[arg(long)]
-    pub date: Option<String>,
+    #[arg(long, value_name = "YYYY-MM-DD", value_parser = parse_date)]
+    pub date: Option<NaiveDate>,
+
+    /// Time zone deciding today's date and the default year, e.g. Asia/Tokyo (default: the system zone)
+    #[arg(long, value_name = "ZONE", value_parser = parse_tz)]
+    pub tz: Option<chrono_tz::Tz>,
+
+    /// Decide today's date and the default year in UTC
+    #[arg(long, default_value_t = false, conflicts_with = "tz")]
+    pub utc: bool,
+
+    /// New year the draw is held on
+    #[arg(long, value_enum, default_value_t = Calendar::Gregorian)]
+    pub calendar: Calendar,
+
+    /// Also allow a personal draw on this birthday (MM-DD; overrides config)
+    #[arg(long, value_name = "MM-DD")]
+    pub birthday: Option<Birthday>,
+
+    /// Encoding used for the fingerprint
+    #[arg(long, value_enum, default_value_t = FingerprintFormat::Hex)]
+    pub fingerprint_format: FingerprintFormat,
+
+    /// Language for topic verdicts and other prose
+    #[arg(long, value_enum, default_value_t = Lang::En)]
+    pub lang: Lang,
+
+    /// With --lang ja, number the slip and write the score and era year in kanji numerals (第八十八番, 七十三点)
+    #[arg(long, default_value_t = false)]
+    pub kanji_numerals: bool,
+
+    /// Grade weights 大吉,中吉,小吉,吉,末吉,凶,大凶 (overrides config; default: 16,20,20,18,12,10,4)
+    #[arg(long, value_name = "W1,..,W7")]
+    pub grade_weights: Option<GradeWeights>,
+
+    /// Weights of the 16 luck categories in the overall score (overrides config; default: all 1)
+    #[arg(long, value_name = "W1,..,W16")]
+    pub category_weights: Option<CategoryWeights>,
+
+    /// Bit layout version (v2 turns entropy_check into a field checksum)
+    #[arg(long, value_enum, default_value_t = Layout::V1)]
+    pub layout: Layout,
+
+    /// Hash function deriving the digest from the seed
+    #[arg(long, value_enum, default_value_t = Algo::Sha256)]
+    pub algo: Algo,
+
+    /// Post the Discord embed to this webhook URL instead of printing (retries on rate limits)
+    #[arg(long, value_name = "WEBHOOK_URL")]
+    pub post: Option<String>,
+
+    /// POST the --output-format serialization (JSON for text) to this URL, with headers from the config
+    #[arg(long, value_name = "URL", conflicts_with = "post")]
+    pub post_url: Option<String>,
+
+    /// Config file (default: ~/.config/hash-omikuji/config.toml, on Windows %APPDATA%\hash-omikuji\config.toml)
+    #[arg(long, value_name = "PATH", value_parser = parse_path)]
+    pub config: Option<PathBuf>,
+
+    /// History file (default: ~/.local/share/hash-omikuji/history.jsonl, on Windows %APPDATA%\hash-omikuji\history.jsonl)
+    #[arg(long, value_name = "PATH", value_parser = parse_path)]
+    pub history: Option<PathBuf>,
+
+    /// Do not record this draw in the history
+    #[arg(long, default_value_t = false)]
+    pub no_history: bool,
+
+    /// Directory of corpus files adding items, actions, topic phrases, poems and advice
+    /// (default: ~/.config/hash-omikuji/corpus, on Windows %APPDATA%\hash-omikuji\corpus)
+    #[arg(long, value_name = "DIR", value_parser = parse_path)]
+    pub corpus: Option<PathBuf>,
+
+    /// Draw from the embedded tables only, ignoring any corpus
+    #[arg(long, default_value_t = false, conflicts_with = "corpus")]
+    pub no_corpus: bool,
+
+    /// Derive the draw again instead of reading ~/.cache/hash-omikuji/fortunes (and do not store it)
+    #[arg(long, default_value_t = false)]
+    pub no_cache: bool,
+
+    /// Sign the result with an Ed25519 secret key file (see `keygen`)
+    #[arg(long, value_name = "KEYFILE")]
+    pub sign: Option<PathBuf>,
+
+    /// Stretch the seed through Argon2id before hashing (1-10, each level adds a round)
+    #[arg(long, value_name = "LEVEL", value_parser = clap::value_parser!(u8).range(1..=10))]
+    pub effort: Option<u8>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+    /// GitHub Actions: step outputs to $GITHUB_OUTPUT, a markdown summary to $GITHUB_STEP_SUMMARY
+    Gha,
+    /// Slack Block Kit payload for an incoming webhook
+    Slack,
+    /// Discord webhook message with an embed colored by grade
+    Discord,
+    /// GitHub-flavored markdown
+    Markdown,
+    /// Fortune-cookie text in the style of fortune(6): the advice and an attribution line
+    Fortune,
+    /// Omamori token: year, layout, grade and seed fingerprint with a MAC in 22 characters
+    Omamori,
+    /// A spoken paragraph for screen readers and text-to-speech, e.g. `| espeak`
+    Speech,
+    /// The spoken paragraph as an SSML document for smart speakers and TTS services
+    Ssml,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum Calendar {
+    /// January 1st
+    #[default]
+    Gregorian,
+    /// Lunar New Year's Day (Chinese calendar, 2000-2050)
+    Lunar,
+}
+
+impl Calendar {
+    pub fn day_name(self) -> &'static str {
+        match self {
+            Calendar::Gregorian => "January 1st",
+            Calendar::Lunar => "Lunar New Year's Day",
+        }
+    }
+
+    /// The days a draw is allowed: the new year's day and the two after it
+    pub fn period_name(self) -> &'static str {
+        match self {
+            Calendar::Gregorian => "January 1st-3rd",
+            Calendar::Lunar => "the first three days of Lunar New Year",
+        }
+    }
+
+    /// The new year's day in the Gregorian `year`; None for lunar years outside the table
+    pub fn new_year(self, year: i32) -> Option<NaiveDate> {
+        match self {
+            Calendar::Gregorian => NaiveDate::from_ymd_opt(year, 1, 1),
+            Calendar::Lunar => crate::lunar::new_year(year),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum ColorChoice {
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    pub fn enabled(self) -> bool {
+        match self {
+            ColorChoice::Auto => std::io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none() && crate::console::init(),
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+        }
+    }
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Generate an Ed25519 key pair for --sign (public key written to <PATH>.pub)
+    Keygen {
+        /// Path of the secret key file to create
+        path: PathBuf,
+    },
+    /// Verify a JSON fortune: its signature and/or its layout v2 checksum
+    Verify {
+        /// JSON fortune file
+        file: PathBuf,
+
+        /// Require the signature to come from this public key file
+        #[arg(long, value_name = "PUBFILE")]
+        pubkey: Option<PathBuf>,
+    },
+    /// List the achievements earned by the draws in the history
+    Achievements,
+    /// Draw fortunes over synthetic seeds and compare the grade distribution with the weights
+    Simulate {
+        /// Number of fortunes to draw
+        #[arg(long, default_value_t = 100_000)]
+        samples: u64,
+
+        /// Hashing threads (default: all cores)
+        #[arg(long, short)]
+        jobs: Option<usize>,
+    },
+    /// Seal next year's fortune as a commitment now; `capsule open` reveals and verifies it
+    Capsule {
+        #[command(subcommand)]
+        action: Option<CapsuleAction>,
+
+        /// Capsule file (default: ~/.local/share/hash-omikuji/capsules/<YEAR>.json)
+        #[arg(long, value_name = "PATH")]
+        file: Option<PathBuf>,
+    },
+    /// Preview the grade, score and lucky day of coming years for the seed
+    Forecast {
+        /// Years to show, inclusive (default: this year and the next 10)
+        #[arg(long, value_name = "FROM..TO", value_parser = crate::forecast::parse_years)]
+        years: Option<std::ops::RangeInclusive<u32>>,
+    },
+    /// Draw every household member's fortune (config `[[family]]`) in one grouped report
+    Family {
+        /// Only these members; a name not in the config is drawn as its own seed
+        #[arg(long = "for", value_name = "NAME")]
+        members: Vec<String>,
+    },
+    /// Print a short prompt segment (today's grade and emoji) for starship or PS1
+    Prompt {
+        /// No ANSI colors
+        #[arg(long, default_value_t = false)]
+        no_color: bool,
+    },
+    /// Print an 80-column login banner for /etc/update-motd.d (the full draw on January 1st)
+    Motd {
+        /// No ANSI colors
+        #[arg(long, default_value_t = false)]
+        no_color: bool,
+    },
+    /// Write an Atom feed with one entry per member's fortune
+    Feed {
+        /// Member seeds, one per line
+        #[arg(long, value_name = "FILE")]
+        input: PathBuf,
+
+        /// Output file (default: stdout)
+        #[arg(long, short, value_name = "PATH")]
+        output: Option<PathBuf>,
+    },
+    /// Write a fortune(6) file of the members' cookies, plus its strfile index (<OUTPUT>.dat)
+    Fortunes {
+        /// Member seeds, one per line
+        #[arg(long, value_name = "FILE")]
+        input: PathBuf,
+
+        /// Fortunes file to write
+        #[arg(long, short, value_name = "PATH")]
+        output: PathBuf,
+    },
+    /// Draw every seed of a file (or stdin) and print one JSON fortune per line, in input order
+    Batch {
+        /// Seeds, one per line (default: stdin)
+        #[arg(long, value_name = "FILE")]
+        input: Option<PathBuf>,
+
+        /// Drawing threads (default: all cores)
+        #[arg(long, short)]
+        jobs: Option<usize>,
+    },
+    /// Write an iCalendar file with the lucky day at the lucky time (with a reminder)
+    Ics {
+        /// Output file (default: stdout)
+        #[arg(long, short, value_name = "PATH")]
+        output: Option<PathBuf>,
+
+        /// Also add the lucky minute as a daily recurring event
+        #[arg(long, default_value_t = false)]
+        daily: bool,
+    },
+    /// Render a share image of the fortune
+    Card {
+        /// Write a 1200x630 Open Graph PNG (grade, art, masked seed fingerprint) to this path
+        #[arg(long, value_name = "PATH")]
+        og: PathBuf,
+    },
+    /// Write a printable New Year's postcard (hagaki, 100x148 mm) with the grade, art and poem
+    Nengajo {
+        /// Output file: PDF if it ends in .pdf, otherwise SVG (default: SVG on stdout)
+        #[arg(long, short, value_name = "PATH")]
+        output: Option<PathBuf>,
+    },
+    /// Print the JSON Schema of the --json document for the --layout version
+    Schema,
+    /// Publish the JSON fortune as a retained MQTT message (e.g. for a Home Assistant dashboard)
+    Publish {
+        /// Broker URL: tcp://[user[:password]@]host[:port]
+        #[arg(long, value_name = "URL")]
+        mqtt: String,
+
+        /// Topic to publish to
+        #[arg(long)]
+        topic: String,
+
+        /// Publish every seed of this file (one per line) to <TOPIC>/<seed fingerprint> instead
+        #[arg(long, value_name = "FILE")]
+        input: Option<PathBuf>,
+    },
+    /// Amend HEAD with a nonce trailer so its commit id starts with your lucky hex (or --prefix)
+    LuckyCommit {
+        /// Hex prefix to mine for (default: the lucky hex byte of this year's fortune)
+        #[arg(long, value_name = "HEX")]
+        prefix: Option<String>,
+
+        /// Mining threads (default: all cores)
+        #[arg(long)]
+        threads: Option<usize>,
+    },
+    /// Run an HTTP server: fortunes, art, batch draws and verification, Prometheus /metrics and /openapi.json
+    #[cfg(feature = "serve")]
+    Serve {
+        /// Address to listen on: host:port, or unix:/path for a unix domain socket (ignored under systemd socket activation)
+        #[arg(long, default_value = crate::serve::DEFAULT_BIND)]
+        bind: String,
+
+        /// Seeds exported by /metrics, one per line (default: your seed)
+        #[arg(long, value_name = "FILE")]
+        seeds: Option<PathBuf>,
+
+        /// TOML file of [[tenant]] tables (name, salt, hosts, api_keys); each request then draws
+        /// with the salt of the tenant its X-API-Key or Host header selects
+        #[arg(long, value_name = "FILE")]
+        tenants: Option<PathBuf>,
+
+        /// Draws running at once on their own threads (default: one per core)
+        #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+        workers: Option<u32>,
+
+        /// Requests handled at once; more are answered 503 with Retry-After
+        #[arg(long, value_name = "N", default_value_t = 256)]
+        max_requests: usize,
+
+        /// Seconds a request may take before it is answered 503
+        #[arg(long, value_name = "SECS", default_value_t = 30)]
+        timeout: u64,
+
+        /// Serve the gRPC API of proto/omikuji.proto on --bind instead of HTTP
+        #[cfg(feature = "grpc")]
+        #[arg(long, default_value_t = false)]
+        grpc: bool,
+    },
+    /// Run a Matrix bot replying to `!omikuji [name]` with a compact fortune, once per user and year.
+    /// The access token is read from HASH_OMIKUJI_MATRIX_TOKEN.
+    #[cfg(feature = "bot")]
+    Bot {
+        /// Homeserver URL, e.g. https://matrix.org
+        #[arg(long, value_name = "HOMESERVER")]
+        matrix: String,
+
+        /// Room id or alias to join
+        #[arg(long)]
+        room: String,
+
+        /// Who already drew (default: ~/.local/share/hash-omikuji/bot.jsonl)
+        #[arg(long, value_name = "PATH")]
+        state: Option<PathBuf>,
+    },
+    /// Show the fortune in a full-screen terminal interface: a reveal animation, then tabs
+    /// for the overview, a score chart, topics, the art and the history
+    #[cfg(feature = "tui")]
+    Tui,
+    /// Check this build against a shared test-vector file (see spec/conformance.json); fails if any field differs
+    Conformance {
+        /// JSON file of {"vectors": [{"seed", "year", "layout", "algo", "lang", "with", "expected": {...}}]}
+        spec: PathBuf,
+    },
+    /// Work with omamori tokens (--output-format omamori)
+    Omamori {
+        #[command(subcommand)]
+        action: OmamoriAction,
+    },
+    /// Manage the git hook that adds a fortune line to commits made on January 1st
+    Hook {
+        #[command(subcommand)]
+        action: HookAction,
+    },
+    /// Manage the cache of drawn fortunes (~/.cache/hash-omikuji/fortunes)
+    Cache {
+        #[command(subcommand)]
+        action: CacheAction,
+    },
+    /// Print a crontab entry or systemd units that draw at 00:00 on January 1st (in --tz)
+    /// and post to --post-url or the config's webhook.url
+    Schedule {
+        #[arg(value_enum, default_value_t = ScheduleKind::Cron)]
+        kind: ScheduleKind,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum ScheduleKind {
+    /// A crontab line
+    #[default]
+    Cron,
+    /// A systemd user service and timer
+    Systemd,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum OmamoriAction {
+    /// Expand a token and verify it with --seed (default: your seed)
+    Decode {
+        token: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum CapsuleAction {
+    /// On New Year's Day: reveal this year's sealed fortune and check it against the commitment
+    Open,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum CacheAction {
+    /// Remove every cached fortune
+    Clear,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum HookAction {
+    /// Install the prepare-commit-msg hook in the current repository
+    Install,
+    /// Remove the hook installed by `hook install`
+    Uninstall,
+    /// Called by the hook: add the fortune line to the commit message on January 1st
+    #[command(hide = true)]
+    Run {
+        /// Commit message file
+        file: PathBuf,
+        /// Source of the message (message, template, merge, squash, commit)
+        source: Option<String>,
+    },
 }
 
 impl Args {
@@ -46,32 +642,184 @@ impl Args {
         self.seed.clone().unwrap_or_else(get_default_seed)
     }
 
+    /// Seed used for derivation: the seed alone, or the canonical group of it plus --with seeds
+    pub fn get_combined_seed(&self) -> String {
+        let seed = self.get_seed();
+        let mut seeds: Vec<&str> = vec![&seed];
+        seeds.extend(self.with_seeds.iter().map(String::as_str));
+        combine_seeds(&seeds)
+    }
+
+    /// Wipe the seed strings held by the parsed arguments
+    pub fn zeroize_seeds(&mut self) {
+        if let Some(ref mut seed) = self.seed {
+            seed.zeroize();
+        }
+        self.seed = None;
+        self.with_seeds.iter_mut().for_each(|s| s.zeroize());
+        self.with_seeds.clear();
+    }
+
+    /// Body and content type posted by --post-url; text falls back to JSON
+    pub fn post_body(&self, fortune: &crate::output::Fortune) -> Result<(String, &'static str), String> {
+        match self.format() {
+            OutputFormat::Text | OutputFormat::Json => Ok((fortune.format_json(), "application/json")),
+            OutputFormat::Slack => Ok((crate::slack::payload(fortune).to_string(), "application/json")),
+            OutputFormat::Discord => Ok((crate::discord::payload(fortune).to_string(), "application/json")),
+            OutputFormat::Markdown => Ok((fortune.format_markdown(), "text/markdown; charset=utf-8")),
+            OutputFormat::Fortune => Ok((crate::fortunes::cookie(fortune), "text/plain; charset=utf-8")),
+            OutputFormat::Speech => Ok((crate::speech::paragraph(fortune), "text/plain; charset=utf-8")),
+            OutputFormat::Ssml => Ok((crate::speech::ssml(fortune), "application/ssml+xml")),
+            OutputFormat::Gha => Err("--output-format gha writes files and cannot be posted".to_string()),
+            OutputFormat::Omamori => Err("--output-format omamori needs the seed and cannot be posted".to_string()),
+        }
+    }
+
+    /// Colors of the text output; `auto` leaves them out of an --output file
+    pub fn color_enabled(&self) -> bool {
+        match (self.color, &self.output) {
+            (ColorChoice::Auto, Some(_)) => false,
+            (color, _) => color.enabled(),
+        }
+    }
+
+    /// Inline image protocol of the text output; `auto` leaves images out of an --output file
+    pub fn graphics_protocol(&self) -> Option<crate::graphics::Protocol> {
+        match (self.graphics, &self.output) {
+            (GraphicsMode::Auto, Some(_)) => None,
+            (graphics, _) => graphics.protocol(),
+        }
+    }
+
+    /// --output-format, with --json as a shorthand
+    pub fn format(&self) -> OutputFormat {
+        if self.json {
+            OutputFormat::Json
+        } else {
+            self.output_format
+        }
+    }
+
+    /// Today's date in --tz, in UTC with --utc, otherwise in the system zone
+    pub fn today(&self) -> NaiveDate {
+        match (self.tz, self.utc) {
+            (Some(tz), _) => Utc::now().with_timezone(&tz).date_naive(),
+            (None, true) => Utc::now().date_naive(),
+            (None, false) => Local::now().date_naive(),
+        }
+    }
+
     pub fn get_year(&self) -> u32 {
-        self.force_year.unwrap_or_else(|| Local::now().year() as u32)
+        self.force_year.unwrap_or_else(|| self.draw_date().year() as u32)
+    }
+
+    /// Date of the draw: --date if given, otherwise today
+    pub fn draw_date(&self) -> NaiveDate {
+        self.date.unwrap_or_else(|| self.today())
+    }
+
+    /// Art settings from the flags, restricted to ASCII under --ascii
+    pub fn art_options(&self) -> ArtOptions {
+        let options = ArtOptions {
+            width: self.art_width as usize,
+            height: self.art_height as usize,
+            palette: self.art_palette,
+            mode: self.art,
+        };
+        if self.ascii {
+            options.ascii()
+        } else {
+            options
+        }
+    }
+
+    /// Size and path of --poster
+    pub fn poster(&self) -> Result<Option<(usize, usize, PathBuf)>, String> {
+        let Some([size, path]) = self.poster.as_deref() else { return Ok(None) };
+        let (width, height) = parse_size(size)?;
+        Ok(Some((width, height, crate::config::expand_home(std::path::Path::new(path)))))
+    }
+
+    pub fn history_path(&self) -> Option<PathBuf> {
+        self.history.clone().or_else(crate::history::default_path)
+    }
+
+    pub fn corpus_dir(&self) -> Option<PathBuf> {
+        if self.no_corpus {
+            return None;
+        }
+        self.corpus.clone().or_else(crate::corpus::Corpus::default_dir)
     }
 
     pub fn is_january_first(&self) -> bool {
-        if let Some(ref date_str) = self.date {
-            if let Some((_, rest)) = date_str.split_once('-') {
-                if let Some((month, day)) = rest.split_once('-') {
-                    return month == "01" && day == "01";
-                }
+        let date = self.draw_date();
+        date.month() == 1 && date.day() == 1
+    }
+
+    /// Whether the draw date is the new year's day of --calendar
+    pub fn is_new_year(&self) -> bool {
+        match self.calendar {
+            Calendar::Gregorian => self.is_january_first(),
+            Calendar::Lunar => {
+                let date = self.draw_date();
+                Calendar::Lunar.new_year(date.year()) == Some(date)
             }
-            return false;
         }
+    }
 
-        let now = Local::now();
-        now.month() == 1 && now.day() == 1
+    /// Whether the draw date is within the first three days of the year (三が日, when
+    /// shrine visits are made); the year still decides the draw, so all three agree
+    pub fn is_sanganichi(&self) -> bool {
+        let date = self.draw_date();
+        self.calendar
+            .new_year(date.year())
+            .is_some_and(|first| (first..=first + chrono::Days::new(SANGANICHI_DAYS - 1)).contains(&date))
     }
 
-    pub fn can_execute(&self) -> Result<bool, &'static str> {
-        if self.is_january_first() {
-            Ok(false)  // No warning needed
-        } else if self.force_year.is_some() {
-            Ok(true)   // Warning needed
-        } else {
-            Err("This command can only be executed on January 1st.\nUse --force-year <YYYY> to override.")
+    /// The birthday being drawn today (--birthday, else `configured`); the new year
+    /// takes precedence when both fall on the same day
+    pub fn birthday_draw(&self, configured: Option<Birthday>) -> Option<Birthday> {
+        let birthday = self.birthday.or(configured)?;
+        (!self.is_new_year() && birthday.matches(self.draw_date())).then_some(birthday)
+    }
+
+    /// Whether the draw is a preview that the history records as forced: --force-year
+    /// outside the open days, or any --date override
+    pub fn is_preview(&self, permit: &Permit) -> bool {
+        *permit == Permit::Forced || self.date.is_some()
+    }
+
+    /// The execution policy: the first three days of --calendar's year and the birthday
+    /// are always open, then the configured `windows`, then --force-year
+    pub fn can_execute(&self, birthday: Option<Birthday>, windows: &[Window]) -> Result<Permit, Error> {
+        if self.is_sanganichi() || self.birthday_draw(birthday).is_some() {
+            return Ok(Permit::Open);
         }
+        if let Some(window) = window::find(windows, self.draw_date()) {
+            return Ok(window.warning.clone().map_or(Permit::Open, Permit::Warned));
+        }
+        if self.force_year.is_some() {
+            return Ok(Permit::Forced);
+        }
+        let year = self.draw_date().year();
+        let mut day = match (self.calendar, self.calendar.new_year(year)) {
+            (Calendar::Lunar, Some(date)) => {
+                format!("{} ({} to {})", self.calendar.period_name(), date, date + chrono::Days::new(SANGANICHI_DAYS - 1))
+            }
+            (Calendar::Lunar, None) => {
+                return Err(Error::DateRestriction(format!(
+                    "Lunar New Year dates are only known for {}-{}.\nUse --force-year <YYYY> to override.",
+                    crate::lunar::FIRST_YEAR,
+                    crate::lunar::LAST_YEAR
+                )))
+            }
+            (Calendar::Gregorian, _) => self.calendar.period_name().to_string(),
+        };
+        if !windows.is_empty() {
+            let windows: Vec<String> = windows.iter().map(Window::to_string).collect();
+            day = format!("{} or in a configured window: {}", day, windows.join(", "));
+        }
+        Err(Error::DateRestriction(format!("This command can only be executed on {}.\nUse --force-year <YYYY> to override.", day)))
     }
 }
 
@@ -87,7 +835,8 @@ mod tests {
             json: false,
             short: false,
             show_seed: false,
-            date: Some("2026-01-01".to_string()),
+            date: NaiveDate::from_ymd_opt(2026, 1, 1),
+            ..Default::default()
         };
         assert!(args.is_january_first());
 
@@ -97,11 +846,79 @@ mod tests {
             json: false,
             short: false,
             show_seed: false,
-            date: Some("2026-07-15".to_string()),
+            date: NaiveDate::from_ymd_opt(2026, 7, 15),
+            ..Default::default()
         };
         assert!(!args.is_january_first());
     }
 
+    #[test]
+    fn test_lunar_calendar() {
+        let args = Args::parse_from(["hash-omikuji", "--calendar", "lunar", "--date", "2026-02-17"]);
+        assert!(args.is_new_year());
+        assert_eq!(args.can_execute(None, &[]), Ok(Permit::Open));
+
+        let args = Args::parse_from(["hash-omikuji", "--calendar", "lunar", "--date", "2026-01-01"]);
+        assert!(!args.is_new_year());
+        assert!(args.can_execute(None, &[]).unwrap_err().to_string().contains("Lunar New Year (2026-02-17 to 2026-02-19)"));
+        let args = Args::parse_from(["hash-omikuji", "--calendar", "lunar", "--date", "2026-02-19"]);
+        assert!(!args.is_new_year());
+        assert_eq!(args.can_execute(None, &[]), Ok(Permit::Open));
+        let args = Args::parse_from(["hash-omikuji", "--calendar", "lunar", "--date", "2099-01-01"]);
+        assert!(args.can_execute(None, &[]).unwrap_err().to_string().contains("2000-2050"));
+    }
+
+    #[test]
+    fn test_date_draw_is_preview() {
+        let args = Args::parse_from(["hash-omikuji", "--date", "2026-01-01"]);
+        let permit = args.can_execute(None, &[]).unwrap();
+        assert_eq!(permit, Permit::Open);
+        assert!(args.is_preview(&permit));
+
+        // A --date draw on new year's day neither extends nor starts a streak
+        let fortune = crate::output::Fortune::builder().year(2026).seed("alice").draw();
+        let preview = crate::history::HistoryEntry::from_result(&fortune, args.draw_date(), args.is_preview(&permit));
+        let real = |year: u32| crate::history::HistoryEntry {
+            year,
+            drawn_on: NaiveDate::from_ymd_opt(year as i32, 1, 1).unwrap(),
+            forced: false,
+            ..preview.clone()
+        };
+        let streak = crate::streak::current(&[real(2024), real(2025), preview.clone()], 2026).unwrap();
+        assert_eq!((streak.first, streak.last, streak.status), (2024, 2025, crate::streak::Status::Pending));
+        assert_eq!(crate::streak::current(&[preview], 2026), None);
+    }
+
+    #[test]
+    fn test_birthday_draw() {
+        let birthday = "03-14".parse().ok();
+        let args = Args::parse_from(["hash-omikuji", "--date", "2026-03-14"]);
+        assert_eq!(args.birthday_draw(birthday), birthday);
+        assert_eq!(args.can_execute(birthday, &[]), Ok(Permit::Open));
+        assert!(args.can_execute(None, &[]).is_err());
+
+        let args = Args::parse_from(["hash-omikuji", "--date", "2026-03-14", "--birthday", "07-01"]);
+        assert_eq!(args.birthday_draw(birthday), None);
+        assert!(args.can_execute(birthday, &[]).is_err());
+
+        // New Year's Day stays the New Year draw
+        let args = Args::parse_from(["hash-omikuji", "--date", "2026-01-01", "--birthday", "01-01"]);
+        assert_eq!(args.birthday_draw(None), None);
+        assert_eq!(args.can_execute(None, &[]), Ok(Permit::Open));
+    }
+
+    #[test]
+    fn test_sanganichi() {
+        for date in ["2026-01-01", "2026-01-02", "2026-01-03"] {
+            let args = Args::parse_from(["hash-omikuji", "--date", date]);
+            assert_eq!(args.can_execute(None, &[]), Ok(Permit::Open), "{}", date);
+        }
+        let args = Args::parse_from(["hash-omikuji", "--date", "2026-01-04"]);
+        assert!(args.can_execute(None, &[]).unwrap_err().to_string().contains("January 1st-3rd"));
+        let args = Args::parse_from(["hash-omikuji", "--date", "2025-12-31"]);
+        assert!(args.can_execute(None, &[]).is_err());
+    }
+
     #[test]
     fn test_can_execute_with_force_year() {
         let args = Args {
@@ -110,12 +927,33 @@ mod tests {
             json: false,
             short: false,
             show_seed: false,
-            date: Some("2026-07-15".to_string()),
+            date: NaiveDate::from_ymd_opt(2026, 7, 15),
+            ..Default::default()
         };
-        assert!(args.can_execute().is_ok());
+        assert_eq!(args.can_execute(None, &[]), Ok(Permit::Forced));
         assert_eq!(args.get_year(), 2026);
     }
 
+    #[test]
+    fn test_configured_windows() {
+        let windows: Vec<Window> = [("Anniversary", "07-15", Some("Not New Year")), ("Monthly", "*-01", None)]
+            .iter()
+            .map(|&(name, from, warning)| Window {
+                name: name.to_string(),
+                from: from.parse().unwrap(),
+                to: None,
+                warning: warning.map(str::to_string),
+            })
+            .collect();
+        let args = Args::parse_from(["hash-omikuji", "--date", "2026-07-15"]);
+        assert_eq!(args.can_execute(None, &windows), Ok(Permit::Warned("Not New Year".to_string())));
+        let args = Args::parse_from(["hash-omikuji", "--date", "2026-09-01"]);
+        assert_eq!(args.can_execute(None, &windows), Ok(Permit::Open));
+        let args = Args::parse_from(["hash-omikuji", "--date", "2026-09-02"]);
+        let message = args.can_execute(None, &windows).unwrap_err().to_string();
+        assert!(message.contains("January 1st-3rd or in a configured window: Anniversary (07-15), Monthly (*-01)"), "{}", message);
+    }
+
     #[test]
     fn test_cannot_execute_without_force_year() {
         let args = Args {
@@ -124,9 +962,10 @@ mod tests {
             json: false,
             short: false,
             show_seed: false,
-            date: Some("2026-07-15".to_string()),
+            date: NaiveDate::from_ymd_opt(2026, 7, 15),
+            ..Default::default()
         };
-        assert!(args.can_execute().is_err());
+        assert!(matches!(args.can_execute(None, &[]), Err(Error::DateRestriction(_))));
     }
 
     #[test]
@@ -138,6 +977,7 @@ mod tests {
             short: false,
             show_seed: false,
             date: None,
+            ..Default::default()
         };
         assert_eq!(args.get_seed(), "custom-seed");
     }
@@ -151,11 +991,71 @@ mod tests {
             short: false,
             show_seed: false,
             date: None,
+            ..Default::default()
         };
         let seed = args.get_seed();
         assert!(seed.contains('@'));
     }
 
+    #[test]
+    fn test_username_fallback() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| vars.iter().find(|(key, _)| *key == name).map(|(_, value)| value.to_string())
+        };
+        assert_eq!(username(env(&[("USER", "alice"), ("USERNAME", "bob")])), "alice");
+        assert_eq!(username(env(&[("USERNAME", "bob")])), "bob");
+        assert_eq!(username(env(&[])), "anonymous");
+    }
+
+    #[test]
+    fn test_path_flags_expand_home() {
+        let home = dirs::home_dir().unwrap();
+        let args = Args::parse_from(["hash-omikuji", "--config", "~/omikuji.toml", "--history", "~/history.jsonl"]);
+        assert_eq!(args.config, Some(home.join("omikuji.toml")));
+        assert_eq!(args.history_path(), Some(home.join("history.jsonl")));
+    }
+
+    #[test]
+    fn test_poster_size() {
+        let args = Args::parse_from(["hash-omikuji", "--poster", "3840x2160", "~/omikuji.png"]);
+        assert_eq!(args.poster(), Ok(Some((3840, 2160, dirs::home_dir().unwrap().join("omikuji.png")))));
+        assert_eq!(Args::parse_from(["hash-omikuji"]).poster(), Ok(None));
+        assert_eq!(parse_size("1080X1920"), Ok((1080, 1920)));
+        assert!(parse_size("3840").is_err());
+        assert!(parse_size("100x100").unwrap_err().contains("out of range"));
+        assert!(Args::try_parse_from(["hash-omikuji", "--poster", "3840x2160"]).is_err());
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_windows_defaults() {
+        // PowerShell passes `~` through and sets USERNAME rather than USER
+        let args = Args::parse_from(["hash-omikuji", "--config", r"~\omikuji.toml"]);
+        assert_eq!(args.config, Some(dirs::home_dir().unwrap().join("omikuji.toml")));
+        if env::var_os("USER").is_none() {
+            assert!(get_default_seed().starts_with(&format!("{}@", env::var("USERNAME").unwrap())));
+        }
+        assert!(Args::default().history_path().unwrap().starts_with(dirs::data_dir().unwrap()));
+    }
+
+    #[test]
+    fn test_combined_seed() {
+        let args = Args::parse_from(["hash-omikuji", "-s", "alice", "--with", "bob"]);
+        let swapped = Args::parse_from(["hash-omikuji", "-s", "bob", "--with", "alice"]);
+        assert_eq!(args.get_combined_seed(), swapped.get_combined_seed());
+
+        let single = Args::parse_from(["hash-omikuji", "-s", "alice"]);
+        assert_eq!(single.get_combined_seed(), "alice");
+    }
+
+    #[test]
+    fn test_zeroize_seeds() {
+        let mut args = Args::parse_from(["hash-omikuji", "-s", "alice", "--with", "bob"]);
+        args.zeroize_seeds();
+        assert!(args.seed.is_none());
+        assert!(args.with_seeds.is_empty());
+    }
+
     #[test]
     fn test_get_year_with_force() {
         let args = Args {
@@ -165,10 +1065,153 @@ mod tests {
             short: false,
             show_seed: false,
             date: None,
+            ..Default::default()
         };
         assert_eq!(args.get_year(), 2030);
     }
 
+    #[test]
+    fn test_fingerprint_format_flag() {
+        let args = Args::parse_from(["hash-omikuji", "--fingerprint-format", "base58"]);
+        assert_eq!(args.fingerprint_format, FingerprintFormat::Base58);
+        assert_eq!(Args::default().fingerprint_format, FingerprintFormat::Hex);
+    }
+
+    #[test]
+    fn test_subcommands_parse() {
+        let args = Args::parse_from(["hash-omikuji", "keygen", "key"]);
+        assert!(matches!(args.command, Some(Command::Keygen { .. })));
+
+        let args = Args::parse_from(["hash-omikuji", "verify", "fortune.json", "--pubkey", "key.pub"]);
+        match args.command {
+            Some(Command::Verify { file, pubkey }) => {
+                assert_eq!(file, PathBuf::from("fortune.json"));
+                assert_eq!(pubkey, Some(PathBuf::from("key.pub")));
+            }
+            _ => panic!("expected verify"),
+        }
+
+        let args = Args::parse_from(["hash-omikuji", "--grade-weights", "1,1,1,1,1,1,1", "simulate", "--samples", "10"]);
+        assert!(matches!(args.command, Some(Command::Simulate { samples: 10, jobs: None })));
+        assert!(args.grade_weights.is_some());
+
+        let args = Args::parse_from(["hash-omikuji", "hook", "install"]);
+        assert!(matches!(args.command, Some(Command::Hook { action: HookAction::Install })));
+        let args = Args::parse_from(["hash-omikuji", "hook", "run", ".git/COMMIT_EDITMSG", "message"]);
+        assert!(matches!(args.command, Some(Command::Hook { action: HookAction::Run { source: Some(_), .. } })));
+
+        let args = Args::parse_from(["hash-omikuji", "omamori", "decode", "AQfqESvYBskwvxPzlYnjYQ"]);
+        assert!(matches!(args.command, Some(Command::Omamori { action: OmamoriAction::Decode { .. } })));
+
+        let args = Args::parse_from(["hash-omikuji", "cache", "clear"]);
+        assert!(matches!(args.command, Some(Command::Cache { action: CacheAction::Clear })));
+
+        let args = Args::parse_from(["hash-omikuji", "forecast", "--years", "2026..2036"]);
+        assert!(matches!(args.command, Some(Command::Forecast { years: Some(ref years) }) if *years == (2026..=2036)));
+
+        let args = Args::parse_from(["hash-omikuji", "family", "--for", "alice", "--for", "bob"]);
+        assert!(matches!(args.command, Some(Command::Family { ref members }) if members == &["alice", "bob"]));
+
+        let args = Args::parse_from(["hash-omikuji", "capsule"]);
+        assert!(matches!(args.command, Some(Command::Capsule { action: None, file: None })));
+        let args = Args::parse_from(["hash-omikuji", "capsule", "--file", "c.json", "open"]);
+        assert!(matches!(args.command, Some(Command::Capsule { action: Some(CapsuleAction::Open), file: Some(_) })));
+
+        let args = Args::parse_from(["hash-omikuji", "schedule", "systemd"]);
+        assert!(matches!(args.command, Some(Command::Schedule { kind: ScheduleKind::Systemd })));
+
+        assert!(Args::parse_from(["hash-omikuji"]).command.is_none());
+    }
+
+    #[test]
+    fn test_output_format() {
+        assert_eq!(Args::parse_from(["hash-omikuji"]).format(), OutputFormat::Text);
+        assert_eq!(Args::parse_from(["hash-omikuji", "--output-format", "gha"]).format(), OutputFormat::Gha);
+        assert_eq!(Args::parse_from(["hash-omikuji", "--json"]).format(), OutputFormat::Json);
+        assert_eq!(Args::parse_from(["hash-omikuji", "--output-format", "ssml"]).format(), OutputFormat::Ssml);
+
+        let args = Args::parse_from(["hash-omikuji", "-o", "fortune.txt", "--color", "always"]);
+        assert_eq!(args.output, Some(PathBuf::from("fortune.txt")));
+        assert!(args.color_enabled());
+        assert!(!Args::parse_from(["hash-omikuji", "-o", "fortune.txt"]).color_enabled());
+    }
+
+    #[test]
+    fn test_effort_range() {
+        let args = Args::parse_from(["hash-omikuji", "--effort", "3"]);
+        assert_eq!(args.effort, Some(3));
+        assert!(Args::try_parse_from(["hash-omikuji", "--effort", "0"]).is_err());
+        assert!(Args::try_parse_from(["hash-omikuji", "--effort", "11"]).is_err());
+    }
+
+    #[test]
+    fn test_grade_weights_flag() {
+        let args = Args::parse_from(["hash-omikuji", "--grade-weights", "1,1,1,1,1,1,1"]);
+        assert_eq!(args.grade_weights, Some(GradeWeights([1; 7])));
+        assert!(Args::try_parse_from(["hash-omikuji", "--grade-weights", "1,1"]).is_err());
+    }
+
+    #[test]
+    fn test_force_year_wareki() {
+        assert_eq!(Args::parse_from(["hash-omikuji", "--force-year", "R8"]).get_year(), 2026);
+        assert_eq!(Args::parse_from(["hash-omikuji", "--force-year", "平成31年"]).get_year(), 2019);
+        assert!(Args::try_parse_from(["hash-omikuji", "--force-year", "H32"]).is_err());
+        assert!(Args::try_parse_from(["hash-omikuji", "--force-year", "R2147483647"]).is_err());
+    }
+
+    #[test]
+    fn test_force_year_range() {
+        assert_eq!(Args::parse_from(["hash-omikuji", "--force-year", "9999"]).get_year(), 9999);
+        let error = Args::try_parse_from(["hash-omikuji", "--force-year", "300000"]).unwrap_err().to_string();
+        assert!(error.contains("out of range (1-9999)"), "{}", error);
+        assert!(Args::try_parse_from(["hash-omikuji", "--force-year", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_draw_date() {
+        let args = Args::parse_from(["hash-omikuji", "--date", "2026-01-01"]);
+        assert_eq!(args.draw_date(), NaiveDate::from_ymd_opt(2026, 1, 1).unwrap());
+        assert_eq!(Args::default().draw_date(), Local::now().date_naive());
+        // the draw date also decides the default year
+        assert_eq!(Args::parse_from(["hash-omikuji", "--date", "2025-01-02"]).get_year(), 2025);
+        assert!(Args::try_parse_from(["hash-omikuji", "--date", "2026-13-99"]).is_err());
+        assert!(Args::try_parse_from(["hash-omikuji", "--date", "2026-02-29"]).is_err());
+        assert!(Args::try_parse_from(["hash-omikuji", "--date", "01-01"]).is_err());
+    }
+
+    #[test]
+    fn test_art_flags() {
+        let args = Args::parse_from(["hash-omikuji", "--art-width", "17", "--art-height", "9", "--art-palette", "classic"]);
+        assert_eq!((args.art_width, args.art_height, args.art_palette), (17, 9, ArtPalette::Classic));
+        assert!(Args::try_parse_from(["hash-omikuji", "--art-width", "0"]).is_err());
+
+        let args = Args::parse_from(["hash-omikuji", "--art", "emoji", "--art-palette", "blocks", "--ascii"]);
+        assert_eq!(args.art_options().mode, ArtMode::Randomart);
+        assert_eq!(args.art_options().palette, ArtPalette::Simple);
+
+        // Mazes are ASCII already
+        let args = Args::parse_from(["hash-omikuji", "--art", "maze", "--ascii"]);
+        assert_eq!(args.art_options().mode, ArtMode::Maze);
+        let maze = crate::hash::HashBits::from_seed(2026, "test").art(&args.art_options());
+        assert!(maze.is_ascii() && maze.lines().count() == 9 && maze.contains('S'));
+
+        // Mandalas keep their style and draw with the ASCII palette
+        let args = Args::parse_from(["hash-omikuji", "--art", "mandala", "--art-palette", "blocks", "--ascii"]);
+        assert_eq!((args.art_options().mode, args.art_options().palette), (ArtMode::Mandala, ArtPalette::Simple));
+        let mandala = crate::hash::HashBits::from_seed(2026, "test").art(&args.art_options());
+        assert!(mandala.is_ascii() && mandala.lines().count() == 9);
+    }
+
+    #[test]
+    fn test_time_zone() {
+        let args = Args::parse_from(["hash-omikuji", "--tz", "Asia/Tokyo"]);
+        assert_eq!(args.tz, Some(chrono_tz::Asia::Tokyo));
+        assert_eq!(args.today(), Utc::now().with_timezone(&chrono_tz::Asia::Tokyo).date_naive());
+        assert_eq!(Args::parse_from(["hash-omikuji", "--utc"]).today(), Utc::now().date_naive());
+        assert!(Args::try_parse_from(["hash-omikuji", "--tz", "Mars/Olympus"]).is_err());
+        assert!(Args::try_parse_from(["hash-omikuji", "--tz", "UTC", "--utc"]).is_err());
+    }
+
     #[test]
     fn test_get_year_default() {
         let args = Args {
@@ -178,6 +1221,7 @@ mod tests {
             short: false,
             show_seed: false,
             date: None,
+            ..Default::default()
         };
         assert_eq!(args.get_year(), Local::now().year() as u32);
     }
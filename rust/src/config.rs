@@ -0,0 +1,261 @@
+use crate::birthday::Birthday;
+use crate::error::Error;
+use crate::family::Member;
+use crate::luck::{CategoryWeights, GradeWeights, LuckType};
+use crate::theme::Theme;
+use crate::window::Window;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// User configuration, read from `~/.config/hash-omikuji/config.toml` (or `--config`).
+///
+/// ```toml
+/// grade_weights = [16, 20, 20, 18, 12, 10, 4]
+/// advice_grammar = "~/.config/hash-omikuji/advice.toml"
+/// theme = "sakura"
+/// birthday = "03-14"
+///
+/// [categories.study]
+/// label = "OSS contributions"
+/// weight = 2
+///
+/// [webhook.headers]
+/// Authorization = "Bearer 0123abcd"
+///
+/// [[windows]]
+/// name = "Sanganichi"
+/// from = "01-01"
+/// to = "01-03"
+///
+/// [[family]]
+/// name = "Mom"
+/// seed = "mom@home"
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Weights for 大吉..大凶
+    pub grade_weights: Option<[u32; 7]>,
+    /// Per-category overrides keyed by `LuckType::key()`
+    pub categories: BTreeMap<String, CategoryConfig>,
+    /// Replacement for the embedded advice grammar (TOML, see `src/grammar/`)
+    pub advice_grammar: Option<PathBuf>,
+    /// Color theme for rendered charts
+    pub theme: Option<Theme>,
+    /// Settings for `--post-url`
+    pub webhook: WebhookConfig,
+    /// Web version linked by `--share-url` (default: the project's page)
+    pub share_base: Option<String>,
+    /// Birthday for `--birthday` (MM-DD)
+    pub birthday: Option<Birthday>,
+    /// Days a draw is allowed besides New Year's Day
+    pub windows: Vec<Window>,
+    /// Household members drawn by `family`
+    pub family: Vec<Member>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WebhookConfig {
+    /// Where `schedule` posts when --post-url is not given
+    pub url: Option<String>,
+    /// Extra request headers, e.g. an Authorization token
+    pub headers: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CategoryConfig {
+    pub label: Option<String>,
+    pub weight: Option<u32>,
+}
+
+impl Config {
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("hash-omikuji").join("config.toml"))
+    }
+
+    /// Load an explicit path (must exist), or the default path if present
+    pub fn load(path: Option<&Path>) -> Result<Config, Error> {
+        let path = match path {
+            Some(path) => path.to_path_buf(),
+            None => match Self::default_path() {
+                Some(path) if path.exists() => path,
+                _ => return Ok(Config::default()),
+            },
+        };
+        let text = fs::read_to_string(&path).map_err(|e| Error::Io(format!("Failed to read {}: {}", path.display(), e)))?;
+        Self::parse(&text).map_err(|e| Error::Format(format!("Invalid config {}: {}", path.display(), e)))
+    }
+
+    pub fn parse(text: &str) -> Result<Config, String> {
+        let mut config: Config = toml::from_str(text).map_err(|e| e.message().to_string())?;
+        config.advice_grammar = config.advice_grammar.as_deref().map(expand_home);
+        for key in config.categories.keys() {
+            if LuckType::from_key(key).is_none() {
+                return Err(format!("Unknown category '{}'", key));
+            }
+        }
+        if config.grade_weights.is_some_and(|w| w.iter().all(|&x| x == 0)) {
+            return Err("At least one grade weight must be positive".to_string());
+        }
+        for window in &config.windows {
+            window.validate()?;
+        }
+        Ok(config)
+    }
+
+    pub fn grade_weights(&self) -> Option<GradeWeights> {
+        self.grade_weights.map(GradeWeights)
+    }
+
+    /// Category weights with config overrides applied (unlisted categories weigh 1)
+    pub fn category_weights(&self) -> CategoryWeights {
+        let mut weights = CategoryWeights::default();
+        for (key, category) in &self.categories {
+            if let (Some(luck_type), Some(weight)) = (LuckType::from_key(key), category.weight) {
+                weights.0[luck_type.index()] = weight;
+            }
+        }
+        weights
+    }
+
+    pub fn category_labels(&self) -> HashMap<LuckType, String> {
+        self.categories
+            .iter()
+            .filter_map(|(key, category)| Some((LuckType::from_key(key)?, category.label.clone()?)))
+            .collect()
+    }
+}
+
+/// Replace a leading `~` component with the home directory; the rest of the path is
+/// kept as given, so `~/a` and, on Windows, `~\a` both work. Shells expand `~` before
+/// the CLI sees it, but config values and PowerShell arguments reach it verbatim.
+pub fn expand_home(path: &Path) -> PathBuf {
+    match (path.strip_prefix("~"), dirs::home_dir()) {
+        (Ok(rest), Some(home)) => home.join(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"
+grade_weights = [1, 1, 1, 1, 1, 1, 1]
+
+[categories.study]
+label = "OSS contributions"
+weight = 2
+
+[categories.wifi]
+weight = 0
+"#;
+
+    #[test]
+    fn test_parse_sample() {
+        let config = Config::parse(SAMPLE).unwrap();
+        assert_eq!(config.grade_weights(), Some(GradeWeights([1; 7])));
+        let weights = config.category_weights();
+        assert_eq!(weights.0[LuckType::Study.index()], 2);
+        assert_eq!(weights.0[LuckType::WiFi.index()], 0);
+        assert_eq!(weights.0[LuckType::Life.index()], 1);
+        let labels = config.category_labels();
+        assert_eq!(labels.get(&LuckType::Study).map(String::as_str), Some("OSS contributions"));
+        assert_eq!(labels.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_advice_grammar() {
+        let config = Config::parse("advice_grammar = \"/tmp/advice.toml\"\n").unwrap();
+        assert_eq!(config.advice_grammar, Some(PathBuf::from("/tmp/advice.toml")));
+    }
+
+    #[test]
+    fn test_expand_home() {
+        let home = dirs::home_dir().unwrap();
+        assert_eq!(expand_home(Path::new("~")), home);
+        assert_eq!(expand_home(Path::new("~/advice.toml")), home.join("advice.toml"));
+        assert_eq!(expand_home(Path::new("/tmp/~/advice.toml")), PathBuf::from("/tmp/~/advice.toml"));
+        assert_eq!(expand_home(Path::new("~alice/advice.toml")), PathBuf::from("~alice/advice.toml"));
+        let config = Config::parse("advice_grammar = \"~/advice.toml\"\n").unwrap();
+        assert_eq!(config.advice_grammar, Some(home.join("advice.toml")));
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_windows_paths() {
+        let home = dirs::home_dir().unwrap();
+        assert_eq!(expand_home(Path::new(r"~\AppData\advice.toml")), home.join("AppData").join("advice.toml"));
+        let config = Config::default_path().unwrap();
+        assert!(config.starts_with(dirs::config_dir().unwrap()));
+        assert!(config.ends_with(r"hash-omikuji\config.toml"));
+    }
+
+    #[test]
+    fn test_parse_theme() {
+        assert_eq!(Config::parse("theme = \"ink\"\n").unwrap().theme, Some(Theme::Ink));
+        assert!(Config::parse("theme = \"neon\"\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_birthday() {
+        assert_eq!(Config::parse("birthday = \"03-14\"\n").unwrap().birthday, "03-14".parse().ok());
+        assert!(Config::parse("birthday = \"14-03\"\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_family() {
+        let config = Config::parse("[[family]]\nname = \"Mom\"\nseed = \"mom@home\"\n\n[[family]]\nname = \"Ken\"\n").unwrap();
+        assert_eq!(config.family.iter().map(Member::seed).collect::<Vec<_>>(), ["mom@home", "Ken"]);
+        assert!(Config::parse("[[family]]\nseed = \"x\"\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_windows() {
+        let config = Config::parse("[[windows]]\nname = \"Monthly\"\nfrom = \"*-01\"\nwarning = \"Same seed\"\n").unwrap();
+        assert_eq!(config.windows.len(), 1);
+        assert_eq!(config.windows[0].warning.as_deref(), Some("Same seed"));
+        assert!(Config::parse("[[windows]]\nname = \"Mixed\"\nfrom = \"*-01\"\nto = \"03-01\"\n").is_err());
+        assert!(Config::parse("[[windows]]\nfrom = \"04-01\"\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_webhook_headers() {
+        let config = Config::parse("[webhook.headers]\nAuthorization = \"Bearer t\"\n").unwrap();
+        assert_eq!(config.webhook.headers.get("Authorization").map(String::as_str), Some("Bearer t"));
+        assert!(Config::parse("[webhook]\nheader = {}\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_empty() {
+        let config = Config::parse("").unwrap();
+        assert_eq!(config.grade_weights(), None);
+        assert_eq!(config.category_weights(), CategoryWeights::default());
+        assert!(config.category_labels().is_empty());
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_category() {
+        assert!(Config::parse("[categories.money]\nweight = 2\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_field() {
+        assert!(Config::parse("colour = \"red\"\n").is_err());
+        assert!(Config::parse("[categories.study]\nlabl = \"x\"\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_rejects_zero_grade_weights() {
+        assert!(Config::parse("grade_weights = [0, 0, 0, 0, 0, 0, 0]\n").is_err());
+    }
+
+    #[test]
+    fn test_load_missing_explicit_path() {
+        assert!(Config::load(Some(Path::new("/nonexistent/hash-omikuji.toml"))).is_err());
+    }
+}
@@ -0,0 +1,135 @@
+//! `conformance`: run a shared test-vector file so every implementation (this crate,
+//! the web version, the bindings) can show it derives the same fields bit-for-bit.
+//!
+//! ```json
+//! {"vectors": [{"seed": "alice", "year": 2026, "layout": "v2", "expected": {"grade": "Chukichi", "lucky_number": 165}}]}
+//! ```
+//!
+//! `with`, `layout`, `algo` and `lang` are optional (default: none, v1, sha256, en);
+//! `expected` holds top-level `--json` fields, compared as JSON values.
+
+use crate::hash::{Algo, Layout};
+use crate::lang::Lang;
+use crate::output::Fortune;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Vector {
+    pub seed: String,
+    #[serde(default)]
+    pub with: Vec<String>,
+    pub year: u32,
+    #[serde(default)]
+    pub layout: Layout,
+    #[serde(default)]
+    pub algo: Algo,
+    #[serde(default)]
+    pub lang: Lang,
+    pub expected: Map<String, Value>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Spec {
+    pub vectors: Vec<Vector>,
+}
+
+/// One expected field of one vector
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldResult {
+    pub field: String,
+    pub expected: Value,
+    /// None when the field is missing from the document
+    pub actual: Option<Value>,
+}
+
+impl FieldResult {
+    pub fn passed(&self) -> bool {
+        self.actual.as_ref() == Some(&self.expected)
+    }
+}
+
+impl Spec {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        serde_json::from_str(&text).map_err(|e| format!("Invalid spec {}: {}", path.display(), e))
+    }
+}
+
+impl Vector {
+    /// `alice 2026 v2 sha256`, with any shared seeds as `alice+bob` and an empty seed as `""`
+    pub fn name(&self) -> String {
+        let seeds: Vec<&str> = std::iter::once(self.seed.as_str())
+            .chain(self.with.iter().map(String::as_str))
+            .map(|seed| if seed.is_empty() { "\"\"" } else { seed })
+            .collect();
+        let layout = if self.layout == Layout::V2 { "v2" } else { "v1" };
+        let algo = if self.algo == Algo::Blake3 { "blake3" } else { "sha256" };
+        format!("{} {} {} {}", seeds.join("+"), self.year, layout, algo)
+    }
+
+    /// Draw the vector and compare every expected field (ordered by name)
+    pub fn run(&self) -> Vec<FieldResult> {
+        let mut builder = Fortune::builder().year(self.year).seed(&self.seed).layout(self.layout).algo(self.algo).lang(self.lang);
+        for seed in &self.with {
+            builder = builder.with(seed);
+        }
+        let document = serde_json::to_value(builder.draw()).unwrap();
+        self.expected
+            .iter()
+            .map(|(field, expected)| FieldResult { field: field.clone(), expected: expected.clone(), actual: document.get(field).cloned() })
+            .collect()
+    }
+}
+
+/// Per-vector pass counts with a line for every failed field, and a summary; the
+/// second value is the number of failed fields
+pub fn report(spec: &Spec) -> (String, usize) {
+    let (mut output, mut total, mut failed) = (String::new(), 0, 0);
+    for vector in &spec.vectors {
+        let results = vector.run();
+        let passed = results.iter().filter(|r| r.passed()).count();
+        let mark = if passed == results.len() { "PASS" } else { "FAIL" };
+        output.push_str(&format!("{} {} ({}/{} fields)\n", mark, vector.name(), passed, results.len()));
+        for result in results.iter().filter(|r| !r.passed()) {
+            let actual = result.actual.as_ref().map_or("missing".to_string(), Value::to_string);
+            output.push_str(&format!("  {}: expected {}, got {}\n", result.field, result.expected, actual));
+        }
+        total += results.len();
+        failed += results.len() - passed;
+    }
+    output.push_str(&format!("{} vectors, {} fields, {} failed\n", spec.vectors.len(), total, failed));
+    (output, failed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHARED: &str = include_str!("../../spec/conformance.json");
+
+    #[test]
+    fn test_shared_spec_passes() {
+        let spec: Spec = serde_json::from_str(SHARED).unwrap();
+        assert!(!spec.vectors.is_empty());
+        let (output, failed) = report(&spec);
+        assert_eq!(failed, 0, "{}", output);
+    }
+
+    #[test]
+    fn test_report_failures() {
+        let spec: Spec = serde_json::from_str(
+            r#"{"vectors": [{"seed": "alice", "with": ["bob"], "year": 2026, "expected": {"year": 2026, "grade": "Nope", "no_such_field": 1}}]}"#,
+        )
+        .unwrap();
+        let (output, failed) = report(&spec);
+        assert_eq!(failed, 2);
+        assert!(output.starts_with("FAIL alice+bob 2026 v1 sha256 (1/3 fields)\n"), "{}", output);
+        assert!(output.contains("  grade: expected \"Nope\", got \""));
+        assert!(output.contains("  no_such_field: expected 1, got missing\n"));
+        assert!(output.ends_with("1 vectors, 3 fields, 2 failed\n"));
+    }
+}
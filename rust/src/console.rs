@@ -0,0 +1,41 @@
+//! Terminal setup. Windows consoles start in the OEM code page with escape sequences
+//! shown as text, so the kanji, emoji and box glyphs of the output come out garbled and
+//! the heatmap colors as `←[48;2;...`; [`init`] switches them to UTF-8 and VT processing.
+//! Elsewhere there is nothing to set up.
+
+use std::sync::OnceLock;
+
+static ANSI: OnceLock<bool> = OnceLock::new();
+
+/// Prepare the console once, before anything is printed; true if it understands ANSI escapes
+pub fn init() -> bool {
+    *ANSI.get_or_init(enable)
+}
+
+#[cfg(windows)]
+fn enable() -> bool {
+    use windows_sys::Win32::System::Console::{
+        GetConsoleMode, GetStdHandle, SetConsoleMode, SetConsoleOutputCP, ENABLE_VIRTUAL_TERMINAL_PROCESSING,
+        STD_ERROR_HANDLE, STD_OUTPUT_HANDLE,
+    };
+    const CP_UTF8: u32 = 65001;
+    // SAFETY: plain Win32 calls on the process's own standard handles; a redirected
+    // handle fails GetConsoleMode and is left alone
+    unsafe {
+        SetConsoleOutputCP(CP_UTF8);
+        let mut ansi = true;
+        for handle in [STD_OUTPUT_HANDLE, STD_ERROR_HANDLE] {
+            let handle = GetStdHandle(handle);
+            let mut mode = 0;
+            if GetConsoleMode(handle, &mut mode) != 0 && (mode & ENABLE_VIRTUAL_TERMINAL_PROCESSING) == 0 {
+                ansi &= SetConsoleMode(handle, mode | ENABLE_VIRTUAL_TERMINAL_PROCESSING) != 0;
+            }
+        }
+        ansi
+    }
+}
+
+#[cfg(not(windows))]
+fn enable() -> bool {
+    true
+}
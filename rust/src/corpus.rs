@@ -0,0 +1,342 @@
+//! User corpora: lucky items and actions, topic phrases, poems and advice grammars
+//! dropped into `~/.config/hash-omikuji/corpus/` as TOML or JSON files.
+//!
+//! ```toml
+//! items = ["Rubber duck", { en = "Fountain pen", ja = "万年筆" }]
+//! actions = [{ en = "Refactor something old", ja = "古いコードを整理する" }]
+//!
+//! [topics]
+//! study = [{ en = "Read the source, not the docs.", ja = "文書より源を読め" }]
+//!
+//! [[poems]]
+//! text = "古池や 蛙飛びこむ 水の音"
+//! romaji = "furuike ya / kawazu tobikomu / mizu no oto"
+//! gloss = "An old pond; a frog jumps in, the sound of water."
+//! author = "松尾芭蕉"
+//! author_romaji = "Matsuo Bashō"
+//!
+//! [advice.en]
+//! templates = ["{verb} the {thing} before the spring."]
+//! slots = { thing = ["backlog", "garden"] }
+//! ```
+//!
+//! Entries are added to the embedded tables, never replace them: a table is picked from
+//! the embedded entries followed by the corpus ones with its own domain-separated bits,
+//! so a draw stays deterministic for a given corpus. Tables the corpus leaves empty keep
+//! the embedded selection. Files are merged in file name order.
+
+use crate::advice::Grammar;
+use crate::hash::HashBits;
+use crate::items::{self, ACTION_COUNT, ITEM_COUNT};
+use crate::lang::Lang;
+use crate::poems::{Poem, POEMS};
+use crate::topics::{Topic, PHRASES_PER_TOPIC};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A corpus entry: one text for both languages, or `{ en = "...", ja = "..." }`
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+pub enum Phrase {
+    Plain(String),
+    Localized(Localized),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Localized {
+    pub en: Option<String>,
+    pub ja: Option<String>,
+}
+
+impl Phrase {
+    /// The text in `lang`, else in the other language
+    pub fn get(&self, lang: Lang) -> &str {
+        match self {
+            Phrase::Plain(text) => text,
+            Phrase::Localized(Localized { en, ja }) => {
+                let (first, second) = match lang {
+                    Lang::En => (en, ja),
+                    Lang::Ja => (ja, en),
+                };
+                first.as_deref().or(second.as_deref()).unwrap_or_default()
+            }
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        match self {
+            Phrase::Plain(text) => text.is_empty(),
+            Phrase::Localized(Localized { en, ja }) => en.as_deref().unwrap_or_default().is_empty() && ja.as_deref().unwrap_or_default().is_empty(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PoemEntry {
+    text: String,
+    #[serde(default)]
+    romaji: String,
+    #[serde(default)]
+    gloss: String,
+    author: String,
+    #[serde(default)]
+    author_romaji: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct AdviceGrammars {
+    en: Option<Grammar>,
+    ja: Option<Grammar>,
+}
+
+/// One corpus file
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct CorpusFile {
+    items: Vec<Phrase>,
+    actions: Vec<Phrase>,
+    topics: HashMap<Topic, Vec<Phrase>>,
+    poems: Vec<PoemEntry>,
+    advice: AdviceGrammars,
+}
+
+impl CorpusFile {
+    fn validate(self) -> Result<CorpusFile, String> {
+        let phrases = self.items.iter().chain(&self.actions).chain(self.topics.values().flatten());
+        if phrases.into_iter().any(Phrase::is_empty) {
+            return Err("Entries need an `en` or `ja` text".to_string());
+        }
+        if let Some(poem) = self.poems.iter().find(|poem| poem.text.is_empty() || poem.author.is_empty()) {
+            return Err(format!("Poem '{}' needs a text and an author", poem.text));
+        }
+        let AdviceGrammars { en, ja } = self.advice;
+        let advice = AdviceGrammars {
+            en: en.map(Grammar::validate).transpose()?,
+            ja: ja.map(Grammar::validate).transpose()?,
+        };
+        Ok(CorpusFile { advice, ..self })
+    }
+}
+
+/// Where a selection landed: the embedded table, or a corpus entry
+enum Choice<'a, T> {
+    Embedded(usize),
+    Corpus(&'a T),
+}
+
+/// Pick from `embedded` entries followed by `extra`; None when the corpus adds nothing
+fn choose<'a, T>(hash: &HashBits, table: &str, embedded: usize, extra: &'a [T]) -> Option<Choice<'a, T>> {
+    if extra.is_empty() {
+        return None;
+    }
+    let index = hash.corpus_index(table, embedded + extra.len());
+    Some(match index.checked_sub(embedded) {
+        Some(i) => Choice::Corpus(&extra[i]),
+        None => Choice::Embedded(index),
+    })
+}
+
+/// All corpus files of a directory, merged
+#[derive(Debug, Default)]
+pub struct Corpus {
+    pub items: Vec<Phrase>,
+    pub actions: Vec<Phrase>,
+    /// Phrases per topic, in `Topic::ALL` order
+    pub topics: [Vec<Phrase>; 12],
+    pub poems: Vec<Poem>,
+    pub advice_en: Option<Grammar>,
+    pub advice_ja: Option<Grammar>,
+}
+
+impl Corpus {
+    pub fn default_dir() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("hash-omikuji").join("corpus"))
+    }
+
+    /// Parse one file's text, as JSON for a `.json` path and TOML otherwise
+    fn parse_file(path: &Path, text: &str) -> Result<CorpusFile, String> {
+        let file: CorpusFile = if path.extension().is_some_and(|ext| ext == "json") {
+            serde_json::from_str(text).map_err(|e| e.to_string())?
+        } else {
+            toml::from_str(text).map_err(|e| e.message().to_string())?
+        };
+        file.validate()
+    }
+
+    /// Every `*.toml` and `*.json` file of `dir` in file name order; a missing directory
+    /// is an empty corpus
+    pub fn load_dir(dir: &Path) -> Result<Corpus, String> {
+        let mut corpus = Corpus::default();
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(corpus),
+            Err(e) => return Err(format!("Failed to read {}: {}", dir.display(), e)),
+        };
+        let mut paths: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "toml" || ext == "json"))
+            .collect();
+        paths.sort();
+        for path in paths {
+            let text = fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+            let file = Self::parse_file(&path, &text).map_err(|e| format!("Invalid corpus {}: {}", path.display(), e))?;
+            corpus.add(file);
+        }
+        Ok(corpus)
+    }
+
+    fn add(&mut self, file: CorpusFile) {
+        self.items.extend(file.items);
+        self.actions.extend(file.actions);
+        for (topic, phrases) in file.topics {
+            let index = Topic::ALL.iter().position(|&t| t == topic).unwrap_or(0);
+            self.topics[index].extend(phrases);
+        }
+        self.poems.extend(file.poems.into_iter().map(|entry| Poem {
+            text: entry.text.into(),
+            romaji: entry.romaji.into(),
+            gloss: entry.gloss.into(),
+            author: entry.author.into(),
+            author_romaji: entry.author_romaji.into(),
+        }));
+        for (grammar, added) in [(&mut self.advice_en, file.advice.en), (&mut self.advice_ja, file.advice.ja)] {
+            match (grammar.as_mut(), added) {
+                (Some(grammar), Some(added)) => grammar.extend(&added),
+                (None, added) => *grammar = added,
+                (_, None) => {}
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+            && self.actions.is_empty()
+            && self.topics.iter().all(Vec::is_empty)
+            && self.poems.is_empty()
+            && self.advice_en.is_none()
+            && self.advice_ja.is_none()
+    }
+
+    /// The lucky item from the embedded items and the corpus ones
+    pub fn lucky_item(&self, hash: &HashBits, lang: Lang) -> Option<String> {
+        Some(match choose(hash, "items", ITEM_COUNT, &self.items)? {
+            Choice::Embedded(i) => items::lucky_item(i as u8, lang).to_string(),
+            Choice::Corpus(phrase) => phrase.get(lang).to_string(),
+        })
+    }
+
+    pub fn lucky_action(&self, hash: &HashBits, lang: Lang) -> Option<String> {
+        Some(match choose(hash, "actions", ACTION_COUNT, &self.actions)? {
+            Choice::Embedded(i) => items::lucky_action(i as u8, lang).to_string(),
+            Choice::Corpus(phrase) => phrase.get(lang).to_string(),
+        })
+    }
+
+    /// The phrase of the `index`th topic of `Topic::ALL`
+    pub fn topic_phrase(&self, hash: &HashBits, index: usize, lang: Lang) -> Option<String> {
+        let table = format!("topics/{}", index);
+        Some(match choose(hash, &table, PHRASES_PER_TOPIC, self.topics.get(index)?)? {
+            Choice::Embedded(i) => Topic::ALL[index].phrase(i as u8, lang).to_string(),
+            Choice::Corpus(phrase) => phrase.get(lang).to_string(),
+        })
+    }
+
+    pub fn poem(&self, hash: &HashBits) -> Option<Poem> {
+        Some(match choose(hash, "poems", POEMS.len(), &self.poems)? {
+            Choice::Embedded(i) => POEMS[i].clone(),
+            Choice::Corpus(poem) => poem.clone(),
+        })
+    }
+
+    /// The embedded advice grammar of `lang` with the corpus templates and slot entries
+    pub fn advice(&self, lang: Lang) -> Option<Grammar> {
+        let added = match lang {
+            Lang::En => self.advice_en.as_ref(),
+            Lang::Ja => self.advice_ja.as_ref(),
+        }?;
+        let mut grammar = Grammar::embedded(lang).clone();
+        grammar.extend(added);
+        Some(grammar)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOML: &str = r#"
+items = ["Rubber duck", { en = "Fountain pen", ja = "万年筆" }]
+
+[topics]
+study = [{ ja = "文書より源を読め" }]
+
+[[poems]]
+text = "古池や 蛙飛びこむ 水の音"
+author = "松尾芭蕉"
+
+[advice.en]
+templates = ["Water the {thing}."]
+slots = { thing = ["garden"] }
+"#;
+
+    fn corpus(files: &[(&str, &str)]) -> Corpus {
+        let dir = std::env::temp_dir().join(format!("hash-omikuji-corpus-{}-{}", std::process::id(), files.len()));
+        fs::create_dir_all(&dir).unwrap();
+        for (name, text) in files {
+            fs::write(dir.join(name), text).unwrap();
+        }
+        let corpus = Corpus::load_dir(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+        corpus.unwrap()
+    }
+
+    #[test]
+    fn test_load_and_merge() {
+        let json = r#"{"items": ["Paper crane"], "actions": [{"en": "Stretch"}], "advice": {"en": {"templates": ["Sweep the {thing}."]}}}"#;
+        let corpus = corpus(&[("b.json", json), ("a.toml", TOML), ("notes.txt", "ignored")]);
+        let items: Vec<_> = corpus.items.iter().map(|item| item.get(Lang::Ja)).collect();
+        assert_eq!(items, ["Rubber duck", "万年筆", "Paper crane"]);
+        assert_eq!(corpus.actions[0].get(Lang::Ja), "Stretch");
+        assert_eq!(corpus.topics[5][0].get(Lang::En), "文書より源を読め");
+        assert_eq!(corpus.poems[0].author, "松尾芭蕉");
+        assert_eq!(corpus.advice_en.as_ref().unwrap().templates, ["Water the {thing}.", "Sweep the {thing}."]);
+        assert!(corpus.advice_ja.is_none() && corpus.advice(Lang::Ja).is_none());
+
+        // Poems of the corpus round-trip through JSON
+        let poem: Poem = serde_json::from_value(serde_json::to_value(&corpus.poems[0]).unwrap()).unwrap();
+        assert_eq!(poem, corpus.poems[0]);
+
+        assert!(Corpus::load_dir(Path::new("/nonexistent/hash-omikuji/corpus")).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_invalid_files() {
+        let path = Path::new("corpus.toml");
+        assert!(Corpus::parse_file(path, "item = [\"typo\"]").is_err());
+        assert!(Corpus::parse_file(path, "[topics]\nfishing = [\"Bites\"]").is_err());
+        assert!(Corpus::parse_file(path, "items = [{ de = \"Ente\" }]").is_err());
+        assert!(Corpus::parse_file(path, "items = [{}]").unwrap_err().contains("`en` or `ja`"));
+        assert!(Corpus::parse_file(path, "[advice.ja]\ntemplates = []").unwrap_err().contains("template"));
+        assert!(Corpus::parse_file(Path::new("corpus.json"), "items = []").is_err());
+    }
+
+    #[test]
+    fn test_selection_is_deterministic() {
+        let mut corpus = Corpus::default();
+        assert!(corpus.lucky_item(&HashBits::from_seed(2026, "alice"), Lang::En).is_none());
+        corpus.items = (0..256).map(|i| Phrase::Plain(format!("corpus item {}", i))).collect();
+
+        let picks: Vec<_> = (0..200).map(|i| corpus.lucky_item(&HashBits::from_seed(2026, &i.to_string()), Lang::En).unwrap()).collect();
+        let again: Vec<_> = (0..200).map(|i| corpus.lucky_item(&HashBits::from_seed(2026, &i.to_string()), Lang::En).unwrap()).collect();
+        assert_eq!(picks, again);
+        // Half of the table comes from the corpus; both halves are drawn
+        let from_corpus = picks.iter().filter(|item| item.starts_with("corpus item")).count();
+        assert!(from_corpus > 50 && from_corpus < 150, "{}", from_corpus);
+        assert!(corpus.poem(&HashBits::from_seed(2026, "alice")).is_none());
+    }
+}
@@ -0,0 +1,34 @@
+//! Day-of-year dates, shared by the lucky day, the ICS export and the achievements.
+
+use chrono::NaiveDate;
+use std::ops::RangeInclusive;
+
+/// Years accepted on the command line: four-digit years, well within chrono's range
+pub const YEARS: RangeInclusive<u32> = 1..=9999;
+
+/// The `ordinal`th day (1-based) of `year`; leap years count February 29th, so day
+/// 365 is December 30th in them. None outside chrono's range of years
+pub fn day_of_year(year: u32, ordinal: u16) -> Option<NaiveDate> {
+    NaiveDate::from_yo_opt(i32::try_from(year).ok()?, ordinal as u32)
+}
+
+/// Month, day and weekday, e.g. `July 31 (Fri)`
+pub fn label(date: NaiveDate) -> String {
+    date.format("%B %-d (%a)").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_day_of_year() {
+        assert_eq!(day_of_year(2026, 212), NaiveDate::from_ymd_opt(2026, 7, 31));
+        assert_eq!(day_of_year(2028, 212), NaiveDate::from_ymd_opt(2028, 7, 30));
+        assert_eq!(day_of_year(2028, 365), NaiveDate::from_ymd_opt(2028, 12, 30));
+        assert_eq!(label(day_of_year(2026, 212).unwrap()), "July 31 (Fri)");
+        assert_eq!(label(day_of_year(2026, 1).unwrap()), "January 1 (Thu)");
+        assert_eq!(day_of_year(300_000, 1), None);
+        assert_eq!(day_of_year(u32::MAX, 1), None);
+    }
+}
@@ -0,0 +1,73 @@
+//! `--output-format discord`: a webhook message with one embed, colored by grade.
+
+use crate::art;
+use crate::luck::Grade;
+use crate::output::Fortune;
+use serde_json::{json, Value};
+
+/// Embed sidebar color (0xRRGGBB): vermilion for 大吉 down to violet for 大凶
+pub fn embed_color(grade: Grade) -> u32 {
+    match grade {
+        Grade::Daikichi => 0xE60033,
+        Grade::Chukichi => 0xF39800,
+        Grade::Shokichi => 0xF8D000,
+        Grade::Kichi => 0x3EB370,
+        Grade::Suekichi => 0x59B9C6,
+        Grade::Kyo => 0x9E9E9E,
+        Grade::Daikyo => 0x7B3DB8,
+    }
+}
+
+fn field(name: &str, value: String) -> Value {
+    json!({ "name": name, "value": value, "inline": true })
+}
+
+/// Title with the grade, the advice and the framed art as the description, inline fields, fingerprint footer
+pub fn payload(fortune: &Fortune) -> Value {
+    let mut best: Vec<_> = fortune.luck_scores.iter().collect();
+    best.sort_by_key(|s| std::cmp::Reverse(s.score));
+    let fields = vec![
+        field("Overall Score", format!("{} {}", fortune.overall_score, fortune.star_rating)),
+        field("Lucky Number", fortune.lucky_number.to_string()),
+        field("Lucky Color", fortune.lucky_color.clone()),
+        field("Lucky Day", fortune.lucky_day_text()),
+        field("Lucky Item", fortune.lucky_item.clone()),
+        field("Lucky Emoji", fortune.lucky_emoji.clone()),
+        field("Best Luck", best.first().map_or(String::new(), |s| format!("{} {}", s.label, s.score))),
+    ];
+    let art = art::framed(&fortune.art, fortune.year, fortune.grade, false);
+    json!({
+        "embeds": [{
+            "title": format!("🎍 Hash-Omikuji {}: {} ({:#}) 🎍", fortune.year, fortune.grade, fortune.grade),
+            "description": format!("> {}\n```\n{}```", fortune.advice, art),
+            "color": embed_color(fortune.grade),
+            "fields": fields,
+            "footer": { "text": fortune.fingerprint },
+        }],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_payload() {
+        let fortune = Fortune::builder().year(2026).seed("alice").draw();
+        let payload = payload(&fortune);
+        let embed = &payload["embeds"][0];
+        assert_eq!(embed["color"], embed_color(fortune.grade));
+        assert!(embed["title"].as_str().unwrap().contains(&fortune.grade.to_string()));
+        assert!(embed["description"].as_str().unwrap().contains("```\n+-[hash-omikuji 2026]"));
+        // Discord allows at most 25 fields per embed
+        assert!(embed["fields"].as_array().unwrap().len() <= 25);
+        assert_eq!(embed["footer"]["text"], fortune.fingerprint);
+    }
+
+    #[test]
+    fn test_embed_colors_distinct() {
+        let mut colors: Vec<_> = Grade::ALL.iter().map(|&g| embed_color(g)).collect();
+        colors.dedup();
+        assert_eq!(colors.len(), 7);
+    }
+}
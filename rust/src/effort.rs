@@ -0,0 +1,135 @@
+use crate::fingerprint::hex;
+use argon2::{Algorithm, Argon2, Params, Version};
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+pub const MIN_LEVEL: u8 = 1;
+pub const MAX_LEVEL: u8 = 10;
+
+/// Argon2id settings for one "shake" of the box; a level runs `rounds` shakes back to back
+const MEMORY_KIB: u32 = 32 * 1024;
+const ITERATIONS: u32 = 2;
+const PARALLELISM: u32 = 1;
+const OUTPUT_LEN: usize = 32;
+
+/// Stretching parameters, recorded in the output so a draw can be reproduced
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema)]
+#[cfg_attr(feature = "serve", derive(utoipa::ToSchema))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject), graphql(rename_fields = "snake_case"))]
+pub struct EffortParams {
+    pub level: u8,
+    pub algorithm: String,
+    pub memory_kib: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+    pub rounds: u32,
+}
+
+impl EffortParams {
+    pub fn for_level(level: u8) -> Self {
+        let level = level.clamp(MIN_LEVEL, MAX_LEVEL);
+        Self {
+            level,
+            algorithm: "argon2id".to_string(),
+            memory_kib: MEMORY_KIB,
+            iterations: ITERATIONS,
+            parallelism: PARALLELISM,
+            rounds: level as u32,
+        }
+    }
+}
+
+/// Stretch the seed through chained Argon2id rounds and return the hex result,
+/// which then replaces the seed in the SHA-256 derivation.
+/// `progress(done, total)` is called after every round.
+pub fn stretch_seed(
+    seed: &str,
+    year: u32,
+    params: &EffortParams,
+    mut progress: impl FnMut(u32, u32),
+) -> String {
+    let argon2 = Argon2::new(
+        Algorithm::Argon2id,
+        Version::V0x13,
+        Params::new(params.memory_kib, params.iterations, params.parallelism, Some(OUTPUT_LEN))
+            .expect("valid argon2 params"),
+    );
+    // Year-bound salt keeps stretched seeds from being reused across years
+    let salt = format!("hash-omikuji-effort-{}", year);
+
+    let mut block = Zeroizing::new(seed.as_bytes().to_vec());
+    for round in 0..params.rounds {
+        let mut output = Zeroizing::new([0u8; OUTPUT_LEN]);
+        argon2
+            .hash_password_into(&block, salt.as_bytes(), output.as_mut())
+            .expect("argon2 output length is valid");
+        block = Zeroizing::new(output.to_vec());
+        progress(round + 1, params.rounds);
+    }
+    hex(&block[..])
+}
+
+/// Progress bar for stderr, e.g. "Shaking the box... [###-------] 3/10"
+pub fn progress_line(done: u32, total: u32) -> String {
+    let width = MAX_LEVEL as usize;
+    let filled = (done as usize * width) / total.max(1) as usize;
+    format!(
+        "Shaking the box... [{}{}] {}/{}",
+        "#".repeat(filled),
+        "-".repeat(width - filled),
+        done,
+        total
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn light_params(rounds: u32) -> EffortParams {
+        EffortParams {
+            memory_kib: 64,
+            iterations: 1,
+            rounds,
+            ..EffortParams::for_level(1)
+        }
+    }
+
+    #[test]
+    fn test_for_level_clamps() {
+        assert_eq!(EffortParams::for_level(0).level, MIN_LEVEL);
+        assert_eq!(EffortParams::for_level(99).rounds, MAX_LEVEL as u32);
+        assert_eq!(EffortParams::for_level(3).rounds, 3);
+    }
+
+    #[test]
+    fn test_stretch_deterministic() {
+        let params = light_params(2);
+        let a = stretch_seed("alice", 2026, &params, |_, _| {});
+        let b = stretch_seed("alice", 2026, &params, |_, _| {});
+        assert_eq!(a, b);
+        assert_eq!(a.len(), 64);
+    }
+
+    #[test]
+    fn test_stretch_depends_on_inputs() {
+        let params = light_params(1);
+        let base = stretch_seed("alice", 2026, &params, |_, _| {});
+        assert_ne!(base, stretch_seed("bob", 2026, &params, |_, _| {}));
+        assert_ne!(base, stretch_seed("alice", 2027, &params, |_, _| {}));
+        assert_ne!(base, stretch_seed("alice", 2026, &light_params(2), |_, _| {}));
+    }
+
+    #[test]
+    fn test_stretch_reports_progress() {
+        let mut seen = Vec::new();
+        stretch_seed("alice", 2026, &light_params(3), |done, total| seen.push((done, total)));
+        assert_eq!(seen, vec![(1, 3), (2, 3), (3, 3)]);
+    }
+
+    #[test]
+    fn test_progress_line() {
+        assert_eq!(progress_line(3, 10), "Shaking the box... [###-------] 3/10");
+        assert_eq!(progress_line(1, 1), "Shaking the box... [##########] 1/1");
+    }
+}
@@ -0,0 +1,119 @@
+//! Errors of the binary, each with its own exit code so scripts can tell "not New Year's
+//! Day" from a real failure. The file-backed modules (history, capsule, team, tenants,
+//! hook) return an [`Error`] directly; other library functions mostly return `String`s,
+//! which convert to [`Error::Other`] unless the call site picks a variant. Under `--json` the
+//! error is printed as a JSON document instead, see [`Error::to_json`].
+
+use serde_json::{json, Value};
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum Error {
+    /// Exit code 1: any other failure
+    #[error("{0}")]
+    Other(String),
+    /// Exit code 3: the draw is not allowed on this date
+    #[error("{0}")]
+    DateRestriction(String),
+    /// Exit code 4: a seed that cannot be drawn, e.g. an input line that is not UTF-8
+    #[error("Invalid seed: {0}")]
+    InvalidSeed(String),
+    /// Exit code 5: a file or stream could not be read or written
+    #[error("{0}")]
+    Io(String),
+    /// Exit code 6: malformed input (config file, JSON, token, spec)
+    #[error("{0}")]
+    Format(String),
+    /// Exit code 7: a signature, checksum, token, capsule or conformance check failed
+    #[error("{0}")]
+    Verify(String),
+    /// Exit code 8: a webhook or broker could not be reached
+    #[error("{0}")]
+    Network(String),
+}
+
+impl Error {
+    /// Exit code of the process; 2 is left to command-line usage errors
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Other(_) => 1,
+            Error::DateRestriction(_) => 3,
+            Error::InvalidSeed(_) => 4,
+            Error::Io(_) => 5,
+            Error::Format(_) => 6,
+            Error::Verify(_) => 7,
+            Error::Network(_) => 8,
+        }
+    }
+
+    /// Stable name of the failure kind for machine readers, e.g. `date_restriction`
+    pub fn code(&self) -> &'static str {
+        match self {
+            Error::Other(_) => "other",
+            Error::DateRestriction(_) => "date_restriction",
+            Error::InvalidSeed(_) => "invalid_seed",
+            Error::Io(_) => "io",
+            Error::Format(_) => "format",
+            Error::Verify(_) => "verify",
+            Error::Network(_) => "network",
+        }
+    }
+
+    /// `{"error": {"code", "message", "exit_code"}}`, printed instead of the message under `--json`
+    pub fn to_json(&self) -> Value {
+        json!({
+            "error": {
+                "code": self.code(),
+                "message": self.to_string(),
+                "exit_code": self.exit_code(),
+            }
+        })
+    }
+}
+
+impl From<String> for Error {
+    fn from(message: String) -> Self {
+        Error::Other(message)
+    }
+}
+
+impl From<&str> for Error {
+    fn from(message: &str) -> Self {
+        Error::Other(message.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exit_codes_are_distinct() {
+        let errors = [
+            Error::Other(String::new()),
+            Error::DateRestriction(String::new()),
+            Error::InvalidSeed(String::new()),
+            Error::Io(String::new()),
+            Error::Format(String::new()),
+            Error::Verify(String::new()),
+            Error::Network(String::new()),
+        ];
+        let mut codes: Vec<i32> = errors.iter().map(Error::exit_code).collect();
+        codes.sort();
+        codes.dedup();
+        assert_eq!(codes.len(), errors.len());
+        assert!(!codes.contains(&0) && !codes.contains(&2));
+        assert_eq!(Error::from("boom").to_string(), "boom");
+        assert_eq!(Error::InvalidSeed("line 3 is not valid UTF-8".into()).to_string(), "Invalid seed: line 3 is not valid UTF-8");
+    }
+
+    #[test]
+    fn test_json_error() {
+        let error = Error::DateRestriction("This command can only be executed on January 1st-3rd.".into());
+        assert_eq!(
+            error.to_json(),
+            json!({"error": {"code": "date_restriction", "message": "This command can only be executed on January 1st-3rd.", "exit_code": 3}})
+        );
+        assert_eq!(Error::InvalidSeed("x".into()).to_json()["error"]["message"], "Invalid seed: x");
+    }
+}
@@ -0,0 +1,105 @@
+//! The twelve zodiac animals (eto): the animal of the drawn year as a small banner
+//! atop the interactive text output. The animal changes on January 1st, as on
+//! Japanese New Year's cards, not at the lunar new year.
+
+use crate::lang::Lang;
+use crate::theme::{rgb, Palette};
+
+/// A zodiac animal with its three-line ASCII art
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Eto {
+    pub kanji: &'static str,
+    pub reading: &'static str,
+    pub kana: &'static str,
+    pub animal: &'static str,
+    pub emoji: &'static str,
+    pub art: [&'static str; 3],
+}
+
+/// Rat first; 2020 was a year of the Rat
+pub const ETO: [Eto; 12] = [
+    Eto { kanji: "子", reading: "ne", kana: "ね", animal: "Rat", emoji: "🐀", art: [r"  ()_()   ", r"  (o.o)__ ", r"  (_)(_) ~"] },
+    Eto { kanji: "丑", reading: "ushi", kana: "うし", animal: "Ox", emoji: "🐂", art: [r" \_~~~_/  ", r"  (o o)   ", r"   (oo)   "] },
+    Eto { kanji: "寅", reading: "tora", kana: "とら", animal: "Tiger", emoji: "🐅", art: [r"  /\_/\   ", r" (=o.o=)  ", r"  > ^ <   "] },
+    Eto { kanji: "卯", reading: "u", kana: "う", animal: "Rabbit", emoji: "🐇", art: [r"  (\(\    ", r"  ( -.-)  ", r"  o(_(_)  "] },
+    Eto { kanji: "辰", reading: "tatsu", kana: "たつ", animal: "Dragon", emoji: "🐉", art: [r"   __/\_  ", r" <(o   )~~", r"   ^^ ^^  "] },
+    Eto { kanji: "巳", reading: "mi", kana: "み", animal: "Snake", emoji: "🐍", art: [r"     __   ", r"  __/ o\< ", r" /___/    "] },
+    Eto { kanji: "午", reading: "uma", kana: "うま", animal: "Horse", emoji: "🐎", art: [r"    _/|   ", r"   / o \_ ", r"  /|~~|   "] },
+    Eto { kanji: "未", reading: "hitsuji", kana: "ひつじ", animal: "Sheep", emoji: "🐑", art: [r"  @@@@@   ", r" @(o o)@  ", r"  || ||   "] },
+    Eto { kanji: "申", reading: "saru", kana: "さる", animal: "Monkey", emoji: "🐒", art: [r"   .-^-.  ", r" c( o o )o", r"   ( - )  "] },
+    Eto { kanji: "酉", reading: "tori", kana: "とり", animal: "Rooster", emoji: "🐓", art: [r"   ,~~.   ", r"  (o  >   ", r"  /)_)    "] },
+    Eto { kanji: "戌", reading: "inu", kana: "いぬ", animal: "Dog", emoji: "🐕", art: [r"  /^-^\   ", r" / o o \  ", r"  \_v_/   "] },
+    Eto { kanji: "亥", reading: "i", kana: "い", animal: "Boar", emoji: "🐗", art: [r"  ,-v-,   ", r" (o oo o) ", r"  \_~~_/  "] },
+];
+
+/// The animal of `year`
+pub fn of_year(year: u32) -> &'static Eto {
+    &ETO[(year as usize + 8) % 12]
+}
+
+impl Eto {
+    /// `🐎 午年 (uma) · Year of the Horse`, or `Year of the Horse (uma)` in ASCII
+    pub fn caption(&self, lang: Lang, ascii: bool) -> String {
+        match (ascii, lang) {
+            (true, _) => format!("Year of the {} ({})", self.animal, self.reading),
+            (false, Lang::Ja) => format!("{} {}年 ({}どし)", self.emoji, self.kanji, self.kana),
+            (false, Lang::En) => format!("{} {}年 ({}) · Year of the {}", self.emoji, self.kanji, self.reading, self.animal),
+        }
+    }
+
+    /// The art with the caption beside its middle line, the art in the accent color
+    /// of `palette` if given
+    pub fn banner(&self, lang: Lang, ascii: bool, palette: Option<&Palette>) -> String {
+        let paint = |line: &str| match palette {
+            Some(palette) => {
+                let (r, g, b) = rgb(palette.accent);
+                format!("\x1b[38;2;{};{};{}m{}\x1b[0m", r, g, b, line)
+            }
+            None => line.to_string(),
+        };
+        let caption = self.caption(lang, ascii);
+        let mut output = String::new();
+        for (i, line) in self.art.iter().enumerate() {
+            if i == 1 {
+                output.push_str(&format!("{} {}\n", paint(line), caption));
+            } else {
+                output.push_str(&format!("{}\n", paint(line.trim_end())));
+            }
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::theme::Theme;
+
+    #[test]
+    fn test_of_year() {
+        assert_eq!(of_year(2026).animal, "Horse");
+        assert_eq!(of_year(2020).kanji, "子");
+        assert_eq!(of_year(2031).kanji, "亥");
+        assert_eq!(of_year(1984).animal, "Rat");
+    }
+
+    #[test]
+    fn test_art_is_ascii() {
+        for eto in &ETO {
+            assert!(eto.art.iter().all(|line| line.is_ascii() && line.len() == eto.art[0].len()), "{}", eto.animal);
+        }
+    }
+
+    #[test]
+    fn test_banner() {
+        let horse = of_year(2026);
+        let banner = horse.banner(Lang::En, false, None);
+        assert_eq!(banner.lines().count(), 3);
+        assert!(banner.lines().nth(1).unwrap().ends_with(" 🐎 午年 (uma) · Year of the Horse"));
+        assert!(horse.banner(Lang::En, true, None).is_ascii());
+        assert!(horse.banner(Lang::Ja, false, None).contains("午年 (うまどし)"));
+
+        let colored = horse.banner(Lang::En, true, Some(&Theme::Shrine.palette()));
+        assert!(colored.starts_with("\x1b[38;2;215;38;30m"));
+    }
+}
@@ -0,0 +1,99 @@
+//! `family`: the fortunes of every household member in one grouped report, for New
+//! Year's morning at the breakfast table. Members are listed in the config file.
+
+use crate::builder::FortuneBuilder;
+use crate::lang::display_width;
+use crate::output::Fortune;
+use serde::Deserialize;
+
+/// A household member: a display name, and the seed drawn for them
+///
+/// ```toml
+/// [[family]]
+/// name = "Mom"
+/// seed = "mom@home"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Member {
+    pub name: String,
+    /// Seed of the member's draw (default: the name)
+    pub seed: Option<String>,
+}
+
+impl Member {
+    pub fn seed(&self) -> &str {
+        self.seed.as_deref().unwrap_or(&self.name)
+    }
+}
+
+/// The members to draw: every configured one, or those named by `--for`; a name that is
+/// not configured is drawn as its own seed
+pub fn select(configured: &[Member], names: &[String]) -> Result<Vec<Member>, String> {
+    let members: Vec<Member> = if names.is_empty() {
+        configured.to_vec()
+    } else {
+        names
+            .iter()
+            .map(|name| {
+                configured
+                    .iter()
+                    .find(|member| member.name.eq_ignore_ascii_case(name))
+                    .cloned()
+                    .unwrap_or_else(|| Member { name: name.clone(), seed: None })
+            })
+            .collect()
+    };
+    if members.is_empty() {
+        return Err("No family members: add [[family]] entries to the config file, or use --for <NAME>".to_string());
+    }
+    Ok(members)
+}
+
+pub fn draw(builder: &FortuneBuilder, members: &[Member]) -> Vec<(String, Fortune)> {
+    members.iter().map(|member| (member.name.clone(), builder.clone().seed(member.seed()).draw())).collect()
+}
+
+/// One block per member, then who drew best
+pub fn report(year: u32, draws: &[(String, Fortune)]) -> String {
+    let mut output = format!("🎍 Hash-Omikuji {} · Family 🎍\n", year);
+    for (name, fortune) in draws {
+        output.push_str(&format!("\n── {} {}\n", name, "─".repeat(36usize.saturating_sub(display_width(name)))));
+        output.push_str(&format!("{} ({:#})  {} {}\n", fortune.grade, fortune.grade, fortune.overall_score, fortune.star_rating));
+        output.push_str(&format!("Lucky Number : {}   Lucky Day : {}\n", fortune.lucky_number, fortune.lucky_date_label));
+        output.push_str(&format!("Lucky Item   : {}\n", fortune.lucky_item));
+        output.push_str(&format!("Advice       : {}\n", fortune.advice));
+    }
+    // Grades rank first, then the overall score; ties go to the first listed
+    if let Some((name, best)) = draws.iter().rev().max_by_key(|(_, fortune)| (fortune.grade, fortune.overall_score)) {
+        if draws.len() > 1 {
+            output.push_str(&format!("\nBest luck this year: {} ({}, {})\n", name, best.grade, best.overall_score));
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_family_report() {
+        let configured = vec![
+            Member { name: "Mom".to_string(), seed: Some("mom@home".to_string()) },
+            Member { name: "Ken".to_string(), seed: None },
+        ];
+        assert_eq!(select(&configured, &[]).unwrap(), configured);
+        let chosen = select(&configured, &["mom".to_string(), "guest".to_string()]).unwrap();
+        assert_eq!(chosen.iter().map(Member::seed).collect::<Vec<_>>(), ["mom@home", "guest"]);
+        assert!(select(&[], &[]).is_err());
+
+        let builder = Fortune::builder().year(2026);
+        let draws = draw(&builder, &configured);
+        assert_eq!(draws[0].1, builder.clone().seed("mom@home").draw());
+        let text = report(2026, &draws);
+        assert!(text.starts_with("🎍 Hash-Omikuji 2026 · Family 🎍\n"));
+        assert!(text.contains("\n── Mom ─") && text.contains("\n── Ken ─"));
+        assert!(text.contains("Best luck this year: "));
+    }
+}
@@ -0,0 +1,59 @@
+//! `feed`: an Atom feed with one entry per team member's fortune.
+
+use crate::output::{seed_fingerprint, Fortune};
+use chrono::{DateTime, Utc};
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// Atom document for `year`: entries titled `name: grade`, with the markdown fortune as content.
+/// Ids use the seed fingerprint, so a regenerated feed keeps its entries.
+pub fn atom(year: u32, members: &[(String, Fortune)], updated: DateTime<Utc>) -> String {
+    let published = format!("{}-01-01T00:00:00Z", year);
+    let mut output = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    output.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+    output.push_str(&format!("  <id>urn:hash-omikuji:{}</id>\n", year));
+    output.push_str(&format!("  <title>Hash-Omikuji {}</title>\n", year));
+    output.push_str(&format!("  <updated>{}</updated>\n", updated.format("%Y-%m-%dT%H:%M:%SZ")));
+    output.push_str("  <author><name>hash-omikuji</name></author>\n");
+    output.push_str("  <generator uri=\"https://github.com/elzup/hash-omikuji\">hash-omikuji</generator>\n");
+    for (name, fortune) in members {
+        output.push_str("  <entry>\n");
+        output.push_str(&format!("    <id>urn:hash-omikuji:{}:{}</id>\n", year, seed_fingerprint(name)));
+        output.push_str(&format!(
+            "    <title>{}: {} ({:#}) {}</title>\n",
+            escape(name),
+            fortune.grade,
+            fortune.grade,
+            fortune.lucky_emoji
+        ));
+        output.push_str(&format!("    <published>{}</published>\n", published));
+        output.push_str(&format!("    <updated>{}</updated>\n", published));
+        output.push_str(&format!("    <content type=\"text\">{}</content>\n", escape(&fortune.format_markdown())));
+        output.push_str("  </entry>\n");
+    }
+    output.push_str("</feed>\n");
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_atom() {
+        let members: Vec<_> = ["alice", "<bob>"]
+            .iter()
+            .map(|name| (name.to_string(), Fortune::builder().year(2026).seed(name).draw()))
+            .collect();
+        let xml = atom(2026, &members, Utc.with_ymd_and_hms(2026, 1, 1, 9, 0, 0).unwrap());
+        assert!(xml.contains("<updated>2026-01-01T09:00:00Z</updated>"));
+        assert_eq!(xml.matches("<entry>").count(), 2);
+        assert!(xml.contains(&format!("<title>alice: {} ({:#})", members[0].1.grade, members[0].1.grade)));
+        assert!(xml.contains("<title>&lt;bob&gt;: "));
+        assert!(xml.contains(&format!("<id>urn:hash-omikuji:2026:{}</id>", seed_fingerprint("alice"))));
+        assert!(xml.contains("<content type=\"text\">## 🎍 Hash-Omikuji 2026 🎍"));
+    }
+}
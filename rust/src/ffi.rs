@@ -0,0 +1,55 @@
+//! C ABI for embedding in C/C++/Swift apps. `include/hash_omikuji.h` is generated
+//! from this file by cbindgen when building with the `ffi` feature.
+
+use crate::output::Fortune;
+use std::ffi::{c_char, CStr, CString};
+use std::ptr;
+
+/// Draw the fortune for `year` and the NUL-terminated UTF-8 `seed` and return it as
+/// JSON (the `--json` document). Returns NULL if `seed` is NULL or not valid UTF-8.
+/// Release the result with `omikuji_string_free`.
+///
+/// # Safety
+///
+/// `seed` must be NULL or point to a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn omikuji_draw_json(year: u32, seed: *const c_char) -> *mut c_char {
+    if seed.is_null() {
+        return ptr::null_mut();
+    }
+    let Ok(seed) = CStr::from_ptr(seed).to_str() else {
+        return ptr::null_mut();
+    };
+    let json = Fortune::builder().year(year).seed(seed).draw().format_json();
+    CString::new(json).map_or(ptr::null_mut(), CString::into_raw)
+}
+
+/// Release a string returned by `omikuji_draw_json`; NULL is ignored.
+///
+/// # Safety
+///
+/// `s` must be NULL or a pointer returned by this library that has not been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn omikuji_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_draw_json() {
+        let seed = CString::new("alice").unwrap();
+        unsafe {
+            let json = omikuji_draw_json(2026, seed.as_ptr());
+            assert!(!json.is_null());
+            let expected = Fortune::builder().year(2026).seed("alice").draw().format_json();
+            assert_eq!(CStr::from_ptr(json).to_str().unwrap(), expected);
+            omikuji_string_free(json);
+            assert!(omikuji_draw_json(2026, ptr::null()).is_null());
+        }
+    }
+}
@@ -0,0 +1,283 @@
+use crate::pgp_words::{EVEN_WORDS, ODD_WORDS};
+use alloc::string::String;
+use alloc::vec::Vec;
+use alloc::format;
+
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const BASE64URL_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Fixed 64-entry alphabet for the emoji fingerprint (index = 6-bit value).
+/// Order is part of the output format: never reorder or replace entries.
+pub const EMOJI_ALPHABET: [char; 64] = [
+    '🐶', '🐱', '🐭', '🐹', '🐰', '🦊', '🐻', '🐼',
+    '🐨', '🐯', '🦁', '🐮', '🐷', '🐸', '🐵', '🐔',
+    '🐧', '🐦', '🐤', '🦆', '🦉', '🐺', '🐗', '🐴',
+    '🦄', '🐝', '🐛', '🦋', '🐌', '🐞', '🐢', '🐍',
+    '🦖', '🐙', '🦑', '🦀', '🐡', '🐠', '🐬', '🐳',
+    '🌵', '🌲', '🌴', '🍀', '🍁', '🍄', '🌻', '🌸',
+    '🌙', '⭐', '🌈', '⛄', '🔥', '💧', '🍎', '🍊',
+    '🍋', '🍌', '🍉', '🍇', '🍓', '🍒', '🍑', '🍍',
+];
+
+/// Emoji fingerprint length (8 x 6 bits = first 48 bits of the digest)
+pub const EMOJI_COUNT: usize = 8;
+
+/// Number of leading digest bytes kept by the short form (16 hex chars)
+const SHORT_BYTES: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum FingerprintFormat {
+    /// Full digest as lowercase hex (64 chars)
+    #[default]
+    Hex,
+    /// RFC 4648 base32, unpadded (52 chars)
+    Base32,
+    /// Bitcoin-alphabet base58 (~44 chars)
+    Base58,
+    /// RFC 4648 URL-safe base64, unpadded (43 chars)
+    Base64url,
+    /// First 8 bytes as hex (16 chars)
+    Short,
+    /// PGP word list, one word per byte (32 words)
+    Pgp,
+}
+
+pub fn encode(bytes: &[u8], format: FingerprintFormat) -> String {
+    match format {
+        FingerprintFormat::Hex => hex(bytes),
+        FingerprintFormat::Base32 => base32(bytes),
+        FingerprintFormat::Base58 => base58(bytes),
+        FingerprintFormat::Base64url => base64url(bytes),
+        FingerprintFormat::Short => hex(&bytes[..SHORT_BYTES.min(bytes.len())]),
+        FingerprintFormat::Pgp => pgp_words(bytes),
+    }
+}
+
+pub fn hex(bytes: &[u8]) -> String {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+    let mut hex = String::with_capacity(bytes.len() * 2);
+    for &b in bytes {
+        hex.push(DIGITS[(b >> 4) as usize] as char);
+        hex.push(DIGITS[(b & 0xf) as usize] as char);
+    }
+    hex
+}
+
+/// Inverse of `hex`; returns None on odd length or non-hex characters
+pub fn decode_hex(text: &str) -> Option<Vec<u8>> {
+    let text = text.trim();
+    if !text.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(text.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// Generic MSB-first bit packer shared by base32 and base64 variants (no padding)
+fn encode_bits(bytes: &[u8], bits_per_char: u32, alphabet: &[u8]) -> String {
+    let mask = (1u32 << bits_per_char) - 1;
+    let mut output = String::with_capacity((bytes.len() * 8).div_ceil(bits_per_char as usize));
+    let mut buffer: u32 = 0;
+    let mut buffered: u32 = 0;
+    for &byte in bytes {
+        buffer = (buffer << 8) | byte as u32;
+        buffered += 8;
+        while buffered >= bits_per_char {
+            buffered -= bits_per_char;
+            output.push(alphabet[((buffer >> buffered) & mask) as usize] as char);
+        }
+    }
+    if buffered > 0 {
+        output.push(alphabet[((buffer << (bits_per_char - buffered)) & mask) as usize] as char);
+    }
+    output
+}
+
+pub fn base32(bytes: &[u8]) -> String {
+    encode_bits(bytes, 5, BASE32_ALPHABET)
+}
+
+pub fn base64url(bytes: &[u8]) -> String {
+    encode_bits(bytes, 6, BASE64URL_ALPHABET)
+}
+
+/// Inverse of `base64url`; returns None on characters outside the alphabet or leftover bits
+pub fn decode_base64url(text: &str) -> Option<Vec<u8>> {
+    let mut output = Vec::new();
+    let mut buffer: u32 = 0;
+    let mut buffered: u32 = 0;
+    for c in text.trim().bytes() {
+        let value = BASE64URL_ALPHABET.iter().position(|&a| a == c)? as u32;
+        buffer = (buffer << 6) | value;
+        buffered += 6;
+        if buffered >= 8 {
+            buffered -= 8;
+            output.push((buffer >> buffered) as u8);
+        }
+    }
+    // Unpadded input ends in fewer than 6 spare bits, all zero
+    (buffered < 6 && buffer & ((1 << buffered) - 1) == 0).then_some(output)
+}
+
+/// Standard base64 without padding, as printed by `ssh-keygen -l`
+pub fn base64(bytes: &[u8]) -> String {
+    encode_bits(bytes, 6, BASE64_ALPHABET)
+}
+
+/// OpenSSH-style one-liner: `SHA256:<unpadded base64>`
+pub fn openssh(bytes: &[u8]) -> String {
+    format!("SHA256:{}", base64(bytes))
+}
+
+pub fn base58(bytes: &[u8]) -> String {
+    // Big-number division by 58, little-endian digit accumulator
+    let mut digits: Vec<u8> = Vec::new();
+    for &byte in bytes {
+        let mut carry = byte as u32;
+        for digit in digits.iter_mut() {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+    let leading_zeros = bytes.iter().take_while(|&&b| b == 0).count();
+    let mut output = String::with_capacity(leading_zeros + digits.len());
+    output.extend(core::iter::repeat_n('1', leading_zeros));
+    output.extend(digits.iter().rev().map(|&d| BASE58_ALPHABET[d as usize] as char));
+    output
+}
+
+/// PGP word list rendering: even/odd byte positions alternate between word lists
+pub fn pgp_words(bytes: &[u8]) -> String {
+    let mut words = String::with_capacity(bytes.len() * 10);
+    for (i, &b) in bytes.iter().enumerate() {
+        if i > 0 {
+            words.push(' ');
+        }
+        words.push_str(if i % 2 == 0 { EVEN_WORDS[b as usize] } else { ODD_WORDS[b as usize] });
+    }
+    words
+}
+
+/// Signal safety-number style fingerprint: 8 emoji separated by spaces
+pub fn emoji(bytes: &[u8]) -> String {
+    let mut emoji = String::with_capacity(EMOJI_COUNT * 5);
+    for i in 0..EMOJI_COUNT {
+        let mut index = 0usize;
+        for bit in i * 6..(i + 1) * 6 {
+            index = (index << 1) | ((bytes[bit / 8] >> (7 - bit % 8)) & 1) as usize;
+        }
+        if i > 0 {
+            emoji.push(' ');
+        }
+        emoji.push(EMOJI_ALPHABET[index]);
+    }
+    emoji
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base32_rfc4648_vectors() {
+        assert_eq!(base32(b""), "");
+        assert_eq!(base32(b"f"), "MY");
+        assert_eq!(base32(b"fo"), "MZXQ");
+        assert_eq!(base32(b"foo"), "MZXW6");
+        assert_eq!(base32(b"foob"), "MZXW6YQ");
+        assert_eq!(base32(b"fooba"), "MZXW6YTB");
+        assert_eq!(base32(b"foobar"), "MZXW6YTBOI");
+    }
+
+    #[test]
+    fn test_base64url_rfc4648_vectors() {
+        assert_eq!(base64url(b""), "");
+        assert_eq!(base64url(b"f"), "Zg");
+        assert_eq!(base64url(b"fo"), "Zm8");
+        assert_eq!(base64url(b"foo"), "Zm9v");
+        assert_eq!(base64url(b"foobar"), "Zm9vYmFy");
+        assert_eq!(base64url(&[0xfb, 0xff]), "-_8");
+    }
+
+    #[test]
+    fn test_decode_base64url() {
+        for bytes in [&b""[..], b"f", b"fo", b"foo", b"foobar", &[0xfb, 0xff]] {
+            assert_eq!(decode_base64url(&base64url(bytes)).as_deref(), Some(bytes));
+        }
+        assert_eq!(decode_base64url("Zm9"), None);
+        assert_eq!(decode_base64url("Zm+v"), None);
+    }
+
+    #[test]
+    fn test_base64_standard_alphabet() {
+        assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+        assert_eq!(base64(&[0xfb, 0xff]), "+/8");
+    }
+
+    #[test]
+    fn test_openssh_format() {
+        let line = openssh(&[0u8; 32]);
+        assert_eq!(line, format!("SHA256:{}", "A".repeat(43)));
+    }
+
+    #[test]
+    fn test_emoji_alphabet_unique() {
+        let mut sorted = EMOJI_ALPHABET.to_vec();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(sorted.len(), 64);
+    }
+
+    #[test]
+    fn test_emoji_fingerprint() {
+        assert_eq!(emoji(&[0u8; 32]), ["🐶"; 8].join(" "));
+        assert_eq!(emoji(&[0xFFu8; 32]), ["🍍"; 8].join(" "));
+        // 0b000001 000010 ... first 6 bits select index 1 (🐱)
+        assert!(emoji(&[0x04, 0x20, 0, 0, 0, 0]).starts_with("🐱 🐭"));
+        assert_eq!(emoji(&[0x5Au8; 32]).split(' ').count(), EMOJI_COUNT);
+    }
+
+    #[test]
+    fn test_pgp_words() {
+        assert_eq!(pgp_words(&[0x00, 0x00]), "aardvark adroitness");
+        assert_eq!(pgp_words(&[0xFF, 0xFF]), "Zulu Yucatan");
+        assert_eq!(pgp_words(&[0xE5, 0x82, 0x94]), "topmost Istanbul Pluto");
+        assert_eq!(encode(&[0u8; 32], FingerprintFormat::Pgp).split(' ').count(), 32);
+    }
+
+    #[test]
+    fn test_decode_hex_roundtrip() {
+        let bytes = [0x00, 0x7f, 0xab, 0xff];
+        assert_eq!(decode_hex(&hex(&bytes)), Some(bytes.to_vec()));
+        assert_eq!(decode_hex("abc"), None);
+        assert_eq!(decode_hex("zz"), None);
+    }
+
+    #[test]
+    fn test_base58_vectors() {
+        assert_eq!(base58(b""), "");
+        assert_eq!(base58(b"Hello World!"), "2NEpo7TZRRrLZSi2U");
+        assert_eq!(base58(&[0, 0, 1]), "112");
+    }
+
+    #[test]
+    fn test_encode_lengths() {
+        let bytes = [0xABu8; 32];
+        assert_eq!(encode(&bytes, FingerprintFormat::Hex).len(), 64);
+        assert_eq!(encode(&bytes, FingerprintFormat::Base32).len(), 52);
+        assert_eq!(encode(&bytes, FingerprintFormat::Base64url).len(), 43);
+        assert_eq!(encode(&bytes, FingerprintFormat::Short), "abababababababab");
+    }
+}
@@ -0,0 +1,93 @@
+//! `forecast`: a preview of the fortunes of coming years for one seed. Nothing is
+//! recorded; each year's fortune is still only drawn on its New Year's Day.
+
+use crate::builder::FortuneBuilder;
+use crate::date::YEARS;
+use crate::output::Fortune;
+use std::ops::RangeInclusive;
+
+/// Years shown after the current one when --years is not given
+pub const DEFAULT_SPAN: u32 = 10;
+/// Most years one table may cover
+pub const MAX_YEARS: u32 = 100;
+
+/// The default table: `year` and the DEFAULT_SPAN years after it, up to the last drawable year
+pub fn default_years(year: u32) -> RangeInclusive<u32> {
+    year..=year.saturating_add(DEFAULT_SPAN).min(*YEARS.end())
+}
+
+/// `2026..2036` (inclusive) or a single `2030`, within 1-9999
+pub fn parse_years(text: &str) -> Result<RangeInclusive<u32>, String> {
+    let invalid = || format!("invalid year range {:?} (expected FROM..TO, e.g. 2026..2036)", text);
+    let (from, to) = text.split_once("..").unwrap_or((text, text));
+    let from: u32 = from.trim().parse().map_err(|_| invalid())?;
+    let to: u32 = to.trim_start_matches('=').trim().parse().map_err(|_| invalid())?;
+    if from > to {
+        return Err(invalid());
+    }
+    if let Some(year) = [from, to].into_iter().find(|year| !YEARS.contains(year)) {
+        return Err(format!("year {} is out of range ({}-{})", year, YEARS.start(), YEARS.end()));
+    }
+    if to - from >= MAX_YEARS {
+        return Err(format!("a forecast covers at most {} years", MAX_YEARS));
+    }
+    Ok(from..=to)
+}
+
+/// The fortune of each year in `years`, drawn with `builder`'s seed and settings
+pub fn draw(builder: &FortuneBuilder, years: RangeInclusive<u32>) -> Vec<Fortune> {
+    years.map(|year| builder.clone().year(year).draw()).collect()
+}
+
+/// One row per year, each marked as a preview
+pub fn format_table(fortunes: &[Fortune]) -> String {
+    let mut output = String::from("Year  Grade   Score        Lucky Day\n");
+    for fortune in fortunes {
+        let grade = fortune.grade.to_string();
+        let pad = 8 - grade.chars().count() * 2;
+        output.push_str(&format!(
+            "{}  {}{}{:>3} {}  {:<20} (preview)\n",
+            fortune.year,
+            grade,
+            " ".repeat(pad),
+            fortune.overall_score,
+            fortune.star_rating,
+            fortune.lucky_date_label
+        ));
+    }
+    output.push_str("\nPreviews only: each year's fortune is drawn on its New Year's Day.\n");
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_forecast() {
+        assert_eq!(parse_years("2026..2036"), Ok(2026..=2036));
+        assert_eq!(parse_years("2026..=2030"), Ok(2026..=2030));
+        assert_eq!(parse_years("2030"), Ok(2030..=2030));
+        assert!(parse_years("2036..2026").is_err());
+        assert!(parse_years("2026..").is_err());
+        assert!(parse_years("2000..2200").is_err());
+        assert!(parse_years("0").unwrap_err().contains("out of range"));
+        assert!(parse_years("0..5").is_err());
+        assert_eq!(parse_years("9999"), Ok(9999..=9999));
+        assert_eq!(parse_years("9990..9999"), Ok(9990..=9999));
+        assert!(parse_years("10000").is_err());
+        assert!(parse_years("9999..10000").is_err());
+        assert!(parse_years("4294967295").is_err());
+        assert_eq!(default_years(2026), 2026..=2036);
+        assert_eq!(default_years(9995), 9995..=9999);
+        assert_eq!(default_years(9999), 9999..=9999);
+
+        let builder = Fortune::builder().seed("alice");
+        let fortunes = draw(&builder, 2026..=2028);
+        assert_eq!(fortunes.iter().map(|f| f.year).collect::<Vec<_>>(), [2026, 2027, 2028]);
+        assert_eq!(fortunes[0], builder.clone().year(2026).draw());
+        let table = format_table(&fortunes);
+        assert_eq!(table.matches("(preview)").count(), 3);
+        assert!(table.contains(&fortunes[1].lucky_date_label));
+    }
+}
@@ -0,0 +1,121 @@
+//! fortune(6) support: fortune-cookie text for `--output-format fortune`, and the
+//! `fortunes` subcommand's %-delimited file with its strfile(8) `.dat` index.
+
+use crate::lang::display_width;
+use crate::output::Fortune;
+
+/// Column the advice is wrapped at
+const WRAP: usize = 72;
+
+/// Header version of the index written by fortune-mod's strfile
+const STRFILE_VERSION: u32 = 2;
+
+/// The cookie: the wrapped advice and an attribution line
+pub fn cookie(fortune: &Fortune) -> String {
+    format!("{}\t\t-- hash-omikuji {}, {} ({:#})\n", wrap(&fortune.advice, WRAP), fortune.year, fortune.grade, fortune.grade)
+}
+
+/// Greedy word wrap; every line ends in a newline and a word longer than `width` gets its own line
+fn wrap(text: &str, width: usize) -> String {
+    let mut output = String::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        if !line.is_empty() && display_width(&line) + 1 + display_width(word) > width {
+            output.push_str(&line);
+            output.push('\n');
+            line.clear();
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    output.push_str(&line);
+    output.push('\n');
+    output
+}
+
+/// A fortunes file: every entry followed by a `%` line
+pub fn file(entries: &[String]) -> String {
+    entries.iter().map(|entry| format!("{}%\n", entry)).collect()
+}
+
+/// strfile(8) index of a %-delimited file, as fortune-mod reads it: a big-endian header
+/// (version, count, longest, shortest, flags, delimiter) followed by the 32-bit offset
+/// of every entry and the offset of the end. Empty entries are skipped.
+pub fn strfile(text: &str) -> Vec<u8> {
+    let mut offsets = vec![0u32];
+    let mut lengths = Vec::new();
+    let (mut start, mut pos) = (0, 0);
+    for line in text.split_inclusive('\n') {
+        pos += line.len();
+        if line.trim_end_matches('\n') == "%" {
+            let length = pos - line.len() - start;
+            if length > 0 {
+                lengths.push(length as u32);
+                offsets.push(pos as u32);
+            } else {
+                *offsets.last_mut().unwrap() = pos as u32;
+            }
+            start = pos;
+        }
+    }
+    if start < text.len() {
+        lengths.push((text.len() - start) as u32);
+        offsets.push(text.len() as u32);
+    }
+
+    let header = [
+        STRFILE_VERSION,
+        lengths.len() as u32,
+        lengths.iter().copied().max().unwrap_or(0),
+        lengths.iter().copied().min().unwrap_or(0),
+        0,
+    ];
+    let mut bytes: Vec<u8> = header.iter().flat_map(|n| n.to_be_bytes()).collect();
+    bytes.extend([b'%', 0, 0, 0]);
+    bytes.extend(offsets.iter().flat_map(|n| n.to_be_bytes()));
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(bytes: &[u8]) -> Vec<u32> {
+        bytes.chunks(4).map(|c| u32::from_be_bytes(c.try_into().unwrap())).collect()
+    }
+
+    #[test]
+    fn test_cookie() {
+        let fortune = Fortune::builder().year(2026).seed("alice").draw();
+        let cookie = cookie(&fortune);
+        assert!(cookie.ends_with(&format!("\t\t-- hash-omikuji 2026, {} ({:#})\n", fortune.grade, fortune.grade)));
+        assert!(cookie.lines().all(|line| display_width(line) <= WRAP));
+    }
+
+    #[test]
+    fn test_wrap() {
+        assert_eq!(wrap("one two three", 7), "one two\nthree\n");
+        assert_eq!(wrap("supercalifragilistic x", 5), "supercalifragilistic\nx\n");
+    }
+
+    #[test]
+    fn test_strfile() {
+        let text = file(&["first\n".to_string(), "second one\n".to_string()]);
+        assert_eq!(text, "first\n%\nsecond one\n%\n");
+        let bytes = strfile(&text);
+        assert_eq!(&bytes[20..24], b"%\0\0\0");
+        let header = words(&bytes[..20]);
+        assert_eq!(header, [STRFILE_VERSION, 2, 11, 6, 0]);
+        assert_eq!(words(&bytes[24..]), [0, 8, 21]);
+        assert_eq!(&text[8..18], "second one");
+    }
+
+    #[test]
+    fn test_strfile_skips_empty_and_takes_unterminated_entries() {
+        let bytes = strfile("%\nonly\n%\ntail\n");
+        assert_eq!(words(&bytes[4..8]), [2]);
+        assert_eq!(words(&bytes[24..]), [2, 9, 14]);
+    }
+}
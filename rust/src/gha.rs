@@ -0,0 +1,82 @@
+//! `--output-format gha`: step outputs and a job summary for GitHub Actions.
+
+use crate::output::Fortune;
+use std::env;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+
+/// `name=value` lines for `$GITHUB_OUTPUT`; the art and the full JSON document
+/// (for `fromJSON(steps.<id>.outputs.json)`) use the multiline `name<<DELIMITER` form
+pub fn outputs(fortune: &Fortune) -> String {
+    let fields = [
+        ("year", fortune.year.to_string()),
+        ("grade", fortune.grade.to_string()),
+        ("grade_en", format!("{:#}", fortune.grade)),
+        ("overall_score", fortune.overall_score.to_string()),
+        ("lucky_number", fortune.lucky_number.to_string()),
+        ("lucky_hex", fortune.lucky_hex.clone()),
+        ("lucky_color", fortune.lucky_color.clone()),
+        ("lucky_emoji", fortune.lucky_emoji.clone()),
+        ("lucky_item", fortune.lucky_item.clone()),
+        ("lucky_action", fortune.lucky_action.clone()),
+        ("advice", fortune.advice.clone()),
+        ("fingerprint", fortune.fingerprint.clone()),
+    ];
+    let mut output: String = fields.iter().map(|(name, value)| format!("{}={}\n", name, value)).collect();
+    let delimiter = delimiter(fortune);
+    let json = serde_json::to_string(fortune).unwrap();
+    for (name, value) in [("art", fortune.art.as_str()), ("json", json.as_str())] {
+        output.push_str(&format!("{}<<{}\n{}\n{}\n", name, delimiter, value.trim_end_matches('\n'), delimiter));
+    }
+    output
+}
+
+/// Derived from the digest, so a crafted seed in the JSON cannot close the block early
+fn delimiter(fortune: &Fortune) -> String {
+    let digest = fortune.fingerprint_sha256.trim_start_matches("SHA256:");
+    format!("HASH_OMIKUJI_{}", &digest[..16])
+}
+
+fn append(path: &Path, text: &str) -> Result<(), String> {
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(text.as_bytes()))
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Append the outputs to `$GITHUB_OUTPUT` and the markdown summary to `$GITHUB_STEP_SUMMARY`
+pub fn write(fortune: &Fortune) -> Result<(), String> {
+    let output = env::var_os("GITHUB_OUTPUT");
+    let summary = env::var_os("GITHUB_STEP_SUMMARY");
+    if output.is_none() && summary.is_none() {
+        return Err("--output-format gha needs GITHUB_OUTPUT or GITHUB_STEP_SUMMARY (set by GitHub Actions)".to_string());
+    }
+    if let Some(path) = output {
+        append(Path::new(&path), &outputs(fortune))?;
+    }
+    if let Some(path) = summary {
+        append(Path::new(&path), &fortune.format_markdown())?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_outputs() {
+        let fortune = Fortune::builder().year(2026).seed("alice").draw();
+        let text = outputs(&fortune);
+        assert!(text.starts_with("year=2026\n"));
+        assert!(text.contains(&format!("\nlucky_number={}\n", fortune.lucky_number)));
+
+        let delimiter = delimiter(&fortune);
+        let json = text.split(&format!("json<<{}\n", delimiter)).nth(1).unwrap();
+        let json = json.strip_suffix(&format!("\n{}\n", delimiter)).unwrap();
+        assert_eq!(serde_json::from_str::<Fortune>(json).unwrap(), fortune);
+    }
+}
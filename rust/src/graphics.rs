@@ -0,0 +1,167 @@
+//! Inline images for terminals that speak the sixel or Kitty graphics protocol.
+
+use crate::card::Canvas;
+use std::collections::HashMap;
+use std::env;
+use std::io::IsTerminal;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum GraphicsMode {
+    /// Use inline images when the terminal advertises support
+    Auto,
+    Sixel,
+    Kitty,
+    #[default]
+    Off,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+    Sixel,
+    Kitty,
+}
+
+impl GraphicsMode {
+    pub fn protocol(self) -> Option<Protocol> {
+        match self {
+            GraphicsMode::Auto if std::io::stdout().is_terminal() => detect(
+                env::var("TERM").ok().as_deref(),
+                env::var("TERM_PROGRAM").ok().as_deref(),
+                env::var_os("KITTY_WINDOW_ID").is_some(),
+            ),
+            GraphicsMode::Auto | GraphicsMode::Off => None,
+            GraphicsMode::Sixel => Some(Protocol::Sixel),
+            GraphicsMode::Kitty => Some(Protocol::Kitty),
+        }
+    }
+}
+
+/// Capability detection from the environment (no terminal queries)
+fn detect(term: Option<&str>, term_program: Option<&str>, kitty_window: bool) -> Option<Protocol> {
+    let term = term.unwrap_or_default();
+    let program = term_program.unwrap_or_default();
+    if kitty_window || term == "xterm-kitty" || matches!(program, "WezTerm" | "ghostty") {
+        Some(Protocol::Kitty)
+    } else if term.contains("sixel") || matches!(term, "mlterm" | "foot" | "yaft-256color") || program == "iTerm.app" {
+        Some(Protocol::Sixel)
+    } else {
+        None
+    }
+}
+
+pub fn encode(canvas: &Canvas, protocol: Protocol) -> Result<String, String> {
+    match protocol {
+        Protocol::Sixel => Ok(sixel(canvas)),
+        Protocol::Kitty => canvas.encode_png().map(|png| kitty(&png)),
+    }
+}
+
+/// Kitty graphics protocol: PNG payload, base64 in chunks of 4096
+fn kitty(png: &[u8]) -> String {
+    let payload = crate::fingerprint::base64(png);
+    let chunks: Vec<&str> = payload
+        .as_bytes()
+        .chunks(4096)
+        .map(|c| std::str::from_utf8(c).unwrap())
+        .collect();
+    let mut output = String::new();
+    for (i, chunk) in chunks.iter().enumerate() {
+        let more = if i + 1 < chunks.len() { 1 } else { 0 };
+        let control = if i == 0 { format!("f=100,a=T,m={}", more) } else { format!("m={}", more) };
+        output.push_str(&format!("\x1b_G{};{}\x1b\\", control, chunk));
+    }
+    output.push('\n');
+    output
+}
+
+/// Sixel image with one color register per distinct color (cards use only a handful)
+fn sixel(canvas: &Canvas) -> String {
+    let mut registers: HashMap<[u8; 3], usize> = HashMap::new();
+    let mut order: Vec<[u8; 3]> = Vec::new();
+    let indices: Vec<usize> = canvas
+        .pixels
+        .chunks(3)
+        .map(|p| {
+            let color = [p[0], p[1], p[2]];
+            *registers.entry(color).or_insert_with(|| {
+                order.push(color);
+                order.len() - 1
+            })
+        })
+        .collect();
+
+    let mut output = format!("\x1bPq\"1;1;{};{}", canvas.width, canvas.height);
+    for (i, c) in order.iter().enumerate() {
+        let percent = |v: u8| v as u32 * 100 / 255;
+        output.push_str(&format!("#{};2;{};{};{}", i, percent(c[0]), percent(c[1]), percent(c[2])));
+    }
+    for band in (0..canvas.height).step_by(6) {
+        for register in 0..order.len() {
+            let sixels: Vec<u8> = (0..canvas.width)
+                .map(|x| {
+                    (0..6)
+                        .filter(|dy| band + dy < canvas.height && indices[(band + dy) * canvas.width + x] == register)
+                        .fold(0u8, |bits, dy| bits | (1 << dy))
+                })
+                .collect();
+            if sixels.iter().all(|&s| s == 0) {
+                continue;
+            }
+            output.push_str(&format!("#{}", register));
+            let mut x = 0;
+            while x < sixels.len() {
+                let run = sixels[x..].iter().take_while(|&&s| s == sixels[x]).count();
+                let c = (63 + sixels[x]) as char;
+                if run > 3 {
+                    output.push_str(&format!("!{}{}", run, c));
+                } else {
+                    output.extend(std::iter::repeat_n(c, run));
+                }
+                x += run;
+            }
+            output.push('$');
+        }
+        output.push('-');
+    }
+    output.push_str("\x1b\\\n");
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn canvas(width: usize, height: usize) -> Canvas {
+        Canvas { width, height, pixels: [255, 0, 0].repeat(width * height) }
+    }
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(detect(Some("xterm-kitty"), None, false), Some(Protocol::Kitty));
+        assert_eq!(detect(Some("xterm-256color"), Some("WezTerm"), false), Some(Protocol::Kitty));
+        assert_eq!(detect(Some("xterm-256color"), None, true), Some(Protocol::Kitty));
+        assert_eq!(detect(Some("foot"), None, false), Some(Protocol::Sixel));
+        assert_eq!(detect(Some("xterm-256color"), Some("Apple_Terminal"), false), None);
+        assert_eq!(detect(None, None, false), None);
+        assert_eq!(GraphicsMode::Off.protocol(), None);
+        assert_eq!(GraphicsMode::Sixel.protocol(), Some(Protocol::Sixel));
+    }
+
+    #[test]
+    fn test_sixel() {
+        let image = sixel(&canvas(8, 7));
+        assert!(image.starts_with("\x1bPq\"1;1;8;7#0;2;100;0;0"));
+        // full band of 6 rows, then the 7th row alone, both run-length encoded
+        assert!(image.contains("#0!8~$-#0!8@$-"));
+        assert!(image.ends_with("\x1b\\\n"));
+    }
+
+    #[test]
+    fn test_kitty_chunks() {
+        let image = kitty(&[0u8; 6000]);
+        assert!(image.starts_with("\x1b_Gf=100,a=T,m=1;"));
+        assert_eq!(image.matches("\x1b_G").count(), 2);
+        assert!(image.contains("\x1b_Gm=0;"));
+    }
+}
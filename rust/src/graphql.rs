@@ -0,0 +1,76 @@
+//! `/graphql` in serve mode: `fortune(seed, year)` resolves to the fortune document,
+//! whose GraphQL fields are the `--json` fields, so clients fetch only what they render.
+
+use crate::output::Fortune;
+use crate::serve::{check_year, AppState, Tenant};
+use async_graphql::{Context, EmptyMutation, EmptySubscription, Object, Schema};
+use axum::Json;
+use std::sync::OnceLock;
+use zeroize::Zeroizing;
+
+pub struct Query;
+
+#[Object]
+impl Query {
+    /// Fortune of `seed` in `year` (default: the current year, or the server's --force-year)
+    async fn fortune(&self, ctx: &Context<'_>, seed: String, year: Option<u32>) -> async_graphql::Result<Fortune> {
+        check_year(year)?;
+        let seed = Zeroizing::new(seed);
+        Ok(ctx.data_unchecked::<AppState>().run_blocking(move |state| state.draw(&seed, year)).await)
+    }
+}
+
+pub type OmikujiSchema = Schema<Query, EmptyMutation, EmptySubscription>;
+
+fn schema() -> &'static OmikujiSchema {
+    static SCHEMA: OnceLock<OmikujiSchema> = OnceLock::new();
+    SCHEMA.get_or_init(|| Schema::build(Query, EmptyMutation, EmptySubscription).limit_depth(8).finish())
+}
+
+/// The schema in SDL, for client code generators
+pub fn sdl() -> String {
+    schema().sdl()
+}
+
+/// GET /graphql: the SDL
+pub async fn describe() -> String {
+    sdl()
+}
+
+/// POST /graphql: execute a query with the draw settings of the request's tenant
+pub async fn execute(Tenant(state): Tenant, Json(request): Json<async_graphql::Request>) -> Json<async_graphql::Response> {
+    Json(schema().execute(request.data(state)).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_field_selection() {
+        let state = AppState::new(Fortune::builder().year(2026), Vec::new());
+        let request = async_graphql::Request::new(r#"{ fortune(seed: "alice", year: 2025) { grade lucky_number luck_scores { label score } } }"#);
+        let response = schema().execute(request.data(state)).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+        let expected = Fortune::builder().year(2025).seed("alice").draw();
+        let data = response.data.into_json().unwrap();
+        let fortune = data["fortune"].as_object().unwrap();
+        assert_eq!(fortune.len(), 3);
+        assert_eq!(fortune["lucky_number"], expected.lucky_number);
+        assert_eq!(fortune["luck_scores"][0]["label"], *expected.luck_scores[0].label);
+
+        let request = async_graphql::Request::new(r#"{ fortune(seed: "alice", year: 10000) { grade } }"#);
+        let response = schema().execute(request.data(AppState::new(Fortune::builder().year(2026), Vec::new()))).await;
+        assert!(response.errors[0].message.contains("out of range"), "{:?}", response.errors);
+    }
+
+    #[test]
+    fn test_sdl_covers_json_fields() {
+        let sdl = sdl();
+        let document = serde_json::to_value(Fortune::builder().year(2026).seed("alice").draw()).unwrap();
+        for field in document.as_object().unwrap().keys() {
+            assert!(sdl.contains(&format!("\t{}: ", field)), "{}", field);
+        }
+    }
+}
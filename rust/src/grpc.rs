@@ -0,0 +1,147 @@
+//! `serve --grpc`: the Omikuji service of proto/omikuji.proto (Draw, Verify and the
+//! streaming BatchDraw), drawing with the same settings as the HTTP API. With tenants,
+//! calls pick theirs with `x-api-key` metadata.
+
+use crate::output;
+use crate::serve::{check_year, AppState, API_KEY_HEADER, MAX_BATCH};
+use crate::sign;
+use std::pin::Pin;
+use tokio_stream::{Stream, StreamExt};
+use tonic::{Request, Response, Status};
+use zeroize::Zeroizing;
+
+pub mod proto {
+    tonic::include_proto!("hash_omikuji.v1");
+}
+
+use proto::omikuji_server::{Omikuji, OmikujiServer};
+
+impl From<&output::Fortune> for proto::Fortune {
+    fn from(fortune: &output::Fortune) -> Self {
+        Self {
+            year: fortune.year,
+            grade: fortune.grade.romaji().to_string(),
+            overall_score: fortune.overall_score as u32,
+            lucky_number: fortune.lucky_number as u32,
+            lucky_hex: fortune.lucky_hex.clone(),
+            lucky_color: fortune.lucky_color.clone(),
+            lucky_day: fortune.lucky_day.clone(),
+            lucky_time: fortune.lucky_time.clone(),
+            lucky_emoji: fortune.lucky_emoji.clone(),
+            lucky_item: fortune.lucky_item.clone(),
+            advice: fortune.advice.clone(),
+            fingerprint: fortune.fingerprint.clone(),
+            json: fortune.format_json(),
+        }
+    }
+}
+
+pub struct OmikujiService {
+    state: AppState,
+}
+
+impl OmikujiService {
+    pub fn new(state: AppState) -> Self {
+        Self { state }
+    }
+
+    fn tenant<T>(&self, request: &Request<T>) -> Result<AppState, Status> {
+        let api_key = request.metadata().get(API_KEY_HEADER).and_then(|v| v.to_str().ok());
+        self.state.tenant(None, api_key).map_err(Status::unauthenticated)
+    }
+}
+
+#[tonic::async_trait]
+impl Omikuji for OmikujiService {
+    async fn draw(&self, request: Request<proto::DrawRequest>) -> Result<Response<proto::Fortune>, Status> {
+        let state = self.tenant(&request)?;
+        let request = request.into_inner();
+        check_year(request.year).map_err(Status::invalid_argument)?;
+        let seed = Zeroizing::new(request.seed);
+        Ok(Response::new(state.run_blocking(move |state| (&state.draw(&seed, request.year)).into()).await))
+    }
+
+    async fn verify(&self, request: Request<proto::VerifyRequest>) -> Result<Response<proto::VerifyResponse>, Status> {
+        let document = serde_json::from_str(&request.into_inner().json)
+            .map_err(|e| Status::invalid_argument(format!("Invalid JSON: {}", e)))?;
+        let verification = sign::verify_document(&document, None).map_err(Status::failed_precondition)?;
+        Ok(Response::new(proto::VerifyResponse { checksum: verification.checksum, signer: verification.signer }))
+    }
+
+    type BatchDrawStream = Pin<Box<dyn Stream<Item = Result<proto::Fortune, Status>> + Send>>;
+
+    async fn batch_draw(&self, request: Request<proto::BatchDrawRequest>) -> Result<Response<Self::BatchDrawStream>, Status> {
+        let state = self.tenant(&request)?;
+        let request = request.into_inner();
+        if request.seeds.len() > MAX_BATCH {
+            return Err(Status::invalid_argument(format!("At most {} seeds per batch", MAX_BATCH)));
+        }
+        check_year(request.year).map_err(Status::invalid_argument)?;
+        let seeds: Vec<_> = request.seeds.into_iter().map(Zeroizing::new).collect();
+        let stream = tokio_stream::iter(seeds).then(move |seed| {
+            let state = state.clone();
+            async move { Ok(state.run_blocking(move |state| (&state.draw(&seed, request.year)).into()).await) }
+        });
+        Ok(Response::new(Box::pin(stream)))
+    }
+}
+
+/// Serve the gRPC API until SIGINT or SIGTERM, with the state's timeout and its
+/// concurrency limit applied per connection
+pub async fn run(bind: &str, state: AppState) -> Result<(), String> {
+    let address = bind.parse().map_err(|e| format!("Invalid address {}: {}", bind, e))?;
+    let limits = state.limits();
+    tonic::transport::Server::builder()
+        .timeout(limits.timeout)
+        .concurrency_limit_per_connection(limits.concurrency.max(1))
+        .add_service(OmikujiServer::new(OmikujiService::new(state)))
+        .serve_with_shutdown(address, crate::serve::shutdown_signal())
+        .await
+        .map_err(|e| format!("Server error: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::Layout;
+
+    fn service() -> OmikujiService {
+        OmikujiService::new(AppState::new(output::Fortune::builder().year(2026), Vec::new()))
+    }
+
+    #[tokio::test]
+    async fn test_draw() {
+        let request = proto::DrawRequest { seed: "alice".to_string(), year: Some(2025) };
+        let fortune = service().draw(Request::new(request)).await.unwrap().into_inner();
+        let expected = output::Fortune::builder().year(2025).seed("alice").draw();
+        assert_eq!(fortune, (&expected).into());
+        assert_eq!(fortune.grade, expected.grade.romaji());
+
+        let request = proto::DrawRequest { seed: "alice".to_string(), year: Some(10000) };
+        assert_eq!(service().draw(Request::new(request)).await.unwrap_err().code(), tonic::Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn test_verify() {
+        let fortune = output::Fortune::builder().year(2026).layout(Layout::V2).seed("alice").draw();
+        let request = proto::VerifyRequest { json: fortune.format_json() };
+        let response = service().verify(Request::new(request)).await.unwrap().into_inner();
+        assert!(response.checksum);
+        assert_eq!(response.signer, None);
+
+        let status = service().verify(Request::new(proto::VerifyRequest { json: "{".to_string() })).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn test_batch_draw() {
+        let request = proto::BatchDrawRequest { seeds: vec!["alice".to_string(), "bob".to_string()], year: None };
+        let stream = service().batch_draw(Request::new(request)).await.unwrap().into_inner();
+        let fortunes: Vec<_> = stream.map(Result::unwrap).collect().await;
+        assert_eq!(fortunes.len(), 2);
+        assert_eq!(fortunes[1], (&output::Fortune::builder().year(2026).seed("bob").draw()).into());
+
+        let request = proto::BatchDrawRequest { seeds: vec![String::new(); MAX_BATCH + 1], year: None };
+        assert_eq!(service().batch_draw(Request::new(request)).await.err().unwrap().code(), tonic::Code::InvalidArgument);
+    }
+}
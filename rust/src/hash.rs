@@ -1,24 +1,203 @@
+use crate::art::{self, ArtOptions, Walk};
+use crate::fingerprint::{self, FingerprintFormat};
+use serde::{Deserialize, Serialize};
 use sha2::{Sha256, Digest};
+use zeroize::Zeroizing;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloc::format;
 
 const SALT: &str = "sha-omikuji-2026";
 
+/// Interpretation of the 256 digest bits
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "serve", derive(utoipa::ToSchema))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
+#[cfg_attr(feature = "std", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[serde(rename_all = "lowercase")]
+pub enum Layout {
+    /// Original layout: bit[193..204] shown verbatim as entropy
+    #[default]
+    V1,
+    /// bit[193..204] retired; entropy_check is a checksum over the decoded fields
+    V2,
+}
+
+/// Hash function turning the seed into the 256 digest bits
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "serve", derive(utoipa::ToSchema))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
+#[cfg_attr(feature = "std", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[serde(rename_all = "lowercase")]
+pub enum Algo {
+    #[default]
+    Sha256,
+    Blake3,
+}
+
 pub struct HashBits {
     bytes: [u8; 32],
 }
 
+/// Canonical seed for a group draw: a single seed is used verbatim, several are
+/// sorted and length-prefixed so the result is order-independent and unambiguous
+pub fn combine_seeds(seeds: &[&str]) -> String {
+    if let [single] = seeds {
+        return single.to_string();
+    }
+    let mut sorted = seeds.to_vec();
+    sorted.sort_unstable();
+    let parts: Zeroizing<Vec<String>> =
+        Zeroizing::new(sorted.iter().map(|s| format!("{}:{}", s.len(), s)).collect());
+    format!("group[{}]", parts.join(","))
+}
+
+/// Fast path for hashing many single seeds of one year (simulate, batch): the seed
+/// material is written into one reused buffer instead of allocated per seed.
+/// `hash` gives the same digest as [`HashBits::from_seeds_salted`] with one seed.
+pub struct SeedHasher {
+    buffer: Zeroizing<Vec<u8>>,
+    prefix: usize,
+    suffix: Zeroizing<String>,
+    year: u32,
+    algo: Algo,
+}
+
+impl SeedHasher {
+    pub fn new(year: u32, algo: Algo) -> Self {
+        Self::salted(year, algo, None)
+    }
+
+    /// A hasher for the universe of `salt`, like [`HashBits::from_seeds_salted`]
+    pub fn salted(year: u32, algo: Algo, salt: Option<&str>) -> Self {
+        let buffer = Zeroizing::new(format!("{}-", year).into_bytes());
+        let suffix = Zeroizing::new(match salt {
+            Some(salt) => format!("-{}-{}", SALT, salt),
+            None => format!("-{}", SALT),
+        });
+        Self { prefix: buffer.len(), buffer, suffix, year, algo }
+    }
+
+    pub fn year(&self) -> u32 {
+        self.year
+    }
+
+    pub fn hash(&mut self, seed: &str) -> HashBits {
+        self.buffer.truncate(self.prefix);
+        self.buffer.extend_from_slice(seed.as_bytes());
+        self.buffer.extend_from_slice(self.suffix.as_bytes());
+        let mut bytes = [0u8; 32];
+        match self.algo {
+            Algo::Sha256 => bytes.copy_from_slice(&Sha256::digest(&self.buffer[..])),
+            Algo::Blake3 => bytes = *blake3::hash(&self.buffer).as_bytes(),
+        }
+        HashBits { bytes }
+    }
+}
+
 impl HashBits {
     pub fn from_seed(year: u32, user: &str) -> Self {
-        let seed = format!("{}-{}-{}", year, user, SALT);
-        let mut hasher = Sha256::new();
-        hasher.update(seed.as_bytes());
-        let result = hasher.finalize();
+        Self::from_seeds(year, &[user])
+    }
+
+    /// Derive from several seeds at once; order of `seeds` does not matter
+    pub fn from_seeds(year: u32, seeds: &[&str]) -> Self {
+        Self::from_seeds_with(year, seeds, Algo::Sha256)
+    }
+
+    /// Derive with the given hash function; domain-separated fields still use SHA-256
+    pub fn from_seeds_with(year: u32, seeds: &[&str], algo: Algo) -> Self {
+        Self::from_seeds_salted(year, seeds, algo, None)
+    }
+
+    /// Derive with an extra secret `salt` (a tenant of `serve`); without one this is
+    /// [`from_seeds_with`](Self::from_seeds_with), with one nobody can predict the draw
+    pub fn from_seeds_salted(year: u32, seeds: &[&str], algo: Algo, salt: Option<&str>) -> Self {
+        // Seed material is wiped from memory as soon as it has been hashed
+        let combined = Zeroizing::new(combine_seeds(seeds));
+        let seed = Zeroizing::new(match salt {
+            Some(salt) => format!("{}-{}-{}-{}", year, combined.as_str(), SALT, salt),
+            None => format!("{}-{}-{}", year, combined.as_str(), SALT),
+        });
         let mut bytes = [0u8; 32];
-        bytes.copy_from_slice(&result);
+        match algo {
+            Algo::Sha256 => bytes.copy_from_slice(&Sha256::digest(seed.as_bytes())),
+            Algo::Blake3 => bytes = *blake3::hash(seed.as_bytes()).as_bytes(),
+        }
         Self { bytes }
     }
 
+    /// The digest behind a recorded hex fingerprint ([`hex_string`](Self::hex_string))
+    pub fn from_hex(text: &str) -> Option<Self> {
+        Some(Self { bytes: fingerprint::decode_hex(text)?.try_into().ok()? })
+    }
+
     pub fn hex_string(&self) -> String {
-        self.bytes.iter().map(|b| format!("{:02x}", b)).collect()
+        fingerprint::hex(&self.bytes)
+    }
+
+    /// Digest rendered in the requested encoding (hex, base32, base58, base64url, short)
+    pub fn fingerprint(&self, format: FingerprintFormat) -> String {
+        fingerprint::encode(&self.bytes, format)
+    }
+
+    /// Emoji safety-number fingerprint (8 emoji from the first 48 bits)
+    pub fn emoji_fingerprint(&self) -> String {
+        fingerprint::emoji(&self.bytes)
+    }
+
+    /// PGP word list reading of the short (8-byte) fingerprint, for reading aloud
+    pub fn pgp_words(&self) -> String {
+        fingerprint::pgp_words(&self.bytes[..8])
+    }
+
+    /// OpenSSH-style `SHA256:BASE64` line (like `ssh-keygen -l`)
+    pub fn openssh_fingerprint(&self) -> String {
+        fingerprint::openssh(&self.bytes)
+    }
+
+    /// Art of the digest in the requested mode (mazes exit towards the lucky direction)
+    pub fn art(&self, options: &ArtOptions) -> String {
+        art::render(&self.bytes, options, self.get_bits(211, 3) as u8)
+    }
+
+    /// Drunken-bishop walk over the digest, for the randomart
+    pub fn art_walk(&self, width: usize, height: usize) -> Walk {
+        art::walk(&self.bytes, width, height)
+    }
+
+    /// Mini-art strip of the Lucky Number bits [0..7]
+    pub fn lucky_number_strip(&self) -> Walk {
+        art::strip(self.get_bits(0, 8), 8)
+    }
+
+    /// Mini-art strip of the Lucky Day bits [32..40]
+    pub fn lucky_day_strip(&self) -> Walk {
+        art::strip(self.get_bits(32, 9), 9)
+    }
+
+    /// Mini-art strip of the Grade bits [242..253]
+    pub fn grade_strip(&self) -> Walk {
+        art::strip(self.grade_bits() as u64, 12)
+    }
+
+    /// Digest of one day's fortune within the year (`ordinal` = day of the year, 1-366)
+    pub fn daily(&self, ordinal: u32) -> HashBits {
+        self.domain(&format!("daily-{}", ordinal))
+    }
+
+    /// Fresh 256 bits for a named feature: SHA-256(tag || 0x00 || digest).
+    /// Keeps new fields independent of the main layout and of each other.
+    fn domain(&self, tag: &str) -> HashBits {
+        let mut hasher = Sha256::new();
+        hasher.update(tag.as_bytes());
+        hasher.update([0u8]);
+        hasher.update(self.bytes);
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(&hasher.finalize());
+        HashBits { bytes }
     }
 
     fn get_bits(&self, start_bit: usize, num_bits: usize) -> u64 {
@@ -91,17 +270,52 @@ impl HashBits {
     /// bit[65..192]: Luck Scores (128bit = 8bit x 16)
     pub fn luck_scores(&self) -> [u8; 16] {
         let mut scores = [0u8; 16];
-        for i in 0..16 {
-            scores[i] = self.get_bits(65 + i * 8, 8) as u8;
+        for (i, score) in scores.iter_mut().enumerate() {
+            *score = self.get_bits(65 + i * 8, 8) as u8;
         }
         scores
     }
 
-    /// bit[193..204]: Entropy/Checksum (12bit)
+    /// bit[193..204]: Entropy (12bit), layout v1 only
     pub fn entropy_check(&self) -> u16 {
         self.get_bits(193, 12) as u16
     }
 
+    /// bit[242..253]: Grade (12bit), mapped onto 大吉..大凶 by weights
+    pub fn grade_bits(&self) -> u16 {
+        self.get_bits(242, 12) as u16
+    }
+
+    /// domain "topics", bit[2i..2i+1]: phrase selector (2bit) for topic i
+    pub fn topic_selector(&self, index: usize) -> u8 {
+        self.domain("topics").get_bits(index * 2, 2) as u8
+    }
+
+    /// domain "advice": 256 bits consumed by the advice grammar
+    pub fn advice_entropy(&self) -> [u8; 32] {
+        self.domain("advice").bytes
+    }
+
+    /// domain "poem", bit[0..3]: waka index (4bit) -> 16 poems
+    pub fn poem_index(&self) -> u8 {
+        self.domain("poem").get_bits(0, 4) as u8
+    }
+
+    /// domain "corpus/<table>", bit[0..63]: entry of a table from a user corpus
+    pub fn corpus_index(&self, table: &str, len: usize) -> usize {
+        (self.domain(&format!("corpus/{}", table)).get_bits(0, 64) % len.max(1) as u64) as usize
+    }
+
+    /// domain "items", bit[0..7]: lucky item index (8bit) -> 256 objects
+    pub fn lucky_item(&self) -> u8 {
+        self.domain("items").get_bits(0, 8) as u8
+    }
+
+    /// domain "items", bit[8..13]: lucky action index (6bit) -> 64 actions
+    pub fn lucky_action(&self) -> u8 {
+        self.domain("items").get_bits(8, 6) as u8
+    }
+
     /// bit[205..210]: Lucky Emoji (6bit) -> 64 smileys from U+1F600-1F63F
     pub fn lucky_emoji(&self) -> char {
         // Unicode Emoticons: U+1F600 (😀) to U+1F63F (64 smileys)
@@ -151,6 +365,33 @@ impl HashBits {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_daily() {
+        let hash = HashBits::from_seed(2026, "alice");
+        assert_eq!(hash.daily(1).hex_string(), hash.daily(1).hex_string());
+        assert_ne!(hash.daily(1).hex_string(), hash.daily(2).hex_string());
+        assert_ne!(hash.daily(1).hex_string(), hash.hex_string());
+    }
+
+    #[test]
+    fn test_algo() {
+        let sha = HashBits::from_seeds_with(2026, &["alice"], Algo::Sha256);
+        let blake = HashBits::from_seeds_with(2026, &["alice"], Algo::Blake3);
+        assert_eq!(sha.hex_string(), HashBits::from_seed(2026, "alice").hex_string());
+        assert_eq!(blake.hex_string(), blake3::hash(b"2026-alice-sha-omikuji-2026").to_hex().as_str());
+        assert_ne!(sha.hex_string(), blake.hex_string());
+    }
+
+    #[test]
+    fn test_seed_hasher_matches_from_seeds() {
+        for algo in [Algo::Sha256, Algo::Blake3] {
+            let mut hasher = SeedHasher::new(2026, algo);
+            for seed in ["alice", "", "a much longer seed that spills over into a second SHA-256 block"] {
+                assert_eq!(hasher.hash(seed).hex_string(), HashBits::from_seeds_with(2026, &[seed], algo).hex_string());
+            }
+        }
+    }
+
     #[test]
     fn test_hash_deterministic() {
         let hash1 = HashBits::from_seed(2026, "alice");
@@ -172,11 +413,31 @@ mod tests {
         assert_ne!(hash1.hex_string(), hash2.hex_string());
     }
 
+    #[test]
+    fn test_from_seeds_order_independent() {
+        let ab = HashBits::from_seeds(2026, &["alice", "bob"]);
+        let ba = HashBits::from_seeds(2026, &["bob", "alice"]);
+        assert_eq!(ab.hex_string(), ba.hex_string());
+        assert_ne!(ab.hex_string(), HashBits::from_seed(2026, "alice").hex_string());
+    }
+
+    #[test]
+    fn test_from_seeds_single_matches_from_seed() {
+        let single = HashBits::from_seeds(2026, &["alice"]);
+        assert_eq!(single.hex_string(), HashBits::from_seed(2026, "alice").hex_string());
+    }
+
+    #[test]
+    fn test_combine_seeds_unambiguous() {
+        assert_ne!(combine_seeds(&["a,b", "c"]), combine_seeds(&["a", "b,c"]));
+        assert_ne!(combine_seeds(&["alice", "alice"]), combine_seeds(&["alice"]));
+    }
+
     #[test]
     fn test_lucky_day_range() {
         let hash = HashBits::from_seed(2026, "test");
         let day = hash.lucky_day();
-        assert!(day >= 1 && day <= 365);
+        assert!((1..=365).contains(&day));
     }
 
     #[test]
@@ -185,7 +446,7 @@ mod tests {
             let seed = format!("test-{}", i);
             let hash = HashBits::from_seed(2026, &seed);
             let day = hash.lucky_day();
-            assert!(day >= 1 && day <= 365, "Day out of range: {}", day);
+            assert!((1..=365).contains(&day), "Day out of range: {}", day);
         }
     }
 
@@ -224,6 +485,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::absurd_extreme_comparisons, unused_comparisons)]
     fn test_lucky_number_range() {
         let hash = HashBits::from_seed(2026, "test");
         let num = hash.lucky_number();
@@ -253,6 +515,30 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_fingerprint_hex_matches_hex_string() {
+        let hash = HashBits::from_seed(2026, "test");
+        assert_eq!(hash.fingerprint(FingerprintFormat::Hex), hash.hex_string());
+        assert!(hash.hex_string().starts_with(&hash.fingerprint(FingerprintFormat::Short)));
+    }
+
+    #[test]
+    fn test_openssh_fingerprint_format() {
+        let hash = HashBits::from_seed(2026, "test");
+        let line = hash.openssh_fingerprint();
+        assert!(line.starts_with("SHA256:"));
+        assert_eq!(line.len(), 7 + 43);
+        assert!(!line.ends_with('='));
+    }
+
+    #[test]
+    fn test_pgp_words_short_form() {
+        let hash = HashBits::from_seed(2026, "test");
+        let words = hash.pgp_words();
+        assert_eq!(words.split(' ').count(), 8);
+        assert!(hash.fingerprint(FingerprintFormat::Pgp).starts_with(&words));
+    }
+
     #[test]
     fn test_entropy_check_range() {
         let hash = HashBits::from_seed(2026, "test");
@@ -260,6 +546,23 @@ mod tests {
         assert!(entropy <= 0xFFF); // 12 bits max
     }
 
+    #[test]
+    fn test_grade_bits_range() {
+        for i in 0..100 {
+            let seed = format!("test-{}", i);
+            let hash = HashBits::from_seed(2026, &seed);
+            assert!(hash.grade_bits() <= 0xFFF);
+        }
+    }
+
+    #[test]
+    fn test_domain_separation() {
+        let hash = HashBits::from_seed(2026, "test");
+        assert_ne!(hash.domain("topics").hex_string(), hash.hex_string());
+        assert_ne!(hash.domain("topics").hex_string(), hash.domain("items").hex_string());
+        assert_eq!(hash.domain("topics").hex_string(), hash.domain("topics").hex_string());
+    }
+
     #[test]
     fn test_lucky_power_of_2_range() {
         for i in 0..100 {
@@ -280,7 +583,7 @@ mod tests {
             let seed = format!("test-{}", i);
             let hash = HashBits::from_seed(2026, &seed);
             let ch = hash.lucky_ascii();
-            assert!(ch >= ' ' && ch <= '~', "ASCII not printable: {:?}", ch);
+            assert!((' '..='~').contains(&ch), "ASCII not printable: {:?}", ch);
         }
     }
 
@@ -304,7 +607,7 @@ mod tests {
             let emoji = hash.lucky_emoji();
             let codepoint = emoji as u32;
             assert!(
-                codepoint >= 0x1F600 && codepoint <= 0x1F63F,
+                (0x1F600..=0x1F63F).contains(&codepoint),
                 "Emoji codepoint out of range: U+{:X}", codepoint
             );
         }
@@ -351,7 +654,7 @@ mod tests {
             let seed = format!("test-{}", i);
             let hash = HashBits::from_seed(2026, &seed);
             let lat = hash.lucky_latitude();
-            assert!(lat >= -90 && lat <= 90, "Latitude out of range: {}", lat);
+            assert!((-90..=90).contains(&lat), "Latitude out of range: {}", lat);
         }
     }
 
@@ -361,7 +664,7 @@ mod tests {
             let seed = format!("test-{}", i);
             let hash = HashBits::from_seed(2026, &seed);
             let lon = hash.lucky_longitude();
-            assert!(lon >= -180 && lon <= 180, "Longitude out of range: {}", lon);
+            assert!((-180..=180).contains(&lon), "Longitude out of range: {}", lon);
         }
     }
 
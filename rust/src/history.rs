@@ -0,0 +1,128 @@
+use crate::error::Error;
+use crate::luck::Grade;
+use crate::output::Fortune;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// One recorded draw, stored as a JSON line in the history file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub year: u32,
+    pub drawn_on: NaiveDate,
+    /// Drawn with --force-year instead of on January 1st
+    pub forced: bool,
+    pub grade: Grade,
+    pub overall_score: u8,
+    pub lucky_day_number: u16,
+    pub fingerprint: String,
+}
+
+impl HistoryEntry {
+    pub fn from_result(result: &Fortune, drawn_on: NaiveDate, forced: bool) -> Self {
+        Self {
+            year: result.year,
+            drawn_on,
+            forced,
+            grade: result.grade,
+            overall_score: result.overall_score,
+            lucky_day_number: result.lucky_day_number,
+            fingerprint: result.fingerprint.clone(),
+        }
+    }
+}
+
+/// Default store: `~/.local/share/hash-omikuji/history.jsonl` (platform data dir)
+pub fn default_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("hash-omikuji").join("history.jsonl"))
+}
+
+/// Read all entries; a missing file is an empty history
+pub fn load(path: &Path) -> Result<Vec<HistoryEntry>, Error> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(Error::Io(format!("Failed to read {}: {}", path.display(), e))),
+    };
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            serde_json::from_str(line).map_err(|e| Error::Format(format!("Invalid history entry {}:{}: {}", path.display(), i + 1, e)))
+        })
+        .collect()
+}
+
+pub fn append(path: &Path, entry: &HistoryEntry) -> Result<(), Error> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| Error::Io(format!("Failed to create {}: {}", dir.display(), e)))?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| Error::Io(format!("Failed to open {}: {}", path.display(), e)))?;
+    let line = serde_json::to_string(entry).unwrap();
+    writeln!(file, "{}", line).map_err(|e| Error::Io(format!("Failed to write {}: {}", path.display(), e)))
+}
+
+/// Latest entry per year, oldest year first
+pub fn by_year(entries: &[HistoryEntry]) -> BTreeMap<u32, &HistoryEntry> {
+    entries.iter().map(|entry| (entry.year, entry)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(year: u32, grade: Grade) -> HistoryEntry {
+        HistoryEntry {
+            year,
+            drawn_on: NaiveDate::from_ymd_opt(year as i32, 1, 1).unwrap(),
+            forced: false,
+            grade,
+            overall_score: 50,
+            lucky_day_number: 100,
+            fingerprint: "00".repeat(32),
+        }
+    }
+
+    #[test]
+    fn test_append_and_load() {
+        let path = std::env::temp_dir()
+            .join(format!("hash-omikuji-history-{}", std::process::id()))
+            .join("history.jsonl");
+        assert_eq!(load(&path).unwrap(), Vec::new());
+
+        append(&path, &entry(2025, Grade::Kyo)).unwrap();
+        append(&path, &entry(2026, Grade::Daikichi)).unwrap();
+        let entries = load(&path).unwrap();
+        assert_eq!(entries, vec![entry(2025, Grade::Kyo), entry(2026, Grade::Daikichi)]);
+
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_load_errors() {
+        let dir = std::env::temp_dir().join(format!("hash-omikuji-history-errors-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        assert!(matches!(load(&dir), Err(Error::Io(_))));
+
+        let path = dir.join("history.jsonl");
+        fs::write(&path, "not json\n").unwrap();
+        assert!(matches!(load(&path), Err(Error::Format(message)) if message.contains(":1:")));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_by_year_keeps_latest() {
+        let entries = vec![entry(2026, Grade::Kyo), entry(2025, Grade::Kichi), entry(2026, Grade::Daikichi)];
+        let years = by_year(&entries);
+        assert_eq!(years.keys().copied().collect::<Vec<_>>(), vec![2025, 2026]);
+        assert_eq!(years[&2026].grade, Grade::Daikichi);
+    }
+}
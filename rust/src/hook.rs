@@ -0,0 +1,132 @@
+//! Git prepare-commit-msg hook that adds a one-line fortune to commits made on January 1st.
+
+use crate::error::Error;
+use crate::output::Fortune;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+pub const HOOK_NAME: &str = "prepare-commit-msg";
+
+/// Marks hooks written by `hook install`, so `hook uninstall` never removes anyone else's
+const MARKER: &str = "# Installed by `hash-omikuji hook install`";
+
+/// The hook never blocks a commit: a missing binary or any failure is ignored
+const SCRIPT: &str = "#!/bin/sh
+# Installed by `hash-omikuji hook install`; remove with `hash-omikuji hook uninstall`
+command -v hash-omikuji >/dev/null 2>&1 || exit 0
+hash-omikuji hook run \"$1\" \"$2\" || true
+";
+
+/// Hooks directory of the repository containing `dir` (honors core.hooksPath)
+pub fn hooks_dir(dir: &Path) -> Result<PathBuf, Error> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--git-path", "hooks"])
+        .current_dir(dir)
+        .output()
+        .map_err(|e| Error::Io(format!("Failed to run git: {}", e)))?;
+    if !output.status.success() {
+        return Err(Error::Other(format!("Not a git repository: {}", dir.display())));
+    }
+    Ok(dir.join(String::from_utf8_lossy(&output.stdout).trim()))
+}
+
+/// Write the hook; refuses to replace a prepare-commit-msg hook it did not install
+pub fn install(hooks_dir: &Path) -> Result<PathBuf, Error> {
+    let path = hooks_dir.join(HOOK_NAME);
+    if let Ok(existing) = fs::read_to_string(&path) {
+        if !existing.contains(MARKER) {
+            return Err(Error::Other(format!("{} already exists and was not installed by hash-omikuji", path.display())));
+        }
+    }
+    fs::create_dir_all(hooks_dir).map_err(|e| Error::Io(format!("Failed to create {}: {}", hooks_dir.display(), e)))?;
+    fs::write(&path, SCRIPT).map_err(|e| Error::Io(format!("Failed to write {}: {}", path.display(), e)))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755))
+            .map_err(|e| Error::Io(format!("Failed to make {} executable: {}", path.display(), e)))?;
+    }
+    Ok(path)
+}
+
+/// Remove the hook if it is ours
+pub fn uninstall(hooks_dir: &Path) -> Result<PathBuf, Error> {
+    let path = hooks_dir.join(HOOK_NAME);
+    let existing = fs::read_to_string(&path).map_err(|_| Error::Other(format!("No {} hook installed", HOOK_NAME)))?;
+    if !existing.contains(MARKER) {
+        return Err(Error::Other(format!("{} was not installed by hash-omikuji; leaving it alone", path.display())));
+    }
+    fs::remove_file(&path).map_err(|e| Error::Io(format!("Failed to remove {}: {}", path.display(), e)))?;
+    Ok(path)
+}
+
+/// Merges, squashes and amends keep their message untouched
+pub fn applies_to(source: Option<&str>) -> bool {
+    !matches!(source, Some("merge" | "squash" | "commit"))
+}
+
+/// `🎍 2026 大吉 🎉 lucky number 165`
+pub fn fortune_line(fortune: &Fortune) -> String {
+    format!("🎍 {} {} {} lucky number {}", fortune.year, fortune.grade, fortune.lucky_emoji, fortune.lucky_number)
+}
+
+/// Insert the line above git's comment block (or at the end), once
+pub fn add_line(message: &str, line: &str) -> String {
+    if message.contains(line) {
+        return message.to_string();
+    }
+    let split = if message.starts_with('#') { 0 } else { message.find("\n#").map_or(message.len(), |i| i + 1) };
+    let (body, comments) = message.split_at(split);
+    let body = body.trim_end_matches('\n');
+    let separator = if body.is_empty() { "" } else { "\n\n" };
+    let tail = if comments.is_empty() { String::new() } else { format!("\n{}", comments) };
+    format!("{}{}{}\n{}", body, separator, line, tail)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_line() {
+        assert_eq!(add_line("Fix bug\n", "🎍"), "Fix bug\n\n🎍\n");
+        assert_eq!(add_line("Fix bug\n\n🎍\n", "🎍"), "Fix bug\n\n🎍\n");
+        assert_eq!(
+            add_line("Fix bug\n\n# Please enter the commit message\n", "🎍"),
+            "Fix bug\n\n🎍\n\n# Please enter the commit message\n"
+        );
+        assert_eq!(add_line("\n# comments\n", "🎍"), "🎍\n\n# comments\n");
+    }
+
+    #[test]
+    fn test_fortune_line() {
+        let fortune = Fortune::builder().year(2026).seed("alice").draw();
+        let line = fortune_line(&fortune);
+        assert!(line.starts_with("🎍 2026 "));
+        assert!(line.ends_with(&format!("lucky number {}", fortune.lucky_number)));
+    }
+
+    #[test]
+    fn test_applies_to() {
+        assert!(applies_to(None) && applies_to(Some("message")) && applies_to(Some("template")));
+        assert!(!applies_to(Some("merge")) && !applies_to(Some("squash")) && !applies_to(Some("commit")));
+    }
+
+    #[test]
+    fn test_install_and_uninstall() {
+        let dir = std::env::temp_dir().join(format!("hash-omikuji-hook-{}", std::process::id()));
+        let path = install(&dir).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), SCRIPT);
+        install(&dir).unwrap();
+        assert_eq!(uninstall(&dir).unwrap(), path);
+        assert!(!path.exists());
+        assert!(uninstall(&dir).is_err());
+
+        fs::write(&path, "#!/bin/sh\necho custom\n").unwrap();
+        assert!(install(&dir).is_err());
+        assert!(uninstall(&dir).is_err());
+        assert!(path.exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,129 @@
+//! `ics`: an iCalendar file with the lucky day at the lucky time, for calendar apps.
+
+use crate::date;
+use crate::output::Fortune;
+use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use sha2::{Digest, Sha256};
+
+/// Reminder before each event
+const ALARM: &str = "-PT10M";
+
+/// The lucky day at the lucky time (local, floating time); None for a year chrono
+/// cannot represent
+pub fn lucky_moment(fortune: &Fortune) -> Option<NaiveDateTime> {
+    let day = date::day_of_year(fortune.year, fortune.lucky_day_number)?;
+    let time = NaiveTime::parse_from_str(&fortune.lucky_time, "%H:%M").unwrap_or(NaiveTime::MIN);
+    Some(day.and_time(time))
+}
+
+/// TEXT value escaping (RFC 5545 3.3.11)
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace(';', "\\;").replace(',', "\\,").replace('\n', "\\n")
+}
+
+/// Fold a content line at 75 octets, never inside a UTF-8 sequence
+fn fold(line: &str) -> String {
+    let mut output = String::new();
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            output.push_str("\r\n ");
+            width = 1;
+        }
+        width += c.len_utf8();
+        output.push(c);
+    }
+    output.push_str("\r\n");
+    output
+}
+
+fn event(uid: &str, stamp: &str, start: NaiveDateTime, summary: &str, description: &str, rule: Option<&str>) -> Vec<String> {
+    let mut lines = vec![
+        "BEGIN:VEVENT".to_string(),
+        format!("UID:{}", uid),
+        format!("DTSTAMP:{}", stamp),
+        format!("DTSTART:{}", start.format("%Y%m%dT%H%M%S")),
+        "DURATION:PT1M".to_string(),
+    ];
+    lines.extend(rule.map(|rule| format!("RRULE:{}", rule)));
+    lines.extend([
+        format!("SUMMARY:{}", escape(summary)),
+        format!("DESCRIPTION:{}", escape(description)),
+        "BEGIN:VALARM".to_string(),
+        format!("TRIGGER:{}", ALARM),
+        "ACTION:DISPLAY".to_string(),
+        format!("DESCRIPTION:{}", escape(summary)),
+        "END:VALARM".to_string(),
+        "END:VEVENT".to_string(),
+    ]);
+    lines
+}
+
+/// VCALENDAR with the lucky-day event and, with `daily`, the lucky minute every day of the year.
+/// UIDs derive from the fingerprint, so re-importing updates the events instead of duplicating them.
+pub fn calendar(fortune: &Fortune, daily: bool, now: DateTime<Utc>) -> Result<String, String> {
+    let id = crate::fingerprint::hex(&Sha256::digest(fortune.fingerprint.as_bytes())[..8]);
+    let stamp = now.format("%Y%m%dT%H%M%SZ").to_string();
+    let moment = lucky_moment(fortune).ok_or_else(|| format!("The year {} is out of the calendar's range", fortune.year))?;
+    let description = format!("{} ({:#}) · Lucky Item: {} · {}", fortune.grade, fortune.grade, fortune.lucky_item, fortune.advice);
+
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//elzup//hash-omikuji//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+    ];
+    let summary = format!("🍀 Lucky Day {} {}", fortune.year, fortune.lucky_emoji);
+    lines.extend(event(&format!("{}-day@hash-omikuji", id), &stamp, moment, &summary, &description, None));
+    if daily {
+        let start = NaiveDate::from_yo_opt(moment.year(), 1).unwrap().and_time(moment.time());
+        let rule = format!("FREQ=DAILY;UNTIL={}1231T235959", fortune.year);
+        let summary = format!("🍀 Lucky minute {}", fortune.lucky_time);
+        lines.extend(event(&format!("{}-daily@hash-omikuji", id), &stamp, start, &summary, &description, Some(&rule)));
+    }
+    lines.push("END:VCALENDAR".to_string());
+    Ok(lines.iter().map(|line| fold(line)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_lucky_moment() {
+        let fortune = Fortune::builder().year(2026).seed("alice").draw();
+        let moment = lucky_moment(&fortune).unwrap();
+        assert!(fortune.lucky_day.starts_with(&moment.format("%Y-%m-%d").to_string()));
+        assert_eq!(moment.format("%H:%M").to_string(), fortune.lucky_time);
+    }
+
+    #[test]
+    fn test_calendar() {
+        let fortune = Fortune::builder().year(2026).seed("alice").draw();
+        let ics = calendar(&fortune, false, now()).unwrap();
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert!(ics.contains(&format!("DTSTART:{}\r\n", lucky_moment(&fortune).unwrap().format("%Y%m%dT%H%M%S"))));
+        assert!(ics.contains("DTSTAMP:20260101T000000Z\r\n"));
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 1);
+        assert_eq!(ics.matches("BEGIN:VALARM").count(), 1);
+        assert!(ics.split("\r\n").all(|line| line.len() <= 75));
+
+        let ics = calendar(&fortune, true, now()).unwrap();
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 2);
+        assert!(ics.contains("RRULE:FREQ=DAILY;UNTIL=20261231T235959\r\n"));
+    }
+
+    #[test]
+    fn test_escape_and_fold() {
+        assert_eq!(escape("a, b; c\\d\ne"), "a\\, b\\; c\\\\d\\ne");
+        let folded = fold(&"吉".repeat(40));
+        assert!(folded.split("\r\n").all(|line| line.len() <= 75));
+        assert_eq!(folded.replace("\r\n ", ""), format!("{}\r\n", "吉".repeat(40)));
+    }
+}
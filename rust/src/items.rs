@@ -0,0 +1,374 @@
+//! Lucky item and lucky action lists, in the spirit of morning-TV horoscopes.
+//! Order is part of the output format.
+
+use crate::lang::Lang;
+
+/// Everyday objects as (ja, en), selected by 8 hash bits
+const ITEMS: [(&str, &str); 256] = [
+    ("傘", "umbrella"),
+    ("ハンカチ", "handkerchief"),
+    ("腕時計", "wristwatch"),
+    ("鍵", "key"),
+    ("財布", "wallet"),
+    ("手帳", "pocket diary"),
+    ("ボールペン", "ballpoint pen"),
+    ("鉛筆", "pencil"),
+    ("消しゴム", "eraser"),
+    ("ノート", "notebook"),
+    ("付箋", "sticky notes"),
+    ("ホッチキス", "stapler"),
+    ("クリップ", "paper clip"),
+    ("はさみ", "scissors"),
+    ("定規", "ruler"),
+    ("輪ゴム", "rubber band"),
+    ("セロハンテープ", "sticky tape"),
+    ("封筒", "envelope"),
+    ("切手", "postage stamp"),
+    ("はがき", "postcard"),
+    ("カレンダー", "calendar"),
+    ("目覚まし時計", "alarm clock"),
+    ("マグカップ", "mug"),
+    ("湯のみ", "teacup"),
+    ("箸", "chopsticks"),
+    ("スプーン", "spoon"),
+    ("フォーク", "fork"),
+    ("お椀", "soup bowl"),
+    ("お皿", "plate"),
+    ("弁当箱", "lunch box"),
+    ("水筒", "water bottle"),
+    ("やかん", "kettle"),
+    ("急須", "teapot"),
+    ("フライパン", "frying pan"),
+    ("鍋", "cooking pot"),
+    ("おたま", "ladle"),
+    ("まな板", "cutting board"),
+    ("包丁", "kitchen knife"),
+    ("しゃもじ", "rice paddle"),
+    ("エプロン", "apron"),
+    ("ふきん", "dish towel"),
+    ("スポンジ", "sponge"),
+    ("タオル", "towel"),
+    ("歯ブラシ", "toothbrush"),
+    ("くし", "comb"),
+    ("手鏡", "hand mirror"),
+    ("リップクリーム", "lip balm"),
+    ("ハンドクリーム", "hand cream"),
+    ("石けん", "bar of soap"),
+    ("シャンプー", "shampoo"),
+    ("バスタオル", "bath towel"),
+    ("スリッパ", "slippers"),
+    ("靴下", "socks"),
+    ("手袋", "gloves"),
+    ("マフラー", "scarf"),
+    ("帽子", "hat"),
+    ("ネクタイ", "necktie"),
+    ("ベルト", "belt"),
+    ("スニーカー", "sneakers"),
+    ("下駄", "geta sandals"),
+    ("浴衣", "yukata"),
+    ("カーディガン", "cardigan"),
+    ("パーカー", "hoodie"),
+    ("Tシャツ", "T-shirt"),
+    ("ジーンズ", "jeans"),
+    ("パジャマ", "pajamas"),
+    ("エコバッグ", "tote bag"),
+    ("リュック", "backpack"),
+    ("名刺入れ", "card case"),
+    ("定期入れ", "pass holder"),
+    ("小銭入れ", "coin purse"),
+    ("キーホルダー", "key ring"),
+    ("お守り", "charm"),
+    ("眼鏡", "glasses"),
+    ("サングラス", "sunglasses"),
+    ("マスク", "face mask"),
+    ("イヤホン", "earphones"),
+    ("ヘッドホン", "headphones"),
+    ("スマートフォン", "smartphone"),
+    ("充電ケーブル", "charging cable"),
+    ("モバイルバッテリー", "power bank"),
+    ("USBメモリ", "USB stick"),
+    ("キーボード", "keyboard"),
+    ("マウス", "mouse"),
+    ("マウスパッド", "mouse pad"),
+    ("モニター", "monitor"),
+    ("ノートパソコン", "laptop"),
+    ("電卓", "calculator"),
+    ("ラジオ", "radio"),
+    ("懐中電灯", "flashlight"),
+    ("電池", "battery"),
+    ("延長コード", "extension cord"),
+    ("電球", "light bulb"),
+    ("ろうそく", "candle"),
+    ("マッチ", "matches"),
+    ("ライター", "lighter"),
+    ("うちわ", "paper fan"),
+    ("扇子", "folding fan"),
+    ("風鈴", "wind chime"),
+    ("座布団", "floor cushion"),
+    ("枕", "pillow"),
+    ("毛布", "blanket"),
+    ("こたつ", "kotatsu"),
+    ("湯たんぽ", "hot-water bottle"),
+    ("カイロ", "hand warmer"),
+    ("加湿器", "humidifier"),
+    ("観葉植物", "houseplant"),
+    ("サボテン", "cactus"),
+    ("花瓶", "vase"),
+    ("一輪挿し", "bud vase"),
+    ("植木鉢", "flower pot"),
+    ("じょうろ", "watering can"),
+    ("ほうき", "broom"),
+    ("ちりとり", "dustpan"),
+    ("掃除機", "vacuum cleaner"),
+    ("ゴミ袋", "trash bag"),
+    ("洗濯ばさみ", "clothespin"),
+    ("ハンガー", "hanger"),
+    ("アイロン", "iron"),
+    ("裁縫セット", "sewing kit"),
+    ("ボタン", "button"),
+    ("安全ピン", "safety pin"),
+    ("毛糸", "yarn"),
+    ("ししゅう糸", "embroidery thread"),
+    ("折り紙", "origami paper"),
+    ("色鉛筆", "colored pencils"),
+    ("絵の具", "paints"),
+    ("筆", "brush"),
+    ("墨", "ink stick"),
+    ("硯", "inkstone"),
+    ("半紙", "calligraphy paper"),
+    ("スケッチブック", "sketchbook"),
+    ("クレヨン", "crayons"),
+    ("マーカー", "marker"),
+    ("蛍光ペン", "highlighter"),
+    ("万年筆", "fountain pen"),
+    ("しおり", "bookmark"),
+    ("文庫本", "paperback"),
+    ("辞書", "dictionary"),
+    ("雑誌", "magazine"),
+    ("新聞", "newspaper"),
+    ("地図", "map"),
+    ("写真立て", "photo frame"),
+    ("アルバム", "photo album"),
+    ("カメラ", "camera"),
+    ("フィルム", "film roll"),
+    ("双眼鏡", "binoculars"),
+    ("虫眼鏡", "magnifying glass"),
+    ("方位磁針", "compass"),
+    ("地球儀", "globe"),
+    ("砂時計", "hourglass"),
+    ("温度計", "thermometer"),
+    ("体重計", "bathroom scale"),
+    ("メジャー", "tape measure"),
+    ("ドライバー", "screwdriver"),
+    ("ペンチ", "pliers"),
+    ("金づち", "hammer"),
+    ("くぎ", "nail"),
+    ("ねじ", "screw"),
+    ("ガムテープ", "packing tape"),
+    ("段ボール", "cardboard box"),
+    ("紙袋", "paper bag"),
+    ("風呂敷", "furoshiki"),
+    ("手ぬぐい", "tenugui"),
+    ("巾着", "drawstring pouch"),
+    ("がま口", "clasp purse"),
+    ("印鑑", "personal seal"),
+    ("朱肉", "ink pad"),
+    ("通帳", "bankbook"),
+    ("レシート", "receipt"),
+    ("ポイントカード", "loyalty card"),
+    ("貯金箱", "piggy bank"),
+    ("五円玉", "five-yen coin"),
+    ("サイコロ", "die"),
+    ("トランプ", "playing cards"),
+    ("けん玉", "kendama"),
+    ("こま", "spinning top"),
+    ("ビー玉", "marble"),
+    ("ヨーヨー", "yo-yo"),
+    ("ルービックキューブ", "puzzle cube"),
+    ("ジグソーパズル", "jigsaw puzzle"),
+    ("将棋の駒", "shogi piece"),
+    ("碁石", "go stone"),
+    ("ぬいぐるみ", "stuffed animal"),
+    ("招き猫", "beckoning cat"),
+    ("だるま", "daruma doll"),
+    ("こけし", "kokeshi doll"),
+    ("鈴", "bell"),
+    ("風船", "balloon"),
+    ("シャボン玉", "soap bubbles"),
+    ("紙飛行機", "paper plane"),
+    ("凧", "kite"),
+    ("ボール", "ball"),
+    ("縄跳び", "jump rope"),
+    ("自転車", "bicycle"),
+    ("ヘルメット", "helmet"),
+    ("水着", "swimsuit"),
+    ("ゴーグル", "goggles"),
+    ("ヨガマット", "yoga mat"),
+    ("ダンベル", "dumbbell"),
+    ("ストップウォッチ", "stopwatch"),
+    ("テニスラケット", "tennis racket"),
+    ("バット", "baseball bat"),
+    ("グローブ", "baseball glove"),
+    ("釣り竿", "fishing rod"),
+    ("テント", "tent"),
+    ("寝袋", "sleeping bag"),
+    ("ランタン", "lantern"),
+    ("水鉄砲", "water pistol"),
+    ("麦わら帽子", "straw hat"),
+    ("日焼け止め", "sunscreen"),
+    ("虫よけ", "insect repellent"),
+    ("絆創膏", "adhesive bandage"),
+    ("目薬", "eye drops"),
+    ("のど飴", "throat lozenge"),
+    ("救急箱", "first-aid kit"),
+    ("体温計", "clinical thermometer"),
+    ("ティッシュ", "tissues"),
+    ("ウェットティッシュ", "wet wipes"),
+    ("おにぎり", "rice ball"),
+    ("梅干し", "pickled plum"),
+    ("味噌汁", "miso soup"),
+    ("緑茶", "green tea"),
+    ("コーヒー豆", "coffee beans"),
+    ("紅茶", "black tea"),
+    ("はちみつ", "honey"),
+    ("チョコレート", "chocolate"),
+    ("キャンディ", "candy"),
+    ("せんべい", "rice cracker"),
+    ("大福", "daifuku"),
+    ("どら焼き", "dorayaki"),
+    ("食パン", "loaf of bread"),
+    ("卵", "egg"),
+    ("牛乳", "milk"),
+    ("りんご", "apple"),
+    ("みかん", "mandarin orange"),
+    ("バナナ", "banana"),
+    ("いちご", "strawberry"),
+    ("ぶどう", "grapes"),
+    ("トマト", "tomato"),
+    ("きゅうり", "cucumber"),
+    ("大根", "daikon radish"),
+    ("にんじん", "carrot"),
+    ("ねぎ", "green onion"),
+    ("豆腐", "tofu"),
+    ("納豆", "natto"),
+    ("のり", "nori"),
+    ("塩", "salt"),
+    ("しょうゆ", "soy sauce"),
+    ("わさび", "wasabi"),
+    ("七味唐辛子", "shichimi pepper"),
+    ("ふりかけ", "furikake"),
+    ("カップ麺", "cup noodles"),
+    ("レトルトカレー", "boxed curry"),
+    ("炭酸水", "sparkling water"),
+    ("保存容器", "food container"),
+];
+
+/// Small actions as (ja, en), selected by 6 hash bits
+const ACTIONS: [(&str, &str); 64] = [
+    ("早起きする", "Wake up early"),
+    ("朝日を浴びる", "Catch the morning sun"),
+    ("散歩する", "Take a walk"),
+    ("一駅歩く", "Walk one extra station"),
+    ("階段を使う", "Take the stairs"),
+    ("ストレッチする", "Stretch"),
+    ("深呼吸する", "Breathe deeply"),
+    ("水を一杯飲む", "Drink a glass of water"),
+    ("温かいお茶を淹れる", "Brew a warm cup of tea"),
+    ("朝ごはんをしっかり食べる", "Eat a proper breakfast"),
+    ("新しい店に入る", "Try a new shop"),
+    ("いつもと違う道を通る", "Take a different route"),
+    ("机を片付ける", "Tidy your desk"),
+    ("引き出しを一つ整理する", "Sort out one drawer"),
+    ("窓を開けて換気する", "Open the windows"),
+    ("花を飾る", "Put flowers on display"),
+    ("植物に水をやる", "Water the plants"),
+    ("靴を磨く", "Polish your shoes"),
+    ("ハンカチを持ち歩く", "Carry a handkerchief"),
+    ("手紙を書く", "Write a letter"),
+    ("友人に連絡する", "Message a friend"),
+    ("家族に電話する", "Call your family"),
+    ("ありがとうを言う", "Say thank you"),
+    ("笑顔で挨拶する", "Greet people with a smile"),
+    ("人を褒める", "Compliment someone"),
+    ("誰かの手伝いをする", "Help someone out"),
+    ("本を一章読む", "Read a chapter of a book"),
+    ("日記をつける", "Keep a diary"),
+    ("やることリストを作る", "Write a to-do list"),
+    ("予定を一つ減らす", "Cancel one plan"),
+    ("早めに寝る", "Go to bed early"),
+    ("昼寝を少しする", "Take a short nap"),
+    ("湯船につかる", "Soak in a bath"),
+    ("好きな音楽を聴く", "Listen to your favorite music"),
+    ("歌を口ずさむ", "Hum a song"),
+    ("写真を撮る", "Take a photo"),
+    ("空を見上げる", "Look up at the sky"),
+    ("月を眺める", "Gaze at the moon"),
+    ("神社にお参りする", "Visit a shrine"),
+    ("小銭を募金する", "Donate your spare change"),
+    ("財布の中を整理する", "Clean out your wallet"),
+    ("古いメールを消す", "Delete old email"),
+    ("パスワードを変える", "Change a password"),
+    ("バックアップを取る", "Back up your files"),
+    ("コードを一行消す", "Delete a line of code"),
+    ("テストを一つ書く", "Write one more test"),
+    ("ドキュメントを直す", "Fix some documentation"),
+    ("質問をする", "Ask a question"),
+    ("新しい言葉を覚える", "Learn a new word"),
+    ("料理を一品作る", "Cook a new dish"),
+    ("旬のものを食べる", "Eat something in season"),
+    ("甘いものを少し食べる", "Treat yourself to something sweet"),
+    ("コーヒーを豆から淹れる", "Grind your own coffee"),
+    ("掃除機をかける", "Vacuum the floor"),
+    ("洗濯物を干す", "Hang out the laundry"),
+    ("ベッドを整える", "Make your bed"),
+    ("鏡を磨く", "Polish a mirror"),
+    ("玄関を掃く", "Sweep the entrance"),
+    ("寄り道をする", "Make a detour"),
+    ("ゆっくり歩く", "Walk slowly"),
+    ("スマホを置いて過ごす", "Spend an hour without your phone"),
+    ("目を閉じて休む", "Rest your eyes"),
+    ("背筋を伸ばす", "Sit up straight"),
+    ("五分だけ瞑想する", "Meditate for five minutes"),
+];
+
+fn localize(entry: (&'static str, &'static str), lang: Lang) -> &'static str {
+    match lang {
+        Lang::Ja => entry.0,
+        Lang::En => entry.1,
+    }
+}
+
+/// Sizes of the embedded tables
+pub const ITEM_COUNT: usize = ITEMS.len();
+pub const ACTION_COUNT: usize = ACTIONS.len();
+
+pub fn lucky_item(index: u8, lang: Lang) -> &'static str {
+    localize(ITEMS[index as usize], lang)
+}
+
+pub fn lucky_action(index: u8, lang: Lang) -> &'static str {
+    localize(ACTIONS[index as usize % ACTIONS.len()], lang)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_entries_unique() {
+        for list in [&ITEMS[..], &ACTIONS[..]] {
+            let ja: HashSet<_> = list.iter().map(|e| e.0).collect();
+            let en: HashSet<_> = list.iter().map(|e| e.1).collect();
+            assert_eq!(ja.len(), list.len());
+            assert_eq!(en.len(), list.len());
+        }
+    }
+
+    #[test]
+    fn test_localized() {
+        assert_eq!(lucky_item(0, Lang::En), "umbrella");
+        assert_eq!(lucky_item(0, Lang::Ja), "傘");
+        assert_eq!(lucky_action(64, Lang::En), lucky_action(0, Lang::En));
+    }
+}
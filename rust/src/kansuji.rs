@@ -0,0 +1,114 @@
+//! Kanji numerals (漢数字) for `--kanji-numerals`: slip numbers and scores the way
+//! real omikuji are printed, `第八十八番` and `七十三点`.
+
+const DIGITS: [char; 10] = ['〇', '一', '二', '三', '四', '五', '六', '七', '八', '九'];
+
+/// 0-9999 with place units; a leading 一 is dropped before 十, 百 and 千
+fn group(n: u64) -> String {
+    let mut output = String::new();
+    for (unit, value) in [('千', 1000), ('百', 100), ('十', 10)] {
+        let digit = n / value % 10;
+        if digit > 1 {
+            output.push(DIGITS[digit as usize]);
+        }
+        if digit > 0 {
+            output.push(unit);
+        }
+    }
+    let ones = n % 10;
+    if ones > 0 {
+        output.push(DIGITS[ones as usize]);
+    }
+    output
+}
+
+/// `88` as `八十八`, `2026` as `二千二十六`, `10000` as `一万`
+pub fn kansuji(n: u64) -> String {
+    if n == 0 {
+        return DIGITS[0].to_string();
+    }
+    let mut output = String::new();
+    for (unit, value) in [("京", 10_000_000_000_000_000), ("兆", 1_000_000_000_000), ("億", 100_000_000), ("万", 10_000), ("", 1)] {
+        let part = n / value % 10_000;
+        if part > 0 {
+            output.push_str(&group(part));
+            output.push_str(unit);
+        }
+    }
+    output
+}
+
+/// Digit by digit, as years are often written: `2026` as `二〇二六`
+pub fn digits(n: u64) -> String {
+    n.to_string().chars().map(|c| DIGITS[c as usize - '0' as usize]).collect()
+}
+
+/// Every run of ASCII digits in `text` as kanji numerals: `令和8年` as `令和八年`
+pub fn in_text(text: &str) -> String {
+    let mut output = String::new();
+    let mut run = String::new();
+    for c in text.chars().chain([' ']) {
+        if c.is_ascii_digit() {
+            run.push(c);
+            continue;
+        }
+        if !run.is_empty() {
+            // Runs too long for a u64 are left as they are
+            output.push_str(&run.parse().map(kansuji).unwrap_or_else(|_| run.clone()));
+            run.clear();
+        }
+        output.push(c);
+    }
+    output.pop();
+    output
+}
+
+/// Slip number: `第八十八番`
+pub fn slip_number(n: u64) -> String {
+    format!("第{}番", kansuji(n))
+}
+
+/// Score: `七十三点`
+pub fn points(n: u64) -> String {
+    format!("{}点", kansuji(n))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kansuji() {
+        for (n, text) in [
+            (0, "〇"),
+            (1, "一"),
+            (10, "十"),
+            (11, "十一"),
+            (20, "二十"),
+            (88, "八十八"),
+            (100, "百"),
+            (105, "百五"),
+            (255, "二百五十五"),
+            (1000, "千"),
+            (2026, "二千二十六"),
+            (10_000, "一万"),
+            (110_000, "十一万"),
+            (100_000_000, "一億"),
+            (123_456_789, "一億二千三百四十五万六千七百八十九"),
+        ] {
+            assert_eq!(kansuji(n), text, "{}", n);
+        }
+        assert_eq!(slip_number(88), "第八十八番");
+        assert_eq!(points(73), "七十三点");
+        assert_eq!(digits(2026), "二〇二六");
+    }
+
+    #[test]
+    fn test_in_text() {
+        assert_eq!(in_text("令和8年"), "令和八年");
+        assert_eq!(in_text("令和元年"), "令和元年");
+        assert_eq!(in_text("2026 (令和8年)"), "二千二十六 (令和八年)");
+        assert_eq!(in_text("12:05"), "十二:五");
+        assert_eq!(in_text(""), "");
+    }
+}
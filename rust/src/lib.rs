@@ -0,0 +1,168 @@
+//! SHA-256 based deterministic fortune telling.
+//!
+//! The same year and seed always draw the same [`Fortune`]:
+//!
+//! ```
+//! # #[cfg(feature = "std")] {
+//! use hash_omikuji::{Algo, Fortune, Layout, TextOptions};
+//!
+//! let fortune = Fortune::builder().year(2026).seed("alice").layout(Layout::V2).algo(Algo::Blake3).draw();
+//! assert!(fortune.format_text(&TextOptions::default()).contains("Hash-Omikuji 2026"));
+//! # }
+//! ```
+//!
+//! The `cli` feature (on by default) adds the command-line arguments used by the
+//! `hash-omikuji` binary; `std` alone gives the full library. With no features the
+//! crate is `no_std` + `alloc` and keeps only the core: [`HashBits`], grading and art.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "std")]
+pub mod achievements;
+#[cfg(feature = "std")]
+pub mod advice;
+pub mod art;
+#[cfg(feature = "cli")]
+pub mod batch;
+#[cfg(feature = "std")]
+pub mod birthday;
+#[cfg(feature = "bot")]
+pub mod bot;
+#[cfg(feature = "std")]
+pub mod builder;
+#[cfg(feature = "std")]
+pub mod cache;
+#[cfg(feature = "std")]
+pub mod capsule;
+#[cfg(feature = "std")]
+pub mod card;
+#[cfg(feature = "std")]
+pub mod chart;
+#[cfg(feature = "cli")]
+pub mod cli;
+#[cfg(feature = "std")]
+pub mod config;
+#[cfg(feature = "cli")]
+pub mod console;
+#[cfg(feature = "std")]
+pub mod conformance;
+#[cfg(feature = "std")]
+pub mod corpus;
+#[cfg(feature = "std")]
+pub mod date;
+#[cfg(feature = "std")]
+pub mod discord;
+#[cfg(feature = "std")]
+pub mod effort;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "std")]
+pub mod error;
+#[cfg(feature = "std")]
+pub mod eto;
+#[cfg(feature = "std")]
+pub mod family;
+#[cfg(feature = "std")]
+pub mod feed;
+pub mod fingerprint;
+#[cfg(feature = "std")]
+pub mod forecast;
+#[cfg(feature = "std")]
+pub mod fortunes;
+#[cfg(feature = "std")]
+pub mod gha;
+#[cfg(feature = "std")]
+pub mod graphics;
+#[cfg(feature = "graphql")]
+pub mod graphql;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+pub mod hash;
+#[cfg(feature = "std")]
+pub mod history;
+#[cfg(feature = "std")]
+pub mod hook;
+#[cfg(feature = "std")]
+pub mod ics;
+pub mod items;
+#[cfg(feature = "std")]
+pub mod kansuji;
+pub mod lang;
+pub mod luck;
+#[cfg(feature = "std")]
+pub mod lucky_commit;
+#[cfg(feature = "std")]
+pub mod lunar;
+#[cfg(feature = "std")]
+pub mod melody;
+#[cfg(feature = "std")]
+pub mod motd;
+#[cfg(feature = "std")]
+pub mod metrics;
+#[cfg(feature = "cli")]
+pub mod mqtt;
+#[cfg(feature = "std")]
+pub mod nengajo;
+#[cfg(feature = "node")]
+pub mod node;
+#[cfg(feature = "std")]
+pub mod omamori;
+#[cfg(feature = "std")]
+pub mod output;
+pub mod pgp_words;
+pub mod poems;
+#[cfg(feature = "std")]
+pub mod project;
+#[cfg(feature = "std")]
+pub mod prompt;
+#[cfg(feature = "python")]
+pub mod python;
+#[cfg(feature = "std")]
+pub mod qr;
+#[cfg(feature = "cli")]
+pub mod schedule;
+#[cfg(feature = "std")]
+pub mod schema;
+#[cfg(feature = "serve")]
+pub mod serve;
+#[cfg(feature = "std")]
+pub mod share;
+#[cfg(feature = "std")]
+pub mod sign;
+#[cfg(feature = "std")]
+pub mod simulate;
+#[cfg(feature = "std")]
+pub mod sink;
+#[cfg(feature = "std")]
+pub mod slack;
+#[cfg(feature = "std")]
+pub mod speech;
+#[cfg(feature = "std")]
+pub mod streak;
+#[cfg(feature = "std")]
+pub mod team;
+#[cfg(feature = "serve")]
+pub mod tenants;
+pub mod theme;
+pub mod topics;
+#[cfg(feature = "tui")]
+pub mod tui;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "std")]
+pub mod wareki;
+#[cfg(feature = "cli")]
+pub mod webhook;
+#[cfg(feature = "std")]
+pub mod window;
+
+pub use art::{ArtMode, ArtOptions, ArtPalette, Walk};
+#[cfg(feature = "std")]
+pub use builder::FortuneBuilder;
+pub use hash::{Algo, HashBits, Layout};
+pub use lang::Lang;
+pub use luck::{CategoryWeights, Grade, GradeWeights, LuckScore};
+#[cfg(feature = "std")]
+pub use output::{Fortune, TextOptions};
@@ -1,6 +1,244 @@
-use serde::Serialize;
+use crate::lang::Lang;
+use serde::{Deserialize, Serialize};
+use core::cmp::Ordering;
+use core::fmt;
+use core::str::FromStr;
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloc::format;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+/// Traditional omikuji grade ladder, best first.
+/// Ordering follows luck, so `Grade::Daikichi > Grade::Daikyo`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "serve", derive(utoipa::ToSchema))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
+#[cfg_attr(feature = "std", derive(schemars::JsonSchema))]
+pub enum Grade {
+    #[serde(alias = "daikichi", alias = "大吉")]
+    Daikichi,
+    #[serde(alias = "chukichi", alias = "中吉")]
+    Chukichi,
+    #[serde(alias = "shokichi", alias = "小吉")]
+    Shokichi,
+    #[serde(alias = "kichi", alias = "吉")]
+    Kichi,
+    #[serde(alias = "suekichi", alias = "末吉")]
+    Suekichi,
+    #[serde(alias = "kyo", alias = "凶")]
+    Kyo,
+    #[serde(alias = "daikyo", alias = "大凶")]
+    Daikyo,
+}
+
+impl Grade {
+    pub const ALL: [Grade; 7] = [
+        Grade::Daikichi,
+        Grade::Chukichi,
+        Grade::Shokichi,
+        Grade::Kichi,
+        Grade::Suekichi,
+        Grade::Kyo,
+        Grade::Daikyo,
+    ];
+
+    /// 0 for 大凶 up to 6 for 大吉
+    pub fn rank(&self) -> u8 {
+        6 - Grade::ALL.iter().position(|g| g == self).unwrap_or(6) as u8
+    }
+
+    pub fn romaji(&self) -> &'static str {
+        match self {
+            Grade::Daikichi => "daikichi",
+            Grade::Chukichi => "chukichi",
+            Grade::Shokichi => "shokichi",
+            Grade::Kichi => "kichi",
+            Grade::Suekichi => "suekichi",
+            Grade::Kyo => "kyo",
+            Grade::Daikyo => "daikyo",
+        }
+    }
+
+    pub fn name(&self, lang: Lang) -> &'static str {
+        match lang {
+            Lang::Ja => self.kanji(),
+            Lang::En => self.english(),
+        }
+    }
+
+    pub fn kanji(&self) -> &'static str {
+        match self {
+            Grade::Daikichi => "大吉",
+            Grade::Chukichi => "中吉",
+            Grade::Shokichi => "小吉",
+            Grade::Kichi => "吉",
+            Grade::Suekichi => "末吉",
+            Grade::Kyo => "凶",
+            Grade::Daikyo => "大凶",
+        }
+    }
+
+    pub fn english(&self) -> &'static str {
+        match self {
+            Grade::Daikichi => "Great Blessing",
+            Grade::Chukichi => "Middle Blessing",
+            Grade::Shokichi => "Small Blessing",
+            Grade::Kichi => "Blessing",
+            Grade::Suekichi => "Future Blessing",
+            Grade::Kyo => "Curse",
+            Grade::Daikyo => "Great Curse",
+        }
+    }
+
+    /// Map a 12-bit hash value onto the ladder using cumulative weights
+    pub fn from_bits(value: u16, weights: &GradeWeights) -> Self {
+        let total: u64 = weights.0.iter().map(|&w| w as u64).sum();
+        let pick = (value as u64 & 0xFFF) * total / 4096;
+        let mut cumulative = 0u64;
+        for (grade, &weight) in Grade::ALL.iter().zip(weights.0.iter()) {
+            cumulative += weight as u64;
+            if pick < cumulative {
+                return *grade;
+            }
+        }
+        Grade::Daikyo
+    }
+
+    /// Exact probability of drawing this grade over all 4096 grade values: `from_bits`
+    /// picks it for the v with `v * total / 4096` in `[lo, hi)` of its cumulative weights
+    pub fn probability(&self, weights: &GradeWeights) -> f64 {
+        let total: u64 = weights.0.iter().map(|&w| w as u64).sum();
+        if total == 0 {
+            return if *self == Grade::Daikyo { 1.0 } else { 0.0 };
+        }
+        let index = Grade::ALL.iter().position(|g| g == self).unwrap_or(6);
+        let lo: u64 = weights.0[..index].iter().map(|&w| w as u64).sum();
+        let hi = lo + weights.0[index] as u64;
+        // The first v with v * total / 4096 >= bound
+        let first = |bound: u64| (bound * 4096).div_ceil(total).min(4096);
+        (first(hi) - first(lo)) as f64 / 4096.0
+    }
+}
+
+impl Ord for Grade {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.rank().cmp(&other.rank())
+    }
+}
+
+impl PartialOrd for Grade {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Grade {
+    /// Japanese by default (`大吉`); the alternate flag gives English (`{:#}` -> `Great Blessing`)
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lang = if f.alternate() { Lang::En } else { Lang::Ja };
+        f.write_str(self.name(lang))
+    }
+}
+
+impl FromStr for Grade {
+    type Err = String;
+
+    /// Accepts kanji ("大吉"), romaji with or without long vowels ("daikichi", "kyou")
+    /// and English names ("great blessing"), case-insensitively
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalized = s.trim().to_lowercase().replace("uu", "u").replace("ou", "o");
+        Grade::ALL
+            .iter()
+            .find(|g| {
+                normalized == g.romaji()
+                    || s.trim() == g.kanji()
+                    || s.trim().eq_ignore_ascii_case(g.english())
+            })
+            .copied()
+            .ok_or_else(|| format!("Unknown grade '{}': expected 大吉..大凶 or daikichi..daikyo", s))
+    }
+}
+
+/// Probability that the best of the 16 category scores is exactly `score`.
+/// Each raw value is uniform over 0-255 and `score = raw * 100 / 255`.
+pub fn top_score_probability(score: u8) -> f64 {
+    let raws: Vec<u32> = (0..=255u32).filter(|&r| r * 100 / 255 == score as u32).collect();
+    let (Some(&lo), Some(&hi)) = (raws.first(), raws.last()) else {
+        return 0.0;
+    };
+    // x^16 by repeated squaring (no float intrinsics under no_std)
+    let cdf = |raw: u32| {
+        let x = (raw + 1) as f64 / 256.0;
+        let x4 = x * x * (x * x);
+        x4 * x4 * (x4 * x4)
+    };
+    let below = if lo == 0 { 0.0 } else { cdf(lo - 1) };
+    cdf(hi) - below
+}
+
+/// How many draws it takes on average to see this grade together with this top score
+pub fn rarity_one_in(grade: Grade, weights: &GradeWeights, top_score: u8) -> u64 {
+    let p = grade.probability(weights) * top_score_probability(top_score);
+    if p <= 0.0 {
+        return u64::MAX;
+    }
+    (1.0 / p + 0.5) as u64
+}
+
+/// "roughly 1 in 1,200 draws" (two significant figures, thousands separators)
+pub fn format_rarity(one_in: u64) -> String {
+    if one_in <= 1 {
+        return "about every draw".to_string();
+    }
+    // Rounded in u128: near u64::MAX rounding up overflows u64
+    let magnitude = 10u128.pow((one_in.ilog10()).saturating_sub(1));
+    let rounded = (one_in as u128 + magnitude / 2) / magnitude * magnitude;
+    let digits = rounded.to_string();
+    let mut grouped = String::new();
+    for (i, ch) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(ch);
+    }
+    format!("roughly 1 in {} draws", grouped)
+}
+
+/// Relative draw weights for each grade, in ladder order (大吉 ... 大凶)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GradeWeights(pub [u32; 7]);
+
+impl Default for GradeWeights {
+    /// Roughly what a typical shrine box holds: mostly blessings, a few curses
+    fn default() -> Self {
+        GradeWeights([16, 20, 20, 18, 12, 10, 4])
+    }
+}
+
+impl FromStr for GradeWeights {
+    type Err = String;
+
+    /// Parse seven comma-separated weights, e.g. "16,20,20,18,12,10,4"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let values: Vec<u32> = s
+            .split(',')
+            .map(|part| part.trim().parse::<u32>())
+            .collect::<Result<_, _>>()
+            .map_err(|_| format!("Invalid grade weights '{}': expected 7 non-negative integers", s))?;
+        let weights: [u32; 7] = values
+            .try_into()
+            .map_err(|v: Vec<u32>| format!("Expected 7 grade weights (大吉..大凶), got {}", v.len()))?;
+        if weights.iter().all(|&w| w == 0) {
+            return Err("At least one grade weight must be positive".to_string());
+        }
+        Ok(GradeWeights(weights))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "serve", derive(utoipa::ToSchema))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
+#[cfg_attr(feature = "std", derive(schemars::JsonSchema))]
 pub enum Rank {
     Excellent,
     Good,
@@ -31,7 +269,10 @@ impl Rank {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "serve", derive(utoipa::ToSchema))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
+#[cfg_attr(feature = "std", derive(schemars::JsonSchema))]
 pub enum LuckType {
     Life,
     Health,
@@ -92,11 +333,28 @@ impl LuckType {
         }
     }
 
+    /// Lowercase key used in config files, e.g. `study`, `wifi`
+    pub fn key(&self) -> String {
+        format!("{:?}", self).to_lowercase()
+    }
+
+    pub fn from_key(key: &str) -> Option<LuckType> {
+        LuckType::ALL.iter().copied().find(|t| t.key() == key.trim().to_lowercase())
+    }
+
+    pub fn index(&self) -> usize {
+        LuckType::ALL.iter().position(|t| t == self).unwrap_or(0)
+    }
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "serve", derive(utoipa::ToSchema))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject), graphql(rename_fields = "snake_case"))]
+#[cfg_attr(feature = "std", derive(schemars::JsonSchema))]
 pub struct LuckScore {
     pub luck_type: LuckType,
+    /// Display label; the built-in name unless relabeled in the config file
+    pub label: Cow<'static, str>,
     pub raw_value: u8,
     pub score: u8,
     pub rank: Rank,
@@ -108,6 +366,7 @@ impl LuckScore {
         let rank = Rank::from_score(score);
         Self {
             luck_type,
+            label: Cow::Borrowed(luck_type.name()),
             raw_value,
             score,
             rank,
@@ -115,6 +374,75 @@ impl LuckScore {
     }
 }
 
+/// Relative weight of each luck category in the overall score, in `LuckType::ALL` order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CategoryWeights(pub [u32; 16]);
+
+impl Default for CategoryWeights {
+    fn default() -> Self {
+        CategoryWeights([1; 16])
+    }
+}
+
+impl FromStr for CategoryWeights {
+    type Err = String;
+
+    /// Parse sixteen comma-separated weights in category order
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let values: Vec<u32> = s
+            .split(',')
+            .map(|part| part.trim().parse::<u32>())
+            .collect::<Result<_, _>>()
+            .map_err(|_| format!("Invalid category weights '{}': expected 16 non-negative integers", s))?;
+        let weights: [u32; 16] = values
+            .try_into()
+            .map_err(|v: Vec<u32>| format!("Expected 16 category weights, got {}", v.len()))?;
+        if weights.iter().all(|&w| w == 0) {
+            return Err("At least one category weight must be positive".to_string());
+        }
+        Ok(CategoryWeights(weights))
+    }
+}
+
+/// Weighted mean of the category scores, normalized to 0-100
+pub fn overall_score(scores: &[LuckScore], weights: &CategoryWeights) -> u8 {
+    let mut weighted_sum = 0u64;
+    let mut total_weight = 0u64;
+    for score in scores {
+        let weight = weights.0[score.luck_type.index()] as u64;
+        weighted_sum += weight * score.score as u64;
+        total_weight += weight;
+    }
+    if total_weight == 0 {
+        return 0;
+    }
+    // Round half up
+    ((weighted_sum * 2 + total_weight) / (total_weight * 2)) as u8
+}
+
+/// Five-star rating, e.g. 73 -> "★★★★☆" (each star covers 20 points, rounded)
+pub fn star_rating(score: u8) -> String {
+    let filled = ((score.min(100) as usize) + 10) / 20;
+    let mut stars = String::with_capacity(5 * '★'.len_utf8());
+    (0..5).for_each(|i| stars.push(if i < filled { '★' } else { '☆' }));
+    stars
+}
+
+/// One block character per score in category order, e.g. [0, 50, 100] -> "▁▄█"
+pub fn sparkline(scores: &[LuckScore]) -> String {
+    const LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    scores
+        .iter()
+        .map(|s| LEVELS[(s.score.min(100) as usize * 7 + 50) / 100])
+        .collect()
+}
+
+/// Horizontal bar of `width` cells, filled in proportion to the score (rounded)
+pub fn score_bar(score: u8, width: usize) -> String {
+    let filled = (score.min(100) as usize * width + 50) / 100;
+    format!("{}{}", "█".repeat(filled), "░".repeat(width - filled))
+}
+
 pub fn calculate_luck_scores(scores: &[u8; 16]) -> Vec<LuckScore> {
     LuckType::ALL
         .iter()
@@ -153,6 +481,202 @@ mod tests {
         assert_eq!(luck.score, 49);
     }
 
+    #[test]
+    fn test_grade_from_bits_default_weights() {
+        let weights = GradeWeights::default();
+        assert_eq!(Grade::from_bits(0, &weights), Grade::Daikichi);
+        assert_eq!(Grade::from_bits(0xFFF, &weights), Grade::Daikyo);
+        // 16% of 4096 = 655.36 -> 655 is the last 大吉 value
+        assert_eq!(Grade::from_bits(655, &weights), Grade::Daikichi);
+        assert_eq!(Grade::from_bits(656, &weights), Grade::Chukichi);
+    }
+
+    #[test]
+    fn test_grade_from_bits_distribution_follows_weights() {
+        let weights = GradeWeights::default();
+        let mut counts = [0u32; 7];
+        for value in 0..4096u16 {
+            let grade = Grade::from_bits(value, &weights);
+            counts[Grade::ALL.iter().position(|&g| g == grade).unwrap()] += 1;
+        }
+        for (count, weight) in counts.iter().zip(weights.0.iter()) {
+            let expected = 4096 * weight / 100;
+            assert!(count.abs_diff(expected) <= 1, "{} vs {}", count, expected);
+        }
+    }
+
+    #[test]
+    fn test_grade_zero_weight_never_drawn() {
+        let weights: GradeWeights = "1,0,0,0,0,0,1".parse().unwrap();
+        for value in 0..4096u16 {
+            let grade = Grade::from_bits(value, &weights);
+            assert!(grade == Grade::Daikichi || grade == Grade::Daikyo);
+        }
+    }
+
+    #[test]
+    fn test_grade_weights_parse_errors() {
+        assert!("1,2,3".parse::<GradeWeights>().is_err());
+        assert!("a,b,c,d,e,f,g".parse::<GradeWeights>().is_err());
+        assert!("0,0,0,0,0,0,0".parse::<GradeWeights>().is_err());
+        assert_eq!("16, 20, 20, 18, 12, 10, 4".parse::<GradeWeights>().unwrap(), GradeWeights::default());
+    }
+
+    #[test]
+    fn test_overall_score_equal_weights() {
+        let scores = calculate_luck_scores(&[255; 16]);
+        assert_eq!(overall_score(&scores, &CategoryWeights::default()), 100);
+        let scores = calculate_luck_scores(&[0; 16]);
+        assert_eq!(overall_score(&scores, &CategoryWeights::default()), 0);
+    }
+
+    #[test]
+    fn test_overall_score_respects_weights() {
+        let mut raw = [0u8; 16];
+        raw[8] = 255; // Study
+        let scores = calculate_luck_scores(&raw);
+        let mut weights = [0u32; 16];
+        weights[8] = 1;
+        weights[0] = 1;
+        assert_eq!(overall_score(&scores, &CategoryWeights(weights)), 50);
+        weights[8] = 3;
+        assert_eq!(overall_score(&scores, &CategoryWeights(weights)), 75);
+    }
+
+    #[test]
+    fn test_star_rating() {
+        assert_eq!(star_rating(0), "☆☆☆☆☆");
+        assert_eq!(star_rating(9), "☆☆☆☆☆");
+        assert_eq!(star_rating(10), "★☆☆☆☆");
+        assert_eq!(star_rating(73), "★★★★☆");
+        assert_eq!(star_rating(100), "★★★★★");
+    }
+
+    #[test]
+    fn test_sparkline_and_bar() {
+        let scores = calculate_luck_scores(&[0, 255, 128, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        let line = sparkline(&scores);
+        assert_eq!(line.chars().count(), 16);
+        assert!(line.starts_with("▁█"));
+
+        assert_eq!(score_bar(0, 10), "░░░░░░░░░░");
+        assert_eq!(score_bar(55, 10), "██████░░░░");
+        assert_eq!(score_bar(100, 10), "██████████");
+    }
+
+    #[test]
+    fn test_category_weights_parse() {
+        assert_eq!("1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1".parse::<CategoryWeights>().unwrap(), CategoryWeights::default());
+        assert!("1,1".parse::<CategoryWeights>().is_err());
+        assert!("0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0".parse::<CategoryWeights>().is_err());
+    }
+
+    #[test]
+    fn test_grade_probabilities_sum_to_one() {
+        let weights = GradeWeights::default();
+        let total: f64 = Grade::ALL.iter().map(|g| g.probability(&weights)).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+        assert!((Grade::Daikichi.probability(&weights) - 0.16).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_grade_probability_matches_exhaustive_count() {
+        for text in ["16,24,20,20,10,6,4", "1,0,0,0,0,0,0", "0,0,0,0,0,0,1", "3,0,5,0,7,0,11", "4096,1,1,1,1,1,4095", "1,1,1,1,1,1,1"] {
+            let weights: GradeWeights = text.parse().unwrap();
+            for grade in Grade::ALL {
+                let hits = (0..4096u16).filter(|&v| Grade::from_bits(v, &weights) == grade).count();
+                assert_eq!(grade.probability(&weights), hits as f64 / 4096.0, "{} {:?}", text, grade);
+            }
+        }
+    }
+
+    #[test]
+    fn test_top_score_probabilities_sum_to_one() {
+        let total: f64 = (0..=100u8).map(top_score_probability).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+        // A perfect 100 needs some raw value of exactly 255
+        let expected = 1.0 - (255.0f64 / 256.0).powi(16);
+        assert!((top_score_probability(100) - expected).abs() < 1e-12);
+        assert!(top_score_probability(101) == 0.0);
+    }
+
+    #[test]
+    fn test_rarity_one_in() {
+        let weights = GradeWeights::default();
+        let p = Grade::Daikyo.probability(&weights) * top_score_probability(100);
+        assert_eq!(rarity_one_in(Grade::Daikyo, &weights, 100), (1.0 / p).round() as u64);
+        // Low top scores are far rarer than high ones with 16 draws
+        assert!(rarity_one_in(Grade::Kichi, &weights, 40) > rarity_one_in(Grade::Kichi, &weights, 95));
+        let never: GradeWeights = "1,0,0,0,0,0,0".parse().unwrap();
+        assert_eq!(rarity_one_in(Grade::Daikyo, &never, 90), u64::MAX);
+    }
+
+    #[test]
+    fn test_format_rarity() {
+        assert_eq!(format_rarity(1), "about every draw");
+        assert_eq!(format_rarity(7), "roughly 1 in 7 draws");
+        assert_eq!(format_rarity(1234), "roughly 1 in 1,200 draws");
+        assert_eq!(format_rarity(1250), "roughly 1 in 1,300 draws");
+        assert_eq!(format_rarity(987_654), "roughly 1 in 990,000 draws");
+        assert_eq!(format_rarity(u64::MAX), "roughly 1 in 18,000,000,000,000,000,000 draws");
+    }
+
+    #[test]
+    fn test_grade_ordering() {
+        assert!(Grade::Daikichi > Grade::Chukichi);
+        assert!(Grade::Kyo > Grade::Daikyo);
+        let mut grades = Grade::ALL.to_vec();
+        grades.sort();
+        assert_eq!(grades.first(), Some(&Grade::Daikyo));
+        assert_eq!(grades.last(), Some(&Grade::Daikichi));
+        assert_eq!(Grade::ALL.iter().max(), Some(&Grade::Daikichi));
+    }
+
+    #[test]
+    fn test_grade_display_locales() {
+        assert_eq!(Grade::Daikichi.to_string(), "大吉");
+        assert_eq!(format!("{:#}", Grade::Daikichi), "Great Blessing");
+        assert_eq!(Grade::Suekichi.name(Lang::Ja), "末吉");
+        assert_eq!(Grade::Suekichi.name(Lang::En), "Future Blessing");
+    }
+
+    #[test]
+    fn test_grade_from_str() {
+        assert_eq!("daikichi".parse::<Grade>(), Ok(Grade::Daikichi));
+        assert_eq!("大吉".parse::<Grade>(), Ok(Grade::Daikichi));
+        assert_eq!("Chuukichi".parse::<Grade>(), Ok(Grade::Chukichi));
+        assert_eq!("kyou".parse::<Grade>(), Ok(Grade::Kyo));
+        assert_eq!("DAIKYOU".parse::<Grade>(), Ok(Grade::Daikyo));
+        assert_eq!("great curse".parse::<Grade>(), Ok(Grade::Daikyo));
+        assert!("mega-kichi".parse::<Grade>().is_err());
+        for grade in Grade::ALL {
+            assert_eq!(grade.to_string().parse::<Grade>(), Ok(grade));
+            assert_eq!(grade.romaji().parse::<Grade>(), Ok(grade));
+        }
+    }
+
+    #[test]
+    fn test_grade_serde_roundtrip() {
+        for grade in Grade::ALL {
+            let json = serde_json::to_string(&grade).unwrap();
+            assert_eq!(serde_json::from_str::<Grade>(&json).unwrap(), grade);
+        }
+        assert_eq!(serde_json::from_str::<Grade>("\"大吉\"").unwrap(), Grade::Daikichi);
+        assert_eq!(serde_json::from_str::<Grade>("\"kyo\"").unwrap(), Grade::Kyo);
+    }
+
+    #[test]
+    fn test_luck_type_keys() {
+        assert_eq!(LuckType::Study.key(), "study");
+        assert_eq!(LuckType::WiFi.key(), "wifi");
+        for luck_type in LuckType::ALL {
+            assert_eq!(LuckType::from_key(&luck_type.key()), Some(luck_type));
+            assert_eq!(LuckType::ALL[luck_type.index()], luck_type);
+        }
+        assert_eq!(LuckType::from_key("Study"), Some(LuckType::Study));
+        assert_eq!(LuckType::from_key("money"), None);
+    }
+
     #[test]
     fn test_luck_type_count() {
         assert_eq!(LuckType::ALL.len(), 16);
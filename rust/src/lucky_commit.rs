@@ -0,0 +1,184 @@
+//! `lucky-commit`: rewrite HEAD with a nonce trailer so its commit id starts with a lucky prefix.
+
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+
+const TRAILER: &str = "Lucky-Nonce: ";
+
+/// Longest accepted prefix; every extra hex digit makes mining 16 times slower, and 8
+/// digits already take about 4 billion attempts
+pub const MAX_PREFIX_LEN: usize = 8;
+
+/// Hash function of the repository's object ids
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObjectFormat {
+    Sha1,
+    Sha256,
+}
+
+/// Lowercase hex prefix from user input (`BF`, `0xbf`, `c0ffee`)
+pub fn parse_prefix(text: &str) -> Result<String, String> {
+    let prefix = text.trim().trim_start_matches("0x").to_lowercase();
+    if prefix.is_empty() || prefix.len() > MAX_PREFIX_LEN || !prefix.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!("Invalid prefix '{}': expected 1-{} hex digits", text, MAX_PREFIX_LEN));
+    }
+    Ok(prefix)
+}
+
+/// Attempts mining `prefix` takes on average: 16 per hex digit
+pub fn expected_attempts(prefix: &str) -> u64 {
+    16u64.pow(prefix.len() as u32)
+}
+
+/// Git object id (hex) of a commit object with this content
+pub fn object_id(format: ObjectFormat, content: &[u8]) -> String {
+    let header = format!("commit {}\0", content.len());
+    let digest = match format {
+        ObjectFormat::Sha1 => Sha1::new().chain_update(header).chain_update(content).finalize().to_vec(),
+        ObjectFormat::Sha256 => Sha256::new().chain_update(header).chain_update(content).finalize().to_vec(),
+    };
+    crate::fingerprint::hex(&digest)
+}
+
+/// Commit content with its message ending in a `Lucky-Nonce:` trailer (replacing an earlier one)
+pub fn with_nonce(content: &[u8], nonce: u64) -> Vec<u8> {
+    let mut base = content;
+    if let Some(pos) = find_last(content, format!("\n\n{}", TRAILER).as_bytes()) {
+        if !content[pos + 2..content.len() - 1].contains(&b'\n') {
+            base = &content[..pos + 1];
+        }
+    }
+    let mut out = base.to_vec();
+    if !out.ends_with(b"\n") {
+        out.push(b'\n');
+    }
+    out.extend_from_slice(format!("\n{}{:x}\n", TRAILER, nonce).as_bytes());
+    out
+}
+
+fn find_last(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).rposition(|w| w == needle)
+}
+
+/// Try nonces on `threads` threads until the commit id starts with `prefix`.
+/// Returns the nonce and the new commit id.
+pub fn mine(content: &[u8], prefix: &str, format: ObjectFormat, threads: usize) -> (u64, String) {
+    let threads = threads.max(1) as u64;
+    let content: Arc<[u8]> = content.into();
+    let found = Arc::new(AtomicBool::new(false));
+    let (sender, receiver) = mpsc::channel();
+    for start in 0..threads {
+        let (content, found, sender, prefix) = (content.clone(), found.clone(), sender.clone(), prefix.to_string());
+        thread::spawn(move || {
+            let mut nonce = start;
+            while !found.load(Ordering::Relaxed) {
+                let id = object_id(format, &with_nonce(&content, nonce));
+                if id.starts_with(&prefix) {
+                    found.store(true, Ordering::Relaxed);
+                    let _ = sender.send((nonce, id));
+                    return;
+                }
+                nonce += threads;
+            }
+        });
+    }
+    receiver.recv().expect("mining threads stopped without a result")
+}
+
+fn git(dir: &Path, args: &[&str], stdin: Option<&[u8]>) -> Result<Vec<u8>, String> {
+    let mut child = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run git: {}", e))?;
+    if let Some(input) = stdin {
+        child.stdin.take().unwrap().write_all(input).map_err(|e| format!("Failed to write to git: {}", e))?;
+    }
+    drop(child.stdin.take());
+    let output = child.wait_with_output().map_err(|e| format!("Failed to run git: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("git {} failed: {}", args.join(" "), String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    Ok(output.stdout)
+}
+
+pub fn object_format(dir: &Path) -> Result<ObjectFormat, String> {
+    let output = git(dir, &["rev-parse", "--show-object-format"], None)?;
+    match String::from_utf8_lossy(&output).trim() {
+        "sha256" => Ok(ObjectFormat::Sha256),
+        _ => Ok(ObjectFormat::Sha1),
+    }
+}
+
+/// Id and raw content of the HEAD commit; signed commits are refused (the nonce would void the signature)
+pub fn head_commit(dir: &Path) -> Result<(String, Vec<u8>), String> {
+    let id = String::from_utf8_lossy(&git(dir, &["rev-parse", "HEAD"], None)?).trim().to_string();
+    let content = git(dir, &["cat-file", "commit", &id], None)?;
+    if content.windows(7).any(|w| w == b"\ngpgsig") {
+        return Err("HEAD is signed; a lucky nonce would invalidate the signature".to_string());
+    }
+    Ok((id, content))
+}
+
+/// Store the mined commit and point HEAD at it (only if HEAD is still `old`)
+pub fn amend_head(dir: &Path, content: &[u8], old: &str) -> Result<String, String> {
+    let id = git(dir, &["hash-object", "-t", "commit", "-w", "--stdin"], Some(content))?;
+    let id = String::from_utf8_lossy(&id).trim().to_string();
+    git(dir, &["update-ref", "-m", "hash-omikuji: lucky-commit", "HEAD", &id, old], None)?;
+    Ok(id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COMMIT: &[u8] = b"tree 4b825dc642cb6eb9a060e54bf8d69288fbee4904\n\
+author A <a@example.com> 1767225600 +0900\n\
+committer A <a@example.com> 1767225600 +0900\n\nNew Year commit\n";
+
+    #[test]
+    fn test_parse_prefix() {
+        assert_eq!(parse_prefix("0xBF").unwrap(), "bf");
+        assert_eq!(parse_prefix("c0ffee").unwrap(), "c0ffee");
+        assert!(parse_prefix("").is_err());
+        assert!(parse_prefix("xyz").is_err());
+        assert!(parse_prefix(&"a".repeat(MAX_PREFIX_LEN)).is_ok());
+        assert!(parse_prefix(&"a".repeat(MAX_PREFIX_LEN + 1)).is_err());
+    }
+
+    #[test]
+    fn test_expected_attempts() {
+        assert_eq!(expected_attempts("bf"), 256);
+        assert_eq!(expected_attempts(&"a".repeat(MAX_PREFIX_LEN)), 1 << 32);
+    }
+
+    #[test]
+    fn test_object_id_matches_git() {
+        // `git hash-object -t commit --stdin` of COMMIT
+        assert_eq!(object_id(ObjectFormat::Sha1, COMMIT), "a794f827e02d6c7d70bf1b0ac4d9ffd240a7f0e1");
+        assert_eq!(object_id(ObjectFormat::Sha256, COMMIT).len(), 64);
+    }
+
+    #[test]
+    fn test_with_nonce_replaces_trailer() {
+        let once = with_nonce(COMMIT, 0x1f);
+        assert!(once.ends_with(b"New Year commit\n\nLucky-Nonce: 1f\n"));
+        assert_eq!(with_nonce(&once, 0x2a), with_nonce(COMMIT, 0x2a));
+    }
+
+    #[test]
+    fn test_mine() {
+        let (nonce, id) = mine(COMMIT, "ab", ObjectFormat::Sha1, 4);
+        assert!(id.starts_with("ab"));
+        assert_eq!(object_id(ObjectFormat::Sha1, &with_nonce(COMMIT, nonce)), id);
+    }
+}
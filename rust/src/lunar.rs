@@ -0,0 +1,43 @@
+//! Lunar New Year's Day (the first day of the Chinese calendar, China Standard Time) for
+//! `--calendar lunar`. The dates come from a table rather than an astronomical model;
+//! Korea and Vietnam celebrate on a different day in a few years.
+
+use chrono::NaiveDate;
+
+pub const FIRST_YEAR: i32 = 2000;
+pub const LAST_YEAR: i32 = 2050;
+
+/// (month, day) of Lunar New Year's Day for FIRST_YEAR..=LAST_YEAR
+const NEW_YEAR: [(u32, u32); (LAST_YEAR - FIRST_YEAR + 1) as usize] = [
+    (2, 5), (1, 24), (2, 12), (2, 1), (1, 22), (2, 9), (1, 29), (2, 18), (2, 7), (1, 26), // 2000
+    (2, 14), (2, 3), (1, 23), (2, 10), (1, 31), (2, 19), (2, 8), (1, 28), (2, 16), (2, 5), // 2010
+    (1, 25), (2, 12), (2, 1), (1, 22), (2, 10), (1, 29), (2, 17), (2, 6), (1, 26), (2, 13), // 2020
+    (2, 3), (1, 23), (2, 11), (1, 31), (2, 19), (2, 8), (1, 28), (2, 15), (2, 4), (1, 24), // 2030
+    (2, 12), (2, 1), (1, 22), (2, 10), (1, 30), (2, 17), (2, 6), (1, 26), (2, 14), (2, 2), // 2040
+    (1, 23), // 2050
+];
+
+/// Lunar New Year's Day falling in the Gregorian `year`, or None outside the table
+pub fn new_year(year: i32) -> Option<NaiveDate> {
+    let (month, day) = *NEW_YEAR.get(usize::try_from(year - FIRST_YEAR).ok()?)?;
+    NaiveDate::from_ymd_opt(year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_year() {
+        assert_eq!(new_year(2026), NaiveDate::from_ymd_opt(2026, 2, 17));
+        assert_eq!(new_year(2025), NaiveDate::from_ymd_opt(2025, 1, 29));
+        assert_eq!(new_year(LAST_YEAR), NaiveDate::from_ymd_opt(2050, 1, 23));
+        assert_eq!(new_year(FIRST_YEAR - 1), None);
+        assert_eq!(new_year(LAST_YEAR + 1), None);
+        // always between January 21st and February 20th
+        for year in FIRST_YEAR..=LAST_YEAR {
+            let date = new_year(year).unwrap();
+            assert!((NaiveDate::from_ymd_opt(year, 1, 21)..=NaiveDate::from_ymd_opt(year, 2, 20)).contains(&Some(date)), "{}", date);
+        }
+    }
+}
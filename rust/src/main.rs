@@ -1,42 +1,708 @@
-mod cli;
-mod hash;
-mod luck;
-mod output;
-
 use clap::Parser;
-use cli::Args;
-use hash::HashBits;
-use output::OmikujiResult;
+use chrono::Datelike;
+use hash_omikuji::capsule::Capsule;
+use hash_omikuji::cli::{Args, CacheAction, Calendar, CapsuleAction, Command, HookAction, OmamoriAction, OutputFormat, ScheduleKind};
+use hash_omikuji::config::Config;
+use hash_omikuji::corpus::Corpus;
+use hash_omikuji::effort::{self, EffortParams};
+use hash_omikuji::error::Error;
+use hash_omikuji::hash::HashBits;
+use hash_omikuji::luck::GradeWeights;
+use hash_omikuji::omamori::{Check, Omamori};
+use hash_omikuji::output::{self, Fortune};
+use hash_omikuji::{achievements, advice, batch, cache, capsule, card, chart, conformance, discord, family, feed, forecast, fortunes, gha, history, hook, ics, lucky_commit, melody, motd, mqtt, nengajo, prompt, qr, schedule, schema, share, sign, simulate, sink, slack, speech, streak, team, webhook};
+use hash_omikuji::window::Permit;
+use std::io::IsTerminal;
+use zeroize::Zeroizing;
+
+/// Grade weights: command line, then config file, then the default
+fn grade_weights(args: &Args, config: &Config) -> GradeWeights {
+    args.grade_weights
+        .clone()
+        .or_else(|| config.grade_weights())
+        .unwrap_or_default()
+}
+
+fn run_command(command: &Command, args: &Args) -> Result<(), Error> {
+    match command {
+        Command::Keygen { path } => {
+            let public_hex = sign::keygen(path)?;
+            println!("Secret key : {}", path.display());
+            println!("Public key : {}", sign::public_key_path(path).display());
+            println!("{}", public_hex);
+        }
+        Command::Verify { file, pubkey } => {
+            let expected = pubkey.as_deref().map(sign::load_verifying_key).transpose()?;
+            let text = std::fs::read_to_string(file)
+                .map_err(|e| Error::Io(format!("Failed to read {}: {}", file.display(), e)))?;
+            let document: serde_json::Value =
+                serde_json::from_str(&text).map_err(|e| Error::Format(format!("Invalid JSON in {}: {}", file.display(), e)))?;
+            let verification = sign::verify_document(&document, expected.as_ref()).map_err(Error::Verify)?;
+            if verification.checksum {
+                println!("OK: checksum valid");
+            }
+            if let Some(signer) = verification.signer {
+                println!("OK: signature valid (ed25519 {})", signer);
+            }
+        }
+        Command::Cache { action: CacheAction::Clear } => {
+            let dir = cache::cache_dir().ok_or("Cannot determine the cache location")?;
+            let removed = cache::clear(&dir)?;
+            println!("Removed {} cached fortune{} from {}", removed, if removed == 1 { "" } else { "s" }, dir.display());
+        }
+        Command::Schedule { kind } => {
+            if args.calendar != Calendar::Gregorian {
+                return Err("schedule only supports the Gregorian calendar".into());
+            }
+            let config = Config::load(args.config.as_deref())?;
+            let url = args
+                .post_url
+                .clone()
+                .or(config.webhook.url)
+                .ok_or("schedule needs a webhook: pass --post-url or set webhook.url in the config")?;
+            let exe = std::env::current_exe().map_err(|e| format!("Cannot locate the hash-omikuji binary: {}", e))?;
+            let tz = args.tz.map(|tz| tz.name().to_string()).or(args.utc.then(|| "UTC".to_string()));
+            let mut job_args = Vec::new();
+            if let Some(ref tz) = tz {
+                job_args.extend(["--tz".to_string(), tz.clone()]);
+            }
+            if let Some(ref path) = args.config {
+                job_args.extend(["--config".to_string(), path.display().to_string()]);
+            }
+            // Without --seed the job draws for user@host of the account it runs as
+            if let Some(ref seed) = args.seed {
+                job_args.extend(["--seed".to_string(), seed.clone()]);
+            }
+            job_args.extend(["--post-url".to_string(), url]);
+            let job = schedule::Job { exe: exe.display().to_string(), args: job_args, tz };
+            let document = match kind {
+                ScheduleKind::Cron => schedule::crontab(&job),
+                ScheduleKind::Systemd => schedule::systemd(&job),
+            };
+            sink::emit(args.output.as_deref(), &document).map_err(Error::Io)?;
+        }
+        Command::Achievements => {
+            let path = args.history_path().ok_or("Cannot determine the history location; use --history")?;
+            let entries = history::load(&path)?;
+            if let Some(streak) = streak::current(&entries, args.today().year() as u32) {
+                println!("{}\n", streak.message(args.lang));
+            }
+            print!("{}", achievements::format_achievements(&achievements::evaluate(&entries), args.lang));
+        }
+        Command::Simulate { samples, jobs } => {
+            let config = Config::load(args.config.as_deref())?;
+            let weights = grade_weights(args, &config);
+            let pool = batch::pool(jobs.map(|n| n.max(1)));
+            let started = std::time::Instant::now();
+            let counts = match pool {
+                Some(ref pool) => simulate::grade_counts_parallel(args.get_year(), *samples, &weights, pool),
+                None => simulate::grade_counts(args.get_year(), *samples, &weights),
+            };
+            let threads = pool.as_ref().map_or(1, |pool| pool.current_num_threads());
+            print!("{}", simulate::format_report(&counts, &weights));
+            println!("\n{}", batch::rate(*samples, started.elapsed(), "hashes", threads));
+        }
+        Command::Capsule { action: None, file } => {
+            let year = args.get_year() + 1;
+            capsule::check_year(year)?;
+            let path = file.clone().or_else(|| capsule::default_path(year)).ok_or("Cannot determine the capsule location; use --file")?;
+            let capsule = match Capsule::load(&path)? {
+                // Resealing would change the nonce and break a published commitment
+                Some(capsule) if capsule.year == year => {
+                    println!("The {} capsule was sealed on {} ({})", year, capsule.sealed_on, path.display());
+                    capsule
+                }
+                Some(capsule) => return Err(Error::Format(format!("{} holds the {} capsule", path.display(), capsule.year))),
+                None => {
+                    let seed = Zeroizing::new(args.get_combined_seed());
+                    let capsule = Capsule::seal(&seed, year, args.layout, args.algo, args.today())?;
+                    capsule.store(&path)?;
+                    println!("Sealed the {} fortune in {}", year, path.display());
+                    capsule
+                }
+            };
+            println!("Commitment : sha256:{}", capsule.commitment);
+            println!("Open it in {}, on {}, with `hash-omikuji capsule open`", year, args.calendar.period_name());
+        }
+        Command::Capsule { action: Some(CapsuleAction::Open), file } => {
+            let config = Config::load(args.config.as_deref())?;
+            args.can_execute(config.birthday, &config.windows)?;
+            let year = args.get_year();
+            let path = file.clone().or_else(|| capsule::default_path(year)).ok_or("Cannot determine the capsule location; use --file")?;
+            let capsule = Capsule::load(&path)?.ok_or_else(|| Error::Io(format!("No capsule at {}", path.display())))?;
+            if capsule.year != year {
+                return Err(Error::Format(format!("{} holds the {} capsule, not {}", path.display(), capsule.year, year)));
+            }
+            let seed = Zeroizing::new(args.get_combined_seed());
+            let fortune = capsule.open(&seed)?;
+            println!("{}", hook::fortune_line(&fortune));
+            println!("Commitment : sha256:{} (sealed {}) verified", capsule.commitment, capsule.sealed_on);
+            println!("Nonce      : {}", capsule.nonce);
+        }
+        Command::Forecast { years } => {
+            let config = Config::load(args.config.as_deref())?;
+            let year = args.get_year();
+            let seed = Zeroizing::new(args.get_combined_seed());
+            let builder = Fortune::builder()
+                .seed(&seed)
+                .algo(args.algo)
+                .layout(args.layout)
+                .lang(args.lang)
+                .grade_weights(grade_weights(args, &config))
+                .category_weights(args.category_weights.clone().unwrap_or_else(|| config.category_weights()));
+            let years = years.clone().unwrap_or_else(|| forecast::default_years(year));
+            sink::emit(args.output.as_deref(), &forecast::format_table(&forecast::draw(&builder, years))).map_err(Error::Io)?;
+        }
+        Command::Family { members } => {
+            let config = Config::load(args.config.as_deref())?;
+            args.can_execute(config.birthday, &config.windows)?;
+            let members = family::select(&config.family, members)?;
+            let year = args.get_year();
+            let builder = Fortune::builder()
+                .year(year)
+                .algo(args.algo)
+                .layout(args.layout)
+                .lang(args.lang)
+                .grade_weights(grade_weights(args, &config))
+                .category_weights(args.category_weights.clone().unwrap_or_else(|| config.category_weights()));
+            sink::emit(args.output.as_deref(), &family::report(year, &family::draw(&builder, &members))).map_err(Error::Io)?;
+        }
+        Command::Prompt { no_color } => {
+            let (year, date) = (args.get_year(), args.draw_date());
+            let seed = Zeroizing::new(args.get_combined_seed());
+            let weights = args.grade_weights.clone().unwrap_or_default();
+            let key = prompt::cache_key(&seed, year, args.algo, &weights);
+            let cache = prompt::cache_path();
+            let segment = match cache.as_deref().and_then(|path| prompt::load_cached(path, date, &key)) {
+                Some(segment) => segment,
+                None => {
+                    let hash = HashBits::from_seeds_with(year, &[&seed], args.algo);
+                    let segment = prompt::Segment::daily(&hash, date, &weights);
+                    if let Some(ref path) = cache {
+                        prompt::store(path, date, &key, &segment);
+                    }
+                    segment
+                }
+            };
+            println!("{}", segment.render(!no_color && std::env::var_os("NO_COLOR").is_none()));
+        }
+        Command::Motd { no_color } => {
+            // Runs unattended at login: no January 1st restriction, no history, no progress output
+            let config = Config::load(args.config.as_deref())?;
+            let (year, date) = (args.get_year(), args.draw_date());
+            let seed = Zeroizing::new(args.get_combined_seed());
+            let weights = grade_weights(args, &config);
+            let fortune = Fortune::builder()
+                .year(year)
+                .seed(&seed)
+                .algo(args.algo)
+                .layout(args.layout)
+                .lang(args.lang)
+                .grade_weights(weights.clone())
+                .art(motd::art_options())
+                .draw();
+            let color = !no_color && std::env::var_os("NO_COLOR").is_none();
+            if args.is_new_year() {
+                print!("{}", motd::new_year(&fortune, color));
+            } else {
+                let hash = HashBits::from_seeds_with(year, &[&seed], args.algo);
+                print!("{}", motd::daily(&prompt::Segment::daily(&hash, date, &weights), &fortune, date, color));
+            }
+        }
+        Command::Feed { input, output } => {
+            let config = Config::load(args.config.as_deref())?;
+            let year = args.get_year();
+            let builder = Fortune::builder()
+                .year(year)
+                .algo(args.algo)
+                .layout(args.layout)
+                .lang(args.lang)
+                .grade_weights(grade_weights(args, &config))
+                .category_weights(args.category_weights.clone().unwrap_or_else(|| config.category_weights()));
+            let members: Vec<_> = team::read_seeds(input)?
+                .into_iter()
+                .map(|name| {
+                    let fortune = builder.clone().seed(&name).draw();
+                    (name, fortune)
+                })
+                .collect();
+            let xml = feed::atom(year, &members, chrono::Utc::now());
+            sink::emit(output.as_deref().or(args.output.as_deref()), &xml).map_err(Error::Io)?;
+        }
+        Command::Fortunes { input, output } => {
+            let config = Config::load(args.config.as_deref())?;
+            let builder = Fortune::builder()
+                .year(args.get_year())
+                .algo(args.algo)
+                .layout(args.layout)
+                .lang(args.lang)
+                .grade_weights(grade_weights(args, &config));
+            let cookies: Vec<_> =
+                team::read_seeds(input)?.iter().map(|seed| fortunes::cookie(&builder.clone().seed(seed).draw())).collect();
+            let text = fortunes::file(&cookies);
+            let mut dat = output.clone().into_os_string();
+            dat.push(".dat");
+            let dat = std::path::PathBuf::from(dat);
+            sink::write(output, &text).map_err(Error::Io)?;
+            sink::write(&dat, fortunes::strfile(&text)).map_err(Error::Io)?;
+        }
+        Command::Batch { input, jobs } => {
+            let config = Config::load(args.config.as_deref())?;
+            let builder = Fortune::builder()
+                .year(args.get_year())
+                .algo(args.algo)
+                .layout(args.layout)
+                .lang(args.lang)
+                .grade_weights(grade_weights(args, &config))
+                .category_weights(args.category_weights.clone().unwrap_or_else(|| config.category_weights()))
+                .art(args.art_options())
+                .fingerprint_format(args.fingerprint_format);
+            let pool = batch::pool(jobs.map(|n| n.max(1)));
+            let started = std::time::Instant::now();
+            let mut out = sink::Sink::open(args.output.as_deref()).map_err(Error::Io)?;
+            let count = match input {
+                Some(path) => batch::run_file(&builder, path, pool.as_ref(), &mut out)?,
+                None => batch::run(&builder, std::io::stdin().lock(), pool.as_ref(), &mut out)?,
+            };
+            out.finish().map_err(Error::Io)?;
+            if std::io::stderr().is_terminal() {
+                let threads = pool.as_ref().map_or(1, |pool| pool.current_num_threads());
+                eprintln!("{}", batch::rate(count, started.elapsed(), "fortunes", threads));
+            }
+        }
+        Command::Ics { output, daily } => {
+            let config = Config::load(args.config.as_deref())?;
+            let fortune = Fortune::builder()
+                .year(args.get_year())
+                .seed(&args.get_combined_seed())
+                .algo(args.algo)
+                .layout(args.layout)
+                .grade_weights(grade_weights(args, &config))
+                .draw();
+            let calendar = ics::calendar(&fortune, *daily, chrono::Utc::now()).map_err(Error::Format)?;
+            sink::emit(output.as_deref().or(args.output.as_deref()), &calendar).map_err(Error::Io)?;
+        }
+        Command::Card { og } => {
+            let config = Config::load(args.config.as_deref())?;
+            let seed = Zeroizing::new(args.get_combined_seed());
+            let fortune = Fortune::builder()
+                .year(args.get_year())
+                .seed(&seed)
+                .algo(args.algo)
+                .layout(args.layout)
+                .grade_weights(grade_weights(args, &config))
+                .art(args.art_options())
+                .draw();
+            let theme = args.theme.or(config.theme).unwrap_or_default();
+            let bytes = card::render_og(&fortune, &seed, &theme.palette())?;
+            sink::write(og, bytes).map_err(Error::Io)?;
+        }
+        Command::Nengajo { output } => {
+            let config = Config::load(args.config.as_deref())?;
+            let fortune = Fortune::builder()
+                .year(args.get_year())
+                .seed(&args.get_combined_seed())
+                .algo(args.algo)
+                .layout(args.layout)
+                .grade_weights(grade_weights(args, &config))
+                .art(args.art_options())
+                .draw();
+            let palette = args.theme.or(config.theme).unwrap_or_default().palette();
+            match output.as_deref().or(args.output.as_deref()) {
+                Some(path) if path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("pdf")) => {
+                    sink::write(path, nengajo::pdf(&fortune, &palette, args.kanji_numerals)).map_err(Error::Io)?
+                }
+                path => sink::emit(path, &nengajo::svg(&fortune, &palette, args.kanji_numerals)).map_err(Error::Io)?,
+            }
+        }
+        Command::Schema => {
+            let schema = schema::fortune_schema(args.layout);
+            let json = serde_json::to_string_pretty(&schema).map_err(|e| e.to_string())?;
+            sink::emit(args.output.as_deref(), &format!("{}\n", json)).map_err(Error::Io)?;
+        }
+        Command::Publish { mqtt, topic, input } => {
+            let broker = mqtt::Broker::parse(mqtt)?;
+            let config = Config::load(args.config.as_deref())?;
+            let builder = Fortune::builder()
+                .year(args.get_year())
+                .algo(args.algo)
+                .layout(args.layout)
+                .lang(args.lang)
+                .grade_weights(grade_weights(args, &config))
+                .category_weights(args.category_weights.clone().unwrap_or_else(|| config.category_weights()))
+                .art(args.art_options());
+            let messages: Vec<_> = match input {
+                Some(path) => team::read_seeds(path)?
+                    .iter()
+                    .map(|seed| (format!("{}/{}", topic, output::seed_fingerprint(seed)), builder.clone().seed(seed).draw().format_json()))
+                    .collect(),
+                None => vec![(topic.clone(), builder.seed(&args.get_combined_seed()).draw().format_json())],
+            };
+            mqtt::publish(&broker, &messages).map_err(Error::Network)?;
+            eprintln!("Published {} message(s) to {}", messages.len(), mqtt::redact(mqtt));
+        }
+        Command::LuckyCommit { prefix, threads } => {
+            let prefix = match prefix {
+                Some(prefix) => lucky_commit::parse_prefix(prefix)?,
+                None => {
+                    let fortune = Fortune::builder().year(args.get_year()).seed(&args.get_combined_seed()).draw();
+                    lucky_commit::parse_prefix(&fortune.lucky_hex)?
+                }
+            };
+            let threads = threads.unwrap_or_else(|| std::thread::available_parallelism().map_or(1, |n| n.get()));
+            let cwd = std::env::current_dir().map_err(|e| e.to_string())?;
+            let format = lucky_commit::object_format(&cwd)?;
+            let (old, content) = lucky_commit::head_commit(&cwd)?;
+            eprintln!(
+                "Mining for a commit id starting with {} (about {} attempts) on {} threads...",
+                prefix,
+                lucky_commit::expected_attempts(&prefix),
+                threads
+            );
+            let (nonce, _) = lucky_commit::mine(&content, &prefix, format, threads);
+            let id = lucky_commit::amend_head(&cwd, &lucky_commit::with_nonce(&content, nonce), &old)?;
+            println!("HEAD is now {} (nonce {:x})", id, nonce);
+        }
+        #[cfg(feature = "serve")]
+        Command::Serve {
+            bind,
+            seeds,
+            tenants,
+            workers,
+            max_requests,
+            timeout,
+            #[cfg(feature = "grpc")]
+            grpc,
+        } => {
+            let config = Config::load(args.config.as_deref())?;
+            let seeds = match seeds {
+                Some(path) => team::read_seeds(path)?,
+                None => vec![args.get_combined_seed()],
+            };
+            let mut builder = Fortune::builder()
+                .algo(args.algo)
+                .layout(args.layout)
+                .lang(args.lang)
+                .grade_weights(grade_weights(args, &config))
+                .category_weights(args.category_weights.clone().unwrap_or_else(|| config.category_weights()))
+                .art(args.art_options())
+                .fingerprint_format(args.fingerprint_format);
+            if let Some(year) = args.force_year {
+                builder = builder.year(year);
+            }
+            let runtime = tokio::runtime::Runtime::new().map_err(|e| format!("Failed to start the runtime: {}", e))?;
+            let mut limits = hash_omikuji::serve::Limits {
+                concurrency: *max_requests,
+                timeout: std::time::Duration::from_secs(*timeout),
+                ..Default::default()
+            };
+            if let Some(workers) = workers {
+                limits.workers = *workers as usize;
+            }
+            let mut state = hash_omikuji::serve::AppState::new(builder, seeds).with_limits(limits);
+            if let Some(path) = tenants {
+                let tenants = hash_omikuji::tenants::Tenants::load(path)?;
+                eprintln!("Serving {} tenants from {}", tenants.len(), path.display());
+                state = state.with_tenants(tenants);
+            }
+            #[cfg(feature = "grpc")]
+            if *grpc {
+                eprintln!("gRPC listening on {}", bind);
+                return runtime.block_on(hash_omikuji::grpc::run(bind, state)).map_err(Error::from);
+            }
+            let listener = runtime.block_on(hash_omikuji::serve::listen(bind))?;
+            eprintln!("Listening on {}", listener);
+            runtime.block_on(hash_omikuji::serve::run(listener, state))?;
+        }
+        #[cfg(feature = "bot")]
+        Command::Bot { matrix, room, state } => {
+            let token = Zeroizing::new(
+                std::env::var(hash_omikuji::bot::TOKEN_ENV).map_err(|_| format!("Set {} to the bot's access token", hash_omikuji::bot::TOKEN_ENV))?,
+            );
+            let config = Config::load(args.config.as_deref())?;
+            let builder = Fortune::builder()
+                .algo(args.algo)
+                .layout(args.layout)
+                .lang(args.lang)
+                .grade_weights(grade_weights(args, &config))
+                .category_weights(args.category_weights.clone().unwrap_or_else(|| config.category_weights()));
+            let path = state.clone().or_else(hash_omikuji::bot::DrawLog::default_path).ok_or("Cannot determine the state location; use --state")?;
+            let mut log = hash_omikuji::bot::DrawLog::open(&path)?;
+            eprintln!("Joining {} on {}", room, matrix);
+            hash_omikuji::bot::run(matrix, room, &token, &builder, args.force_year, &mut log)?;
+        }
+        #[cfg(feature = "tui")]
+        Command::Tui => {
+            let config = Config::load(args.config.as_deref())?;
+            args.can_execute(config.birthday, &config.windows)?;
+            let seed = Zeroizing::new(args.get_combined_seed());
+            let fortune = Fortune::builder()
+                .year(args.get_year())
+                .seed(&seed)
+                .algo(args.algo)
+                .layout(args.layout)
+                .lang(args.lang)
+                .grade_weights(grade_weights(args, &config))
+                .category_weights(args.category_weights.clone().unwrap_or_else(|| config.category_weights()))
+                .art(args.art_options())
+                .draw();
+            let entries = match args.history_path() {
+                Some(path) => history::load(&path)?,
+                None => Vec::new(),
+            };
+            hash_omikuji::tui::run(&fortune, &entries, args.ascii)?;
+        }
+        Command::Conformance { spec } => {
+            let (report, failed) = conformance::report(&conformance::Spec::load(spec)?);
+            print!("{}", report);
+            if failed > 0 {
+                return Err(Error::Verify(format!("{} fields do not conform", failed)));
+            }
+        }
+        Command::Omamori { action: OmamoriAction::Decode { token } } => {
+            let omamori = Omamori::decode(token).map_err(Error::Format)?;
+            print!("{}", omamori.describe());
+            let config = Config::load(args.config.as_deref())?;
+            let builder = Fortune::builder().grade_weights(grade_weights(args, &config));
+            let seed = Zeroizing::new(args.get_combined_seed());
+            match omamori.check(&seed, &builder) {
+                Check::Verified => println!("OK: MAC and grade verified with the seed"),
+                Check::OtherSeed => println!("Unverified: drawn from another seed (pass it with --seed to check)"),
+                Check::BadMac => return Err(Error::Verify("Token does not verify: altered, or not minted with this seed".to_string())),
+                Check::GradeMismatch(grade) => {
+                    return Err(Error::Verify(format!(
+                        "Token does not verify: the seed draws {} ({:#}) with these grade weights",
+                        grade, grade
+                    )))
+                }
+            }
+        }
+        Command::Hook { action } => {
+            let cwd = std::env::current_dir().map_err(|e| Error::Io(e.to_string()))?;
+            match action {
+                HookAction::Install => println!("Installed {}", hook::install(&hook::hooks_dir(&cwd)?)?.display()),
+                HookAction::Uninstall => println!("Removed {}", hook::uninstall(&hook::hooks_dir(&cwd)?)?.display()),
+                HookAction::Run { file, source } => {
+                    if !args.is_new_year() || !hook::applies_to(source.as_deref()) {
+                        return Ok(());
+                    }
+                    let fortune = Fortune::builder().year(args.get_year()).seed(&args.get_combined_seed()).draw();
+                    let message = std::fs::read_to_string(file)
+                        .map_err(|e| Error::Io(format!("Failed to read {}: {}", file.display(), e)))?;
+                    std::fs::write(file, hook::add_line(&message, &hook::fortune_line(&fortune)))
+                        .map_err(|e| Error::Io(format!("Failed to write {}: {}", file.display(), e)))?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
 
 fn main() {
+    hash_omikuji::console::init();
     let args = Args::parse();
+    // Services reading --json output get failures as a JSON document on stdout too
+    let json_errors = args.format() == OutputFormat::Json;
+    let result = match args.command {
+        Some(ref command) => run_command(command, &args),
+        None => draw(args),
+    };
+    if let Err(error) = result {
+        if json_errors {
+            println!("{}", error.to_json());
+        } else {
+            eprintln!("{}", error);
+        }
+        std::process::exit(error.exit_code());
+    }
+}
+
+/// The year's draw, the command without a subcommand
+fn draw(mut args: Args) -> Result<(), Error> {
+    let config = Config::load(args.config.as_deref())?;
+    let poster = args.poster()?;
 
     // Check if we can execute
-    let show_warning = match args.can_execute() {
-        Ok(warning) => warning,
-        Err(msg) => {
-            eprintln!("{}", msg);
-            std::process::exit(1);
+    let permit = args.can_execute(config.birthday, &config.windows)?;
+
+    let year = args.get_year();
+    let birthday = args.birthday_draw(config.birthday);
+    // The birthday draw is salted, so it never repeats the New Year fortune
+    let salt = birthday.map(|birthday| birthday.domain());
+
+    if args.format() == OutputFormat::Text {
+        match permit {
+            Permit::Forced => eprintln!("WARNING: Running outside {} with --force-year {}.\n", args.calendar.period_name(), year),
+            Permit::Warned(ref warning) => eprintln!("WARNING: {}\n", warning),
+            Permit::Open => {}
+        }
+    }
+    if birthday.is_some() && args.format() == OutputFormat::Text {
+        eprintln!("Happy birthday! Drawing your birthday fortune.\n");
+    }
+
+    // Get seed (default: username@hostname), combined with any --with seeds
+    let seed = Zeroizing::new(args.get_combined_seed());
+    args.zeroize_seeds();
+
+    // Reuse a cached draw, or optionally stretch the seed through Argon2id ("shaking the box")
+    let effort = args.effort.map(EffortParams::for_level);
+    let cache_dir = if args.no_cache { None } else { cache::cache_dir() };
+    let cache_key = cache::key(&seed, year, args.layout, args.algo, effort.as_ref(), salt.as_deref());
+    let cached = cache_dir.as_deref().and_then(|dir| cache::load(dir, &cache_key, &seed, year, args.layout, args.algo));
+    let hash = match cached {
+        Some(hash) => hash,
+        None => {
+            let derivation_seed = match effort {
+                Some(ref params) => {
+                    let show_progress = std::io::stderr().is_terminal();
+                    let stretched = effort::stretch_seed(&seed, year, params, |done, total| {
+                        if show_progress {
+                            eprint!("\r{}", effort::progress_line(done, total));
+                        }
+                    });
+                    if show_progress {
+                        eprintln!();
+                    }
+                    Zeroizing::new(stretched)
+                }
+                None => seed.clone(),
+            };
+            let hash = HashBits::from_seeds_salted(year, &[&derivation_seed], args.algo, salt.as_deref());
+            if let Some(ref dir) = cache_dir {
+                cache::store(dir, &cache_key, &cache::canonical(&hash, year, &seed, args.layout, args.algo, effort.as_ref()));
+            }
+            hash
         }
     };
 
-    let year = args.get_year();
+    // Generate the result
+    let grade_weights = grade_weights(&args, &config);
+    let category_weights = args.category_weights.clone().unwrap_or_else(|| config.category_weights());
+    let mut result = Fortune::from_hash(&hash, year, &seed)
+        .with_grade_weights(&hash, &grade_weights)
+        .with_category_weights(&category_weights)
+        .with_category_labels(&config.category_labels())
+        .with_lang(&hash, args.lang)
+        .with_art(&hash, &args.art_options())
+        .with_algo(args.algo)
+        .with_layout(&hash, args.layout)
+        .with_fingerprint_format(&hash, args.fingerprint_format);
+    if let Some(dir) = args.corpus_dir() {
+        let corpus = Corpus::load_dir(&dir).map_err(Error::Format)?;
+        if !corpus.is_empty() {
+            result = result.with_corpus(&hash, &corpus);
+        }
+    }
+    if let Some(ref path) = config.advice_grammar {
+        result = result.with_advice_grammar(&hash, &advice::Grammar::load(path).map_err(Error::Format)?);
+    }
+    if let Some(params) = effort {
+        result = result.with_effort(params);
+    }
+    if args.anonymize {
+        result = result.anonymized();
+    }
 
-    if show_warning && !args.json {
-        eprintln!("WARNING: Running outside January 1st with --force-year {}.\n", year);
+    if let Some(ref keyfile) = args.sign {
+        result = result.with_signature(&sign::load_signing_key(keyfile)?);
     }
 
-    // Get seed (default: username@hostname)
-    let seed = args.get_seed();
+    // History keeps one draw per year; the birthday fortune is not it
+    if !args.no_history && birthday.is_none() {
+        if let Some(path) = args.history_path() {
+            let entry = history::HistoryEntry::from_result(&result, args.draw_date(), args.is_preview(&permit));
+            match history::append(&path, &entry) {
+                Err(e) => eprintln!("WARNING: {}", e),
+                Ok(()) if args.format() == OutputFormat::Text => {
+                    let this_year = args.draw_date().year() as u32;
+                    if let Some(streak) = history::load(&path).ok().and_then(|entries| streak::current(&entries, this_year)) {
+                        if args.is_preview(&permit) {
+                            eprintln!("NOTE: --force-year and --date previews do not count towards your streak.");
+                        }
+                        eprintln!("{}\n", streak.message(args.lang));
+                    }
+                }
+                Ok(()) => {}
+            }
+        }
+    }
 
-    // Generate hash and result
-    let hash = HashBits::from_seed(year, &seed);
-    let result = OmikujiResult::from_hash(&hash, year, &seed);
+    let theme = args.theme.or(config.theme).unwrap_or_default();
+    if let Some(ref path) = args.chart_svg {
+        let title = format!("Hash-Omikuji {} {}", year, result.grade);
+        let svg = chart::radar_svg(&title, &result.luck_scores, &theme.palette());
+        sink::write(path, svg).map_err(Error::Io)?;
+    }
+
+    if let Some(ref path) = args.png {
+        sink::write(path, card::render_png(&result, &theme.palette())?).map_err(Error::Io)?;
+    }
+
+    if let Some((width, height, path)) = poster {
+        sink::write(&path, card::render_poster(&result, &seed, &theme.palette(), width, height)?).map_err(Error::Io)?;
+    }
+
+    let melody = melody::Melody::from_fortune(&result);
+    if let Some(ref path) = args.midi {
+        sink::write(path, melody.to_midi()).map_err(Error::Io)?;
+    }
+
+    let code = if args.qr || args.qr_png.is_some() {
+        Some(qr::encode(&qr::payload(&result, args.qr_content))?)
+    } else {
+        None
+    };
+    if let (Some(ref path), Some(ref code)) = (&args.qr_png, &code) {
+        sink::write(path, qr::render_png(code)?).map_err(Error::Io)?;
+    }
 
     // Output
-    if args.json {
-        println!("{}", result.format_json());
+    if let Some(ref url) = args.post {
+        let payload = discord::payload(&result).to_string();
+        return webhook::post(url, "application/json", &payload, &Default::default()).map_err(Error::Network);
+    }
+    if let Some(ref url) = args.post_url {
+        let (body, content_type) = args.post_body(&result)?;
+        return webhook::post(url, content_type, &body, &config.webhook.headers).map_err(Error::Network);
+    }
+    let document = if let (true, Some(code)) = (args.qr, &code) {
+        qr::render_terminal(code)
+    } else if args.share_url {
+        format!("{}\n", share::url(config.share_base.as_deref().unwrap_or(share::DEFAULT_BASE), &result))
     } else {
-        print!("{}", result.format_text(args.short, args.show_seed));
+        match args.format() {
+            OutputFormat::Json => format!("{}\n", result.format_json()),
+            OutputFormat::Slack => format!("{}\n", serde_json::to_string_pretty(&slack::payload(&result)).unwrap()),
+            OutputFormat::Discord => format!("{}\n", serde_json::to_string_pretty(&discord::payload(&result)).unwrap()),
+            OutputFormat::Markdown => result.format_markdown(),
+            OutputFormat::Fortune => fortunes::cookie(&result),
+            OutputFormat::Speech => speech::paragraph(&result),
+            OutputFormat::Ssml => speech::ssml(&result),
+            OutputFormat::Omamori => format!("{}\n", Omamori::new(&result, &seed).encode()),
+            OutputFormat::Gha => return gha::write(&result).map_err(Error::Io),
+            OutputFormat::Text => {
+                let options = output::TextOptions {
+                    short: args.short,
+                    show_seed: args.show_seed,
+                    chart: args.chart,
+                    ascii: args.ascii,
+                    color: args.color_enabled(),
+                    theme,
+                    graphics: args.graphics_protocol(),
+                    wide: args.wide,
+                    legend: args.legend,
+                    banner: !args.no_banner && args.output.is_none() && std::io::stdout().is_terminal(),
+                    kanji_numerals: args.kanji_numerals,
+                };
+                result.format_text(&options)
+            }
+        }
+    };
+    sink::emit(args.output.as_deref(), &document).map_err(Error::Io)?;
+    if args.bell {
+        eprintln!("{}", melody.describe());
+        melody.ring(&mut std::io::stderr()).map_err(|e| Error::Io(e.to_string()))?;
     }
+    Ok(())
 }
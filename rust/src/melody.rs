@@ -0,0 +1,189 @@
+//! A short melody derived from the fortune, written as a Standard MIDI File by `--midi`
+//! and tapped out on the terminal bell by `--bell`.
+//!
+//! The lucky bits walk a major pentatonic scale, one step up for a 1 bit and one down
+//! for a 0, like the art walk; the lucky hex picks the key (the lucky note) and the
+//! lucky number the tempo (the lucky BPM). The melody ends held on the tonic.
+
+use crate::output::Fortune;
+use std::io::Write;
+use std::time::Duration;
+
+/// Note names of the keys, C first
+pub const NOTE_NAMES: [&str; 12] = ["C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B"];
+
+/// Semitones of the major pentatonic scale above the tonic
+const PENTATONIC: [u8; 5] = [0, 2, 4, 7, 9];
+
+/// Scale steps the walk may stray from the tonic either way
+const RANGE: i32 = 7;
+
+/// MIDI ticks per quarter note
+const DIVISION: u16 = 480;
+
+/// General MIDI program 108 (0-based 107): koto
+const PROGRAM: u8 = 107;
+
+/// Middle C, the octave the melody starts in
+const MIDDLE_C: i32 = 60;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Melody {
+    /// Key, 0 for C up to 11 for B
+    pub key: u8,
+    /// Quarter notes per minute, 72-168
+    pub bpm: u16,
+    /// MIDI note numbers and lengths in quarter notes
+    pub notes: Vec<(u8, u8)>,
+}
+
+impl Melody {
+    pub fn new(lucky_bits: u16, lucky_hex: u8, lucky_number: u8) -> Self {
+        let key = lucky_hex % 12;
+        let bpm = 72 + lucky_number as u16 % 97;
+        let mut step = 0i32;
+        let mut notes = vec![(pitch(key, step), 1)];
+        for i in (0..16).rev() {
+            step += if lucky_bits >> i & 1 == 1 { 1 } else { -1 };
+            // Bounce off the edges of the range instead of running away
+            if step.abs() > RANGE {
+                step -= 2 * step.signum();
+            }
+            notes.push((pitch(key, step), 1));
+        }
+        notes.push((pitch(key, 0), 4));
+        Self { key, bpm, notes }
+    }
+
+    /// The melody of a drawn fortune, from its rendered lucky fields
+    pub fn from_fortune(fortune: &Fortune) -> Self {
+        let bits = u16::from_str_radix(&fortune.lucky_bits.replace(' ', ""), 2).unwrap_or(0);
+        let hex = u8::from_str_radix(fortune.lucky_hex.trim_start_matches("0x"), 16).unwrap_or(0);
+        Self::new(bits, hex, fortune.lucky_number)
+    }
+
+    /// `D major pentatonic, 132 BPM`
+    pub fn describe(&self) -> String {
+        format!("{} major pentatonic, {} BPM", NOTE_NAMES[self.key as usize], self.bpm)
+    }
+
+    /// Standard MIDI File, format 0: tempo, a koto program and the notes on channel 1
+    pub fn to_midi(&self) -> Vec<u8> {
+        let mut track = Vec::new();
+        let tempo = 60_000_000 / self.bpm as u32;
+        track.extend([0x00, 0xFF, 0x51, 0x03]);
+        track.extend(&tempo.to_be_bytes()[1..]);
+        track.extend([0x00, 0xFF, 0x58, 0x04, 0x04, 0x02, 0x18, 0x08]);
+        track.extend([0x00, 0xC0, PROGRAM]);
+        for &(note, quarters) in &self.notes {
+            track.extend([0x00, 0x90, note, 0x60]);
+            push_vlq(&mut track, quarters as u32 * DIVISION as u32);
+            track.extend([0x80, note, 0x00]);
+        }
+        track.extend([0x00, 0xFF, 0x2F, 0x00]);
+
+        let mut file = Vec::with_capacity(track.len() + 22);
+        file.extend(b"MThd");
+        file.extend(6u32.to_be_bytes());
+        file.extend(0u16.to_be_bytes());
+        file.extend(1u16.to_be_bytes());
+        file.extend(DIVISION.to_be_bytes());
+        file.extend(b"MTrk");
+        file.extend((track.len() as u32).to_be_bytes());
+        file.extend(track);
+        file
+    }
+
+    /// Ring the terminal bell once per note at the melody's tempo, printing the note names.
+    /// The bell has no pitch, so this carries the rhythm only.
+    pub fn ring(&self, out: &mut impl Write) -> std::io::Result<()> {
+        let beat = Duration::from_secs(60) / self.bpm as u32;
+        for &(note, quarters) in &self.notes {
+            write!(out, "\x07{} ", name(note))?;
+            out.flush()?;
+            std::thread::sleep(beat * quarters as u32);
+        }
+        writeln!(out)
+    }
+}
+
+/// MIDI note of the `step`th pentatonic degree from the tonic of `key` above middle C
+fn pitch(key: u8, step: i32) -> u8 {
+    let degrees = PENTATONIC.len() as i32;
+    let (octave, degree) = (step.div_euclid(degrees), step.rem_euclid(degrees));
+    (MIDDLE_C + key as i32 + octave * 12 + PENTATONIC[degree as usize] as i32) as u8
+}
+
+/// `C4` for middle C
+fn name(note: u8) -> String {
+    format!("{}{}", NOTE_NAMES[note as usize % 12], note as i32 / 12 - 1)
+}
+
+/// MIDI variable-length quantity: 7 bits per byte, most significant first
+fn push_vlq(out: &mut Vec<u8>, value: u32) {
+    let mut bytes = vec![(value & 0x7F) as u8];
+    let mut rest = value >> 7;
+    while rest > 0 {
+        bytes.push((rest & 0x7F) as u8 | 0x80);
+        rest >>= 7;
+    }
+    out.extend(bytes.iter().rev());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::HashBits;
+
+    #[test]
+    fn test_walk_and_tempo() {
+        let melody = Melody::new(0xFFFF, 0x0E, 60);
+        assert_eq!((melody.key, melody.bpm), (2, 132));
+        assert_eq!(melody.describe(), "D major pentatonic, 132 BPM");
+        assert_eq!(melody.notes.len(), 18);
+        // D E F# A B, then on into the next octave
+        let first: Vec<u8> = melody.notes[..6].iter().map(|&(note, _)| note).collect();
+        assert_eq!(first, [62, 64, 66, 69, 71, 74]);
+        assert!(melody.notes.iter().all(|&(note, _)| (pitch(2, -RANGE)..=pitch(2, RANGE)).contains(&note)));
+        assert_eq!(*melody.notes.last().unwrap(), (62, 4));
+
+        assert_eq!(Melody::new(0, 0, 96).notes[1].0, 57);
+    }
+
+    #[test]
+    fn test_from_fortune() {
+        let hash = HashBits::from_seed(2026, "test-user");
+        let fortune = Fortune::from_hash(&hash, 2026, "test-user");
+        assert_eq!(Melody::from_fortune(&fortune), Melody::new(hash.lucky_bits(), hash.lucky_hex(), hash.lucky_number()));
+    }
+
+    #[test]
+    fn test_midi_file() {
+        let melody = Melody::new(0xA5A5, 0x00, 48);
+        let midi = melody.to_midi();
+        assert_eq!(&midi[..14], b"MThd\0\0\0\x06\0\0\0\x01\x01\xE0");
+        assert_eq!(&midi[14..18], b"MTrk");
+        let length = u32::from_be_bytes(midi[18..22].try_into().unwrap()) as usize;
+        assert_eq!(midi.len(), 22 + length);
+        // 120 BPM is 500000 microseconds per quarter note
+        assert_eq!(&midi[22..29], [0x00, 0xFF, 0x51, 0x03, 0x07, 0xA1, 0x20]);
+        assert!(midi.ends_with(&[0x00, 0xFF, 0x2F, 0x00]));
+    }
+
+    #[test]
+    fn test_vlq() {
+        for (value, bytes) in [(0, vec![0x00]), (0x7F, vec![0x7F]), (480, vec![0x83, 0x60]), (1920, vec![0x8F, 0x00])] {
+            let mut out = Vec::new();
+            push_vlq(&mut out, value);
+            assert_eq!(out, bytes, "{}", value);
+        }
+    }
+
+    #[test]
+    fn test_ring() {
+        let melody = Melody { key: 0, bpm: 60_000, notes: vec![(60, 1), (69, 2)] };
+        let mut out = Vec::new();
+        melody.ring(&mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "\x07C4 \x07A4 \n");
+    }
+}
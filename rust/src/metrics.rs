@@ -0,0 +1,65 @@
+//! Prometheus text exposition of drawn fortunes, served at `/metrics`.
+
+use crate::luck::Grade;
+use crate::output::Fortune;
+
+/// `# HELP` / `# TYPE` header of a gauge family
+fn family(output: &mut String, name: &str, help: &str) {
+    output.push_str(&format!("# HELP {} {}\n# TYPE {} gauge\n", name, help, name));
+}
+
+/// Gauges for each `(seed fingerprint, fortune)`: the 16 luck scores, the overall score and
+/// the grade as an enum (one series per grade, 1 for the drawn one)
+pub fn exposition(samples: &[(String, Fortune)]) -> String {
+    let mut output = String::new();
+    let labels = |seed: &str, fortune: &Fortune| format!("seed_fingerprint=\"{}\",year=\"{}\"", seed, fortune.year);
+
+    family(&mut output, "omikuji_luck_score", "Luck score (0-100) of one category");
+    for (seed, fortune) in samples {
+        for score in &fortune.luck_scores {
+            output.push_str(&format!(
+                "omikuji_luck_score{{category=\"{}\",{}}} {}\n",
+                score.luck_type.key(),
+                labels(seed, fortune),
+                score.score
+            ));
+        }
+    }
+
+    family(&mut output, "omikuji_overall_score", "Weighted overall score (0-100)");
+    for (seed, fortune) in samples {
+        output.push_str(&format!("omikuji_overall_score{{{}}} {}\n", labels(seed, fortune), fortune.overall_score));
+    }
+
+    family(&mut output, "omikuji_grade", "Drawn grade (1 for the drawn grade, 0 for the others)");
+    for (seed, fortune) in samples {
+        for grade in Grade::ALL {
+            output.push_str(&format!(
+                "omikuji_grade{{grade=\"{}\",{}}} {}\n",
+                grade.romaji(),
+                labels(seed, fortune),
+                u8::from(grade == fortune.grade)
+            ));
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::seed_fingerprint;
+
+    #[test]
+    fn test_exposition() {
+        let fortune = Fortune::builder().year(2026).seed("alice").draw();
+        let text = exposition(&[(seed_fingerprint("alice"), fortune.clone())]);
+        let labels = format!("seed_fingerprint=\"{}\",year=\"2026\"", seed_fingerprint("alice"));
+        assert!(text.starts_with("# HELP omikuji_luck_score "));
+        assert_eq!(text.lines().filter(|l| l.starts_with("omikuji_luck_score{")).count(), 16);
+        assert!(text.contains(&format!("omikuji_overall_score{{{}}} {}\n", labels, fortune.overall_score)));
+        assert!(text.contains(&format!("omikuji_grade{{grade=\"{}\",{}}} 1\n", fortune.grade.romaji(), labels)));
+        assert_eq!(text.lines().filter(|l| l.starts_with("omikuji_grade{") && l.ends_with(" 0")).count(), 6);
+        assert!(!text.contains("alice"));
+    }
+}
@@ -0,0 +1,145 @@
+//! `motd`: an 80-column login banner for /etc/update-motd.d. Most days it shows the
+//! daily fortune; on January 1st it shows the year's draw with its art.
+
+use crate::art::{self, ArtOptions};
+use crate::lang::display_width;
+use crate::luck::Grade;
+use crate::output::Fortune;
+use crate::prompt::{grade_color, Segment};
+use chrono::NaiveDate;
+
+/// Total banner width, frame included
+pub const WIDTH: usize = 80;
+
+/// Art of the New Year banner: a small board that fits next to the fields
+pub fn art_options() -> ArtOptions {
+    ArtOptions { height: 6, ..ArtOptions::default() }
+}
+
+/// Everyday banner: today's grade and emoji, with the year's grade for reference
+pub fn daily(segment: &Segment, fortune: &Fortune, date: NaiveDate, color: bool) -> String {
+    let rows = [
+        format!("{:12}: {} {}", format!("Today {}", date.format("%m-%d")), paint(segment.grade, color), segment.emoji),
+        format!("{:12}: {} ({:#})", format!("Year {}", fortune.year), paint(fortune.grade, color), fortune.grade),
+    ];
+    boxed(&format!("[hash-omikuji {}]", date), &rows)
+}
+
+/// January 1st banner: the framed art next to the headline fields
+pub fn new_year(fortune: &Fortune, color: bool) -> String {
+    let inner = WIDTH - 4;
+    let art = art::framed(&fortune.art, fortune.year, fortune.grade, false);
+    let art: Vec<&str> = art.lines().collect();
+    let art_width = art.iter().map(|l| display_width(l)).max().unwrap_or(0);
+    let text_width = inner.saturating_sub(art_width + 2);
+
+    let mut best: Vec<_> = fortune.luck_scores.iter().collect();
+    best.sort_by_key(|s| std::cmp::Reverse(s.score));
+    let fields = [
+        ("Grade", format!("{} ({:#})", fortune.grade, fortune.grade)),
+        // Stars are left out: terminals disagree on their width
+        ("Score", format!("{}/100", fortune.overall_score)),
+        ("Lucky Number", fortune.lucky_number.to_string()),
+        ("Lucky Color", fortune.lucky_color.clone()),
+        ("Lucky Item", fortune.lucky_item.clone()),
+        ("Best Luck", best.first().map_or(String::new(), |s| format!("{} {}", s.label, s.score))),
+        ("Advice", fortune.advice.clone()),
+    ];
+    let text: Vec<String> = fields
+        .iter()
+        .map(|(label, value)| {
+            let line = truncate(&format!("{:13}: {}", label, value), text_width);
+            if *label == "Grade" {
+                line.replacen(&fortune.grade.to_string(), &paint(fortune.grade, color), 1)
+            } else {
+                line
+            }
+        })
+        .collect();
+
+    let rows: Vec<String> = (0..art.len().max(text.len()))
+        .map(|i| {
+            let left = art.get(i).copied().unwrap_or("");
+            let pad = art_width - display_width(left);
+            format!("{}{}  {}", left, " ".repeat(pad), text.get(i).map_or("", String::as_str))
+        })
+        .collect();
+    boxed(&format!("[🎍 hash-omikuji {} 🎍]", fortune.year), &rows)
+}
+
+fn paint(grade: Grade, color: bool) -> String {
+    if color {
+        format!("\x1b[38;5;{}m{}\x1b[0m", grade_color(grade), grade)
+    } else {
+        grade.to_string()
+    }
+}
+
+/// Longest prefix of `text` at most `width` columns wide
+fn truncate(text: &str, width: usize) -> String {
+    let mut output = String::new();
+    let mut used = 0;
+    for c in text.chars() {
+        let w = display_width(c.encode_utf8(&mut [0; 4]));
+        if used + w > width {
+            break;
+        }
+        used += w;
+        output.push(c);
+    }
+    output
+}
+
+/// Frame `rows` in a WIDTH-column box with `title` in the top border
+fn boxed(title: &str, rows: &[String]) -> String {
+    let inner = WIDTH - 2;
+    let dashes = inner - display_width(title);
+    let mut output = format!("+{}{}{}+\n", "-".repeat(dashes / 2), title, "-".repeat(dashes - dashes / 2));
+    for row in rows {
+        let pad = (inner - 2).saturating_sub(display_width(row));
+        output.push_str(&format!("| {}{} |\n", row, " ".repeat(pad)));
+    }
+    output.push_str(&format!("+{}+\n", "-".repeat(inner)));
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::HashBits;
+    use crate::luck::GradeWeights;
+
+    fn fortune() -> Fortune {
+        Fortune::builder().year(2026).seed("alice").art(art_options()).draw()
+    }
+
+    #[test]
+    fn test_new_year_fits_80_columns() {
+        let fortune = fortune();
+        for color in [false, true] {
+            let banner = new_year(&fortune, color);
+            assert!(banner.lines().all(|l| display_width(l) == WIDTH), "{}", banner);
+            assert!(banner.contains("[hash-omikuji 2026]"));
+            assert!(banner.contains(&format!("Lucky Number : {}", fortune.lucky_number)));
+            assert_eq!(banner.contains('\x1b'), color);
+        }
+    }
+
+    #[test]
+    fn test_daily_fits_80_columns() {
+        let fortune = fortune();
+        let date = NaiveDate::from_ymd_opt(2026, 3, 14).unwrap();
+        let segment = Segment::daily(&HashBits::from_seed(2026, "alice"), date, &GradeWeights::default());
+        let banner = daily(&segment, &fortune, date, false);
+        assert!(banner.lines().all(|l| display_width(l) == WIDTH), "{}", banner);
+        assert!(banner.contains("[hash-omikuji 2026-03-14]"));
+        assert!(banner.contains(&format!("Today 03-14 : {} {}", segment.grade, segment.emoji)));
+        assert!(!banner.contains('\x1b'));
+    }
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!(truncate("大吉 lucky", 5), "大吉 ");
+        assert_eq!(truncate("abc", 10), "abc");
+    }
+}
@@ -0,0 +1,482 @@
+//! Nengajo: the fortune as a New Year's postcard on a hagaki (100x148 mm), for
+//! `nengajo`. The greeting, the grade as a seal, the art board, the poem in vertical
+//! columns and a ruled box left empty for a handwritten note.
+//!
+//! The card is laid out once in millimetres and written as SVG or as a one-page PDF.
+//! The PDF names the standard Japanese font HeiseiMin-W3 without embedding it, so
+//! viewers substitute their own Mincho.
+
+use crate::art::ArtMode;
+use crate::kansuji;
+use crate::output::Fortune;
+use crate::theme::{rgb, Palette};
+use crate::wareki;
+
+/// Hagaki size in millimetres
+pub const WIDTH: f64 = 100.0;
+pub const HEIGHT: f64 = 148.0;
+
+/// PDF points per millimetre
+const PT: f64 = 72.0 / 25.4;
+
+/// Where the poem columns start and the note box begins
+const POEM_TOP: f64 = 42.0;
+const NOTE_TOP: f64 = 104.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Font {
+    /// Japanese serif; every glyph is one em wide
+    Mincho,
+    /// Latin sans-serif, ASCII only
+    Sans,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Shape {
+    Rect { x: f64, y: f64, width: f64, height: f64, fill: String },
+    Frame { x: f64, y: f64, width: f64, height: f64, stroke: String, dashed: bool },
+    Line { from: (f64, f64), to: (f64, f64), stroke: String },
+    /// `y` is the baseline; text always starts at `x`
+    Text { x: f64, y: f64, size: f64, text: String, fill: String, font: Font },
+}
+
+/// ASCII printable characters as their full-width forms, so Mincho text keeps one em per glyph
+fn fullwidth(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            ' ' => '\u{3000}',
+            '!'..='~' => char::from_u32(c as u32 - 0x21 + 0xFF01).unwrap_or(c),
+            _ => c,
+        })
+        .collect()
+}
+
+/// Mincho text centered on `center`
+fn centered(center: f64, y: f64, size: f64, text: &str, fill: &str) -> Shape {
+    let text = fullwidth(text);
+    let width = text.chars().count() as f64 * size;
+    Shape::Text { x: center - width / 2.0, y, size, text, fill: fill.to_string(), font: Font::Mincho }
+}
+
+/// One glyph per row downwards from `top`, centered on `x`; a space leaves half a row
+fn vertical(shapes: &mut Vec<Shape>, x: f64, top: f64, size: f64, text: &str, fill: &str) {
+    let advance = size * 1.1;
+    let mut y = top;
+    for c in text.chars() {
+        if c.is_whitespace() {
+            y += advance / 2.0;
+            continue;
+        }
+        y += advance;
+        shapes.push(Shape::Text {
+            x: x - size / 2.0,
+            y: y - size * 0.12,
+            size,
+            text: c.to_string(),
+            fill: fill.to_string(),
+            font: Font::Mincho,
+        });
+    }
+}
+
+/// Art cells as (column, row, color) on a board of the returned width and height: the
+/// mandala from its glyphs, any other mode from the walk like the PNG card
+fn board(fortune: &Fortune, palette: &Palette) -> (usize, usize, Vec<(usize, usize, &'static str)>) {
+    let ramp = |count: usize| if count == 0 { palette.grid } else { palette.ramp[count.min(palette.ramp.len()) - 1] };
+    if fortune.art_options.mode == ArtMode::Mandala {
+        let symbols = fortune.art_options.palette.symbols();
+        let rows: Vec<&str> = fortune.art.lines().collect();
+        let width = rows.iter().map(|row| row.chars().count()).max().unwrap_or(0);
+        let cells = rows
+            .iter()
+            .enumerate()
+            .flat_map(|(y, row)| {
+                row.chars().enumerate().map(move |(x, glyph)| (x, y, symbols.iter().position(|&s| s == glyph).unwrap_or(0)))
+            })
+            .map(|(x, y, count)| (x, y, ramp(count)))
+            .collect();
+        return (width, rows.len(), cells);
+    }
+    let walk = &fortune.art_walk;
+    let cells = walk
+        .visits
+        .iter()
+        .enumerate()
+        .map(|(i, &count)| {
+            let color = if i == walk.start || i == walk.end { palette.foreground } else { ramp(count as usize) };
+            (i % walk.width, i / walk.width, color)
+        })
+        .collect();
+    (walk.width, walk.visits.len() / walk.width, cells)
+}
+
+fn layout(fortune: &Fortune, palette: &Palette, kanji_numerals: bool) -> Vec<Shape> {
+    let (foreground, accent) = (palette.foreground, palette.accent);
+    let mut shapes = vec![
+        Shape::Rect { x: 0.0, y: 0.0, width: WIDTH, height: HEIGHT, fill: palette.background.to_string() },
+        Shape::Frame { x: 5.0, y: 5.0, width: WIDTH - 10.0, height: HEIGHT - 10.0, stroke: accent.to_string(), dashed: false },
+        centered(WIDTH / 2.0, 24.0, 12.0, "謹賀新年", accent),
+    ];
+    let date = match (kanji_numerals, wareki::format_ja(fortune.year)) {
+        (true, Some(era)) => format!("{} 元旦", kansuji::in_text(&era)),
+        (true, None) => format!("{}年 元旦", kansuji::digits(fortune.year as u64)),
+        (false, Some(era)) => format!("{} ({}) 元旦", era, fortune.year),
+        (false, None) => format!("{}年 元旦", fortune.year),
+    };
+    shapes.push(centered(WIDTH / 2.0, 32.0, 3.6, &date, foreground));
+
+    // The grade stacked in a seal, the way a hanko is carved
+    let kanji: Vec<char> = fortune.grade.kanji().chars().collect();
+    let seal_height = 4.0 + 12.0 * kanji.len() as f64;
+    shapes.push(Shape::Rect { x: 12.0, y: POEM_TOP, width: 16.0, height: seal_height, fill: accent.to_string() });
+    for (i, c) in kanji.iter().enumerate() {
+        let y = POEM_TOP + 2.0 + 12.0 * (i + 1) as f64 - 1.5;
+        shapes.push(Shape::Text { x: 14.0, y, size: 12.0, text: c.to_string(), fill: palette.background.to_string(), font: Font::Mincho });
+    }
+    // The slip number takes the place of the romaji with kanji numerals
+    let (label, score) = if kanji_numerals {
+        (kansuji::slip_number(fortune.lucky_number as u64), kansuji::points(fortune.overall_score as u64))
+    } else {
+        (fortune.grade.romaji().to_string(), format!("{}点", fortune.overall_score))
+    };
+    shapes.push(Shape::Text {
+        x: 32.0,
+        y: POEM_TOP + 5.0,
+        size: 3.2,
+        text: label,
+        fill: foreground.to_string(),
+        font: if kanji_numerals { Font::Mincho } else { Font::Sans },
+    });
+    shapes.push(Shape::Text {
+        x: 32.0,
+        y: POEM_TOP + 11.0,
+        size: 3.2,
+        text: fullwidth(&format!("総合 {}", score)),
+        fill: foreground.to_string(),
+        font: Font::Mincho,
+    });
+    shapes.push(Shape::Rect { x: 32.0, y: POEM_TOP + 14.5, width: 3.2, height: 3.2, fill: fortune.lucky_color.clone() });
+    shapes.push(Shape::Text {
+        x: 36.5,
+        y: POEM_TOP + 17.2,
+        size: 2.6,
+        text: fortune.lucky_color.clone(),
+        fill: foreground.to_string(),
+        font: Font::Sans,
+    });
+
+    // Art board fitted into the lower left box
+    let (area_x, area_y, area_width, area_height) = (10.0, 72.0, 46.0, 28.0);
+    let (columns, rows, cells) = board(fortune, palette);
+    if columns > 0 && rows > 0 {
+        let cell = (area_width / columns as f64).min(area_height / rows as f64);
+        let left = area_x + (area_width - cell * columns as f64) / 2.0;
+        let top = area_y + (area_height - cell * rows as f64) / 2.0;
+        for (x, y, color) in cells {
+            shapes.push(Shape::Rect {
+                x: left + x as f64 * cell + cell * 0.08,
+                y: top + y as f64 * cell + cell * 0.08,
+                width: cell * 0.84,
+                height: cell * 0.84,
+                fill: color.to_string(),
+            });
+        }
+    }
+
+    // The poem right to left: upper verses, lower verses two glyphs lower, then the poet
+    let verses: Vec<&str> = fortune.poem.text.split(' ').collect();
+    let split = verses.len().min(3);
+    let size = 3.2;
+    vertical(&mut shapes, 85.0, POEM_TOP, size, &verses[..split].join(" "), foreground);
+    vertical(&mut shapes, 78.5, POEM_TOP + size * 2.2, size, &verses[split..].join(" "), foreground);
+    let author_size = 2.6;
+    let author_top = NOTE_TOP - 4.0 - author_size * 1.1 * fortune.poem.author.chars().count() as f64;
+    vertical(&mut shapes, 71.0, author_top, author_size, &fortune.poem.author, foreground);
+
+    // Room for a handwritten note
+    let (note_x, note_width, note_height) = (10.0, WIDTH - 20.0, 30.0);
+    shapes.push(Shape::Frame {
+        x: note_x,
+        y: NOTE_TOP,
+        width: note_width,
+        height: note_height,
+        stroke: palette.grid.to_string(),
+        dashed: true,
+    });
+    for line in 1..4 {
+        let y = NOTE_TOP + note_height * line as f64 / 4.0;
+        shapes.push(Shape::Line { from: (note_x + 3.0, y), to: (note_x + note_width - 3.0, y), stroke: palette.grid.to_string() });
+    }
+
+    shapes.push(Shape::Text {
+        x: 10.0,
+        y: HEIGHT - 8.0,
+        size: 2.2,
+        text: format!("hash-omikuji {} {}", fortune.year, fortune.fingerprint.chars().take(16).collect::<String>()),
+        fill: foreground.to_string(),
+        font: Font::Sans,
+    });
+    shapes
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// The card as an SVG document sized in millimetres; `kanji_numerals` writes the era
+/// year and score in kanji and numbers the slip
+pub fn svg(fortune: &Fortune, palette: &Palette, kanji_numerals: bool) -> String {
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}mm\" height=\"{1}mm\" viewBox=\"0 0 {0} {1}\">\n",
+        WIDTH, HEIGHT
+    );
+    for shape in layout(fortune, palette, kanji_numerals) {
+        match shape {
+            Shape::Rect { x, y, width, height, fill } => svg.push_str(&format!(
+                "<rect x=\"{:.2}\" y=\"{:.2}\" width=\"{:.2}\" height=\"{:.2}\" fill=\"{}\"/>\n",
+                x, y, width, height, fill
+            )),
+            Shape::Frame { x, y, width, height, stroke, dashed } => svg.push_str(&format!(
+                "<rect x=\"{:.2}\" y=\"{:.2}\" width=\"{:.2}\" height=\"{:.2}\" fill=\"none\" stroke=\"{}\" stroke-width=\"0.4\"{}/>\n",
+                x,
+                y,
+                width,
+                height,
+                stroke,
+                if dashed { " stroke-dasharray=\"1.5 1\"" } else { "" }
+            )),
+            Shape::Line { from, to, stroke } => svg.push_str(&format!(
+                "<line x1=\"{:.2}\" y1=\"{:.2}\" x2=\"{:.2}\" y2=\"{:.2}\" stroke=\"{}\" stroke-width=\"0.2\"/>\n",
+                from.0, from.1, to.0, to.1, stroke
+            )),
+            Shape::Text { x, y, size, text, fill, font } => {
+                let family = match font {
+                    Font::Mincho => "'Hiragino Mincho ProN', 'Yu Mincho', 'Noto Serif JP', serif",
+                    Font::Sans => "sans-serif",
+                };
+                svg.push_str(&format!(
+                    "<text x=\"{:.2}\" y=\"{:.2}\" font-size=\"{:.2}\" font-family=\"{}\" fill=\"{}\">{}</text>\n",
+                    x,
+                    y,
+                    size,
+                    family,
+                    fill,
+                    escape_xml(&text)
+                ));
+            }
+        }
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// `r g b` operands of a `#RRGGBB` color
+fn pdf_color(hex: &str) -> String {
+    let (r, g, b) = rgb(hex);
+    format!("{:.3} {:.3} {:.3}", r as f64 / 255.0, g as f64 / 255.0, b as f64 / 255.0)
+}
+
+/// Literal string of Latin-1 text; anything else prints as `?`
+fn pdf_latin(text: &str) -> String {
+    let mut output = String::from("(");
+    for c in text.chars() {
+        match c {
+            '(' | ')' | '\\' => {
+                output.push('\\');
+                output.push(c);
+            }
+            ' '..='~' => output.push(c),
+            _ => output.push('?'),
+        }
+    }
+    output.push(')');
+    output
+}
+
+/// Hex string of UTF-16BE code units, as the UniJIS-UCS2-H encoding reads them
+fn pdf_ucs2(text: &str) -> String {
+    let mut output = String::from("<");
+    for unit in text.encode_utf16() {
+        output.push_str(&format!("{:04X}", unit));
+    }
+    output.push('>');
+    output
+}
+
+/// Page content stream in points, origin at the bottom left
+fn pdf_content(shapes: &[Shape]) -> String {
+    let x = |mm: f64| mm * PT;
+    let y = |mm: f64| (HEIGHT - mm) * PT;
+    let mut content = String::new();
+    for shape in shapes {
+        match shape {
+            Shape::Rect { x: left, y: top, width, height, fill } => content.push_str(&format!(
+                "{} rg {:.2} {:.2} {:.2} {:.2} re f\n",
+                pdf_color(fill),
+                x(*left),
+                y(top + height),
+                width * PT,
+                height * PT
+            )),
+            Shape::Frame { x: left, y: top, width, height, stroke, dashed } => content.push_str(&format!(
+                "{} RG {:.2} w {} d {:.2} {:.2} {:.2} {:.2} re S\n",
+                pdf_color(stroke),
+                0.4 * PT,
+                if *dashed { format!("[{:.2} {:.2}] 0", 1.5 * PT, PT) } else { "[] 0".to_string() },
+                x(*left),
+                y(top + height),
+                width * PT,
+                height * PT
+            )),
+            Shape::Line { from, to, stroke } => content.push_str(&format!(
+                "{} RG {:.2} w [] 0 d {:.2} {:.2} m {:.2} {:.2} l S\n",
+                pdf_color(stroke),
+                0.2 * PT,
+                x(from.0),
+                y(from.1),
+                x(to.0),
+                y(to.1)
+            )),
+            Shape::Text { x: left, y: baseline, size, text, fill, font } => {
+                let (name, string) = match font {
+                    Font::Mincho => ("F1", pdf_ucs2(text)),
+                    Font::Sans => ("F2", pdf_latin(text)),
+                };
+                content.push_str(&format!(
+                    "BT {} rg /{} {:.2} Tf {:.2} {:.2} Td {} Tj ET\n",
+                    pdf_color(fill),
+                    name,
+                    size * PT,
+                    x(*left),
+                    y(*baseline),
+                    string
+                ));
+            }
+        }
+    }
+    content
+}
+
+/// The card as a one-page PDF, laid out as [`svg`]
+pub fn pdf(fortune: &Fortune, palette: &Palette, kanji_numerals: bool) -> Vec<u8> {
+    let content = pdf_content(&layout(fortune, palette, kanji_numerals));
+    let objects = [
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        "<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_string(),
+        format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {:.2} {:.2}] /Resources << /Font << /F1 5 0 R /F2 8 0 R >> >> /Contents 4 0 R >>",
+            WIDTH * PT,
+            HEIGHT * PT
+        ),
+        format!("<< /Length {} >>\nstream\n{}endstream", content.len(), content),
+        "<< /Type /Font /Subtype /Type0 /BaseFont /HeiseiMin-W3 /Encoding /UniJIS-UCS2-H /DescendantFonts [6 0 R] >>".to_string(),
+        "<< /Type /Font /Subtype /CIDFontType0 /BaseFont /HeiseiMin-W3 /CIDSystemInfo << /Registry (Adobe) /Ordering (Japan1) /Supplement 2 >> /FontDescriptor 7 0 R /DW 1000 >>".to_string(),
+        "<< /Type /FontDescriptor /FontName /HeiseiMin-W3 /Flags 6 /FontBBox [-123 -257 1001 910] /ItalicAngle 0 /Ascent 859 /Descent -141 /CapHeight 709 /StemV 69 >>".to_string(),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>".to_string(),
+    ];
+
+    let mut pdf = b"%PDF-1.4\n".to_vec();
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.extend(format!("{} 0 obj\n{}\nendobj\n", i + 1, object).as_bytes());
+    }
+    let xref = pdf.len();
+    pdf.extend(format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).as_bytes());
+    for offset in offsets {
+        pdf.extend(format!("{:010} 00000 n \n", offset).as_bytes());
+    }
+    pdf.extend(format!("trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n", objects.len() + 1, xref).as_bytes());
+    pdf
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::art::ArtOptions;
+    use crate::hash::HashBits;
+    use crate::theme::Theme;
+
+    fn fortune() -> Fortune {
+        let hash = HashBits::from_seed(2026, "test-user");
+        Fortune::from_hash(&hash, 2026, "test-user")
+    }
+
+    #[test]
+    fn test_svg() {
+        let fortune = fortune();
+        let svg = svg(&fortune, &Theme::Shrine.palette(), false);
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"100mm\" height=\"148mm\" viewBox=\"0 0 100 148\">\n"));
+        assert!(svg.contains(">謹賀新年</text>"));
+        assert!(svg.contains(">令和８年\u{3000}（２０２６）\u{3000}元旦</text>"));
+        assert!(svg.contains("stroke-dasharray"));
+        let first = fortune.poem.text.chars().next().unwrap();
+        assert!(svg.contains(&format!(">{}</text>", first)));
+        assert!(svg.ends_with("</svg>\n"));
+
+        let svg = self::svg(&fortune, &Theme::Shrine.palette(), true);
+        assert!(svg.contains(">令和八年\u{3000}元旦</text>"));
+        assert!(svg.contains(&format!(">{}</text>", kansuji::slip_number(fortune.lucky_number as u64))));
+        assert!(svg.contains(&format!(">総合\u{3000}{}</text>", kansuji::points(fortune.overall_score as u64))));
+    }
+
+    #[test]
+    fn test_layout_fits_the_card() {
+        let hash = HashBits::from_seed(2026, "test-user");
+        let mandala = ArtOptions { mode: ArtMode::Mandala, ..ArtOptions::default() };
+        for fortune in [fortune(), fortune().with_art(&hash, &mandala)] {
+            for shape in layout(&fortune, &Theme::Ink.palette(), true) {
+                let (x, y) = match shape {
+                    Shape::Rect { x, y, width, height, .. } | Shape::Frame { x, y, width, height, .. } => (x + width, y + height),
+                    Shape::Line { to, .. } => to,
+                    Shape::Text { x, y, .. } => (x, y),
+                };
+                assert!(x <= WIDTH && y <= HEIGHT, "{:?}", (x, y));
+            }
+        }
+        // Poem columns stay above the note box
+        for poem in crate::poems::POEMS {
+            let mut shapes = Vec::new();
+            vertical(&mut shapes, 0.0, POEM_TOP, 3.2, poem.text.split(' ').take(3).collect::<Vec<_>>().join(" ").as_str(), "#000000");
+            assert!(shapes.iter().all(|s| matches!(s, Shape::Text { y, .. } if *y < NOTE_TOP)), "{}", poem.text);
+        }
+    }
+
+    #[test]
+    fn test_mandala_board() {
+        let hash = HashBits::from_seed(2026, "test-user");
+        let options = ArtOptions { mode: ArtMode::Mandala, ..ArtOptions::default() };
+        let fortune = fortune().with_art(&hash, &options);
+        let (width, height, cells) = board(&fortune, &Theme::Shrine.palette());
+        assert_eq!((width, height), crate::art::MANDALA_SIZE);
+        assert_eq!(cells.len(), width * height);
+        let color = |x: usize, y: usize| cells.iter().find(|c| (c.0, c.1) == (x, y)).unwrap().2;
+        assert_eq!(color(0, 0), color(width - 1, height - 1));
+    }
+
+    #[test]
+    fn test_pdf() {
+        let pdf = pdf(&fortune(), &Theme::Shrine.palette(), false);
+        assert!(pdf.starts_with(b"%PDF-1.4\n"));
+        assert!(pdf.ends_with(b"%%EOF\n"));
+        let text = std::str::from_utf8(&pdf).unwrap();
+        assert!(text.contains("/MediaBox [0 0 283.46 419.53]"));
+        // 謹賀新年
+        assert!(text.contains("<8B398CC065B05E74> Tj"));
+
+        // Every xref entry points at its object
+        let xref: usize = text.rsplit("startxref\n").next().unwrap().lines().next().unwrap().parse().unwrap();
+        assert!(pdf[xref..].starts_with(b"xref\n"));
+        for (i, line) in text[xref..].lines().skip(3).take(8).enumerate() {
+            let offset: usize = line[..10].parse().unwrap();
+            assert!(pdf[offset..].starts_with(format!("{} 0 obj\n", i + 1).as_bytes()), "{}", i + 1);
+        }
+    }
+
+    #[test]
+    fn test_fullwidth() {
+        assert_eq!(fullwidth("総合 73点"), "総合\u{3000}７３点");
+        assert_eq!(pdf_ucs2("吉"), "<5409>");
+        assert_eq!(pdf_latin("a(b)\\ō"), "(a\\(b\\)\\\\?)");
+    }
+}
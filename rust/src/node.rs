@@ -0,0 +1,30 @@
+//! Node.js addon (napi-rs) with the same draw/verify API as the CLI.
+//! TypeScript definitions live in `node/index.d.ts`.
+
+use crate::output::Fortune;
+use crate::sign;
+use napi::{Error, Result};
+use napi_derive::napi;
+use serde_json::Value;
+
+/// Result of `verify`, mirroring `sign::Verification`
+#[napi(object)]
+pub struct Verification {
+    pub checksum: bool,
+    pub signer: Option<String>,
+}
+
+/// Full fortune as a plain object, the same document as `--json`
+#[napi]
+pub fn draw(year: u32, seed: String) -> Value {
+    serde_json::to_value(Fortune::builder().year(year).seed(&seed).draw()).unwrap()
+}
+
+/// Check the layout v2 checksum and/or signature of a JSON fortune; throws on failure
+#[napi]
+pub fn verify(json: String, pubkey: Option<String>) -> Result<Verification> {
+    let document: Value = serde_json::from_str(&json).map_err(|e| Error::from_reason(format!("Invalid JSON: {}", e)))?;
+    let expected = pubkey.as_deref().map(sign::parse_verifying_key).transpose().map_err(Error::from_reason)?;
+    let verification = sign::verify_document(&document, expected.as_ref()).map_err(Error::from_reason)?;
+    Ok(Verification { checksum: verification.checksum, signer: verification.signer })
+}
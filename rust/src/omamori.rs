@@ -0,0 +1,183 @@
+//! Omamori tokens: a draw packed into 22 base64url characters, short enough to post.
+//! 16 bytes: version, year (big-endian u16), a flags byte (layout, algo, grade), the
+//! seed fingerprint and an 8-byte MAC keyed with the seed, so only someone who knows
+//! the seed can mint or check one. `omamori decode` expands and verifies a token.
+
+use crate::builder::FortuneBuilder;
+use crate::fingerprint::{base64url, decode_base64url, decode_hex, hex};
+use crate::hash::{Algo, Layout};
+use crate::luck::Grade;
+use crate::output::{seed_fingerprint, Fortune};
+
+const VERSION: u8 = 1;
+
+/// Token bytes before the MAC
+const HEADER_LEN: usize = 8;
+const MAC_LEN: usize = 8;
+
+/// blake3 key derivation context of the MAC key
+const MAC_CONTEXT: &str = "hash-omikuji 2026-01-01 omamori token MAC";
+
+/// The fields of a token
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Omamori {
+    pub year: u32,
+    pub layout: Layout,
+    pub algo: Algo,
+    pub grade: Grade,
+    /// Zero when the fortune was drawn with `--anonymize`
+    pub seed_fingerprint: [u8; 4],
+    pub mac: [u8; MAC_LEN],
+}
+
+/// Outcome of checking a token against a seed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Check {
+    /// The MAC matches and the seed draws the token's grade
+    Verified,
+    /// The token belongs to a different seed
+    OtherSeed,
+    /// The seed fits but the token was altered or minted elsewhere
+    BadMac,
+    /// The MAC matches but the seed draws another grade (e.g. custom grade weights)
+    GradeMismatch(Grade),
+}
+
+fn mac(seed: &str, header: &[u8]) -> [u8; MAC_LEN] {
+    let key = blake3::derive_key(MAC_CONTEXT, seed.as_bytes());
+    let mut mac = [0u8; MAC_LEN];
+    mac.copy_from_slice(&blake3::keyed_hash(&key, header).as_bytes()[..MAC_LEN]);
+    mac
+}
+
+impl Omamori {
+    /// The token of `fortune`, drawn from the (combined) `seed`
+    pub fn new(fortune: &Fortune, seed: &str) -> Self {
+        let fingerprint = fortune.seed.strip_prefix("device:").and_then(decode_hex).unwrap_or_default();
+        let mut omamori = Self {
+            year: fortune.year,
+            layout: fortune.layout,
+            algo: fortune.algo,
+            grade: fortune.grade,
+            seed_fingerprint: fingerprint.try_into().unwrap_or_default(),
+            mac: [0; MAC_LEN],
+        };
+        omamori.mac = mac(seed, &omamori.header());
+        omamori
+    }
+
+    fn header(&self) -> [u8; HEADER_LEN] {
+        let grade = Grade::ALL.iter().position(|&g| g == self.grade).unwrap_or(0) as u8;
+        let flags = (self.layout == Layout::V2) as u8 | ((self.algo == Algo::Blake3) as u8) << 1 | grade << 4;
+        let [hi, lo] = (self.year as u16).to_be_bytes();
+        let [a, b, c, d] = self.seed_fingerprint;
+        [VERSION, hi, lo, flags, a, b, c, d]
+    }
+
+    pub fn encode(&self) -> String {
+        let mut bytes = self.header().to_vec();
+        bytes.extend(self.mac);
+        base64url(&bytes)
+    }
+
+    pub fn decode(token: &str) -> Result<Self, String> {
+        let bytes = decode_base64url(token).ok_or("Not an omamori token: invalid base64url")?;
+        if bytes.len() != HEADER_LEN + MAC_LEN {
+            return Err(format!("Not an omamori token: {} bytes instead of {}", bytes.len(), HEADER_LEN + MAC_LEN));
+        }
+        if bytes[0] != VERSION {
+            return Err(format!("Unsupported omamori token version {}", bytes[0]));
+        }
+        let flags = bytes[3];
+        let grade = *Grade::ALL.get((flags >> 4) as usize).ok_or("Not an omamori token: invalid grade")?;
+        if flags & 0x0c != 0 {
+            return Err("Not an omamori token: unknown flags".to_string());
+        }
+        Ok(Self {
+            year: u16::from_be_bytes([bytes[1], bytes[2]]) as u32,
+            layout: if flags & 1 != 0 { Layout::V2 } else { Layout::V1 },
+            algo: if flags & 2 != 0 { Algo::Blake3 } else { Algo::Sha256 },
+            grade,
+            seed_fingerprint: bytes[4..8].try_into().unwrap(),
+            mac: bytes[8..].try_into().unwrap(),
+        })
+    }
+
+    /// Check the token against `seed`, redrawing it with `builder`'s grade weights
+    pub fn check(&self, seed: &str, builder: &FortuneBuilder) -> Check {
+        let anonymized = self.seed_fingerprint == [0; 4];
+        if !anonymized && hex(&self.seed_fingerprint) != seed_fingerprint(seed) {
+            return Check::OtherSeed;
+        }
+        if mac(seed, &self.header()) != self.mac {
+            return if anonymized { Check::OtherSeed } else { Check::BadMac };
+        }
+        let fortune = builder.clone().year(self.year).layout(self.layout).algo(self.algo).seed(seed).draw();
+        if fortune.grade != self.grade {
+            return Check::GradeMismatch(fortune.grade);
+        }
+        Check::Verified
+    }
+
+    /// Multi-line description of the fields
+    pub fn describe(&self) -> String {
+        let layout = if self.layout == Layout::V2 { "v2" } else { "v1" };
+        let algo = if self.algo == Algo::Blake3 { "blake3" } else { "sha256" };
+        let seed = if self.seed_fingerprint == [0; 4] { "(anonymized)".to_string() } else { hex(&self.seed_fingerprint) };
+        format!(
+            "Year:   {}\nGrade:  {} ({:#})\nLayout: {}\nAlgo:   {}\nSeed:   {}\n",
+            self.year, self.grade, self.grade, layout, algo, seed
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fortune(seed: &str) -> Fortune {
+        Fortune::builder().year(2026).seed(seed).layout(Layout::V2).draw()
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let omamori = Omamori::new(&fortune("alice"), "alice");
+        let token = omamori.encode();
+        assert_eq!(token.len(), 22);
+        assert_eq!(Omamori::decode(&token).unwrap(), omamori);
+        assert_eq!(hex(&omamori.seed_fingerprint), seed_fingerprint("alice"));
+        assert_eq!(omamori.grade, fortune("alice").grade);
+        assert_eq!(omamori.layout, Layout::V2);
+    }
+
+    #[test]
+    fn test_check() {
+        let builder = Fortune::builder();
+        let omamori = Omamori::new(&fortune("alice"), "alice");
+        assert_eq!(omamori.check("alice", &builder), Check::Verified);
+        assert_eq!(omamori.check("bob", &builder), Check::OtherSeed);
+
+        let mut forged = omamori.clone();
+        forged.grade = if omamori.grade == Grade::Daikichi { Grade::Kyo } else { Grade::Daikichi };
+        assert_eq!(forged.check("alice", &builder), Check::BadMac);
+        forged.mac = mac("alice", &forged.header());
+        assert_eq!(forged.check("alice", &builder), Check::GradeMismatch(omamori.grade));
+
+        let anonymized = Omamori::new(&fortune("alice").anonymized(), "alice");
+        assert_eq!(anonymized.seed_fingerprint, [0; 4]);
+        assert_eq!(anonymized.check("alice", &builder), Check::Verified);
+        assert_eq!(anonymized.check("bob", &builder), Check::OtherSeed);
+    }
+
+    #[test]
+    fn test_decode_rejects_garbage() {
+        assert!(Omamori::decode("not a token").is_err());
+        assert!(Omamori::decode(&base64url(&[1; 8])).is_err());
+        let mut bytes = [0u8; 16];
+        bytes[0] = VERSION;
+        bytes[3] = 7 << 4;
+        assert!(Omamori::decode(&base64url(&bytes)).unwrap_err().contains("grade"));
+        bytes[0] = 2;
+        assert!(Omamori::decode(&base64url(&bytes)).unwrap_err().contains("version"));
+    }
+}
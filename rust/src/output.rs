@@ -1,19 +1,148 @@
-use crate::hash::HashBits;
-use crate::luck::{calculate_luck_scores, LuckScore};
-use chrono::NaiveDate;
-use serde::Serialize;
+use crate::advice::Grammar;
+use crate::art::{self, ArtMode, ArtOptions, ArtPalette, Walk};
+use crate::effort::EffortParams;
+use crate::card;
+use crate::corpus::Corpus;
+use crate::date;
+use crate::eto;
+use crate::fingerprint::FingerprintFormat;
+use crate::graphics::{self, Protocol};
+use crate::hash::{Algo, HashBits, Layout};
+use crate::items::{lucky_action, lucky_item};
+use crate::kansuji;
+use crate::lang::{display_width, Lang};
+use crate::luck::{
+    calculate_luck_scores, format_rarity, overall_score, rarity_one_in, score_bar, sparkline, star_rating,
+    CategoryWeights, Grade,
+    GradeWeights, LuckScore, LuckType,
+};
+use crate::poems::{self, Poem};
+use crate::sign::{self, Signature};
+use crate::theme::Theme;
+use crate::wareki;
+use crate::topics::{draw_topics, TopicVerdict};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use sha2::{Digest, Sha256};
+use std::collections::HashMap;
 
-fn hash_seed_for_display(seed: &str) -> String {
+/// First 4 bytes of the seed's SHA-256 in hex: identifies a seed without revealing it
+pub fn seed_fingerprint(seed: &str) -> String {
     let mut hasher = Sha256::new();
     hasher.update(seed.as_bytes());
     let result = hasher.finalize();
-    format!("device:{:02x}{:02x}{:02x}{:02x}", result[0], result[1], result[2], result[3])
+    format!("{:02x}{:02x}{:02x}{:02x}", result[0], result[1], result[2], result[3])
+}
+
+fn hash_seed_for_display(seed: &str) -> String {
+    format!("device:{}", seed_fingerprint(seed))
+}
+
+/// Fields covered by the layout v2 checksum, in hashing order
+const CHECKSUM_FIELDS: [&str; 19] = [
+    "year",
+    "grade",
+    "overall_score",
+    "lucky_number",
+    "lucky_hex",
+    "lucky_color",
+    "lucky_bits",
+    "lucky_day_number",
+    "lucky_time",
+    "lucky_power_of_2",
+    "lucky_ascii",
+    "lucky_logic_gate",
+    "lucky_emoji",
+    "lucky_direction",
+    "lucky_element",
+    "lucky_percent",
+    "lucky_latitude",
+    "lucky_longitude",
+    "luck_scores",
+];
+
+/// 12-bit checksum over the decoded field values of a JSON fortune (layout v2).
+/// Works on the serialized document so verifiers need nothing but the JSON.
+pub fn field_checksum(document: &Value) -> u16 {
+    let mut hasher = Sha256::new();
+    for field in CHECKSUM_FIELDS {
+        let value = document.get(field).map(Value::to_string).unwrap_or_default();
+        hasher.update(value.as_bytes());
+        hasher.update(b"\n");
+    }
+    let digest = hasher.finalize();
+    ((digest[0] as u16) << 4) | (digest[1] as u16 >> 4)
+}
+
+/// Check the layout v2 checksum of a JSON fortune against its `entropy_check`
+pub fn verify_checksum(document: &Value) -> Result<(), String> {
+    let expected = format!("0x{:03X}", field_checksum(document));
+    match document.get("entropy_check").and_then(Value::as_str) {
+        Some(actual) if actual == expected => Ok(()),
+        Some(actual) => Err(format!("Checksum mismatch: document has {}, fields give {}", actual, expected)),
+        None => Err("Document has no entropy_check".to_string()),
+    }
+}
+
+/// Display switches for the text renderer
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TextOptions {
+    /// Only the top 5 luck scores
+    pub short: bool,
+    pub show_seed: bool,
+    /// Luck scores as a bar chart
+    pub chart: bool,
+    /// ASCII-only art and frame
+    pub ascii: bool,
+    /// Color the art as a heatmap of visit counts
+    pub color: bool,
+    pub theme: Theme,
+    /// Draw the art and a radar chart as inline images
+    pub graphics: Option<Protocol>,
+    /// Wide layout: mini-art strips next to the lucky number, lucky day and grade
+    pub wide: bool,
+    /// Explain the art glyphs below the art
+    pub legend: bool,
+    /// Zodiac animal of the year above the header
+    pub banner: bool,
+    /// Slip number, score and era year in kanji numerals (Japanese only)
+    pub kanji_numerals: bool,
 }
 
-#[derive(Debug, Serialize)]
-pub struct OmikujiResult {
+/// Per-field mini-art strips, rendered with the simple palette
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize, schemars::JsonSchema)]
+#[cfg_attr(feature = "serve", derive(utoipa::ToSchema))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject), graphql(rename_fields = "snake_case"))]
+pub struct FieldStrips {
+    pub lucky_number: String,
+    pub lucky_day: String,
+    pub grade: String,
+}
+
+impl FieldStrips {
+    fn from_hash(hash: &HashBits) -> Self {
+        let render = |walk: Walk| art::generate_omikuji_art(&walk, ArtPalette::Simple);
+        Self {
+            lucky_number: render(hash.lucky_number_strip()),
+            lucky_day: render(hash.lucky_day_strip()),
+            grade: render(hash.grade_strip()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, schemars::JsonSchema)]
+#[cfg_attr(feature = "serve", derive(utoipa::ToSchema))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject), graphql(rename_fields = "snake_case"))]
+pub struct Fortune {
     pub year: u32,
+    pub grade: Grade,
+    pub overall_score: u8,
+    pub star_rating: String,
+    pub rarity_one_in: u64,
+    pub layout: Layout,
+    #[serde(default)]
+    pub algo: Algo,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub seed: String,
     pub lucky_number: u8,
     pub lucky_hex: String,
@@ -21,6 +150,10 @@ pub struct OmikujiResult {
     pub lucky_bits: String,
     pub lucky_day: String,
     pub lucky_day_number: u16,
+    /// The lucky day as a date, `2026-07-31`
+    pub lucky_date: String,
+    /// Month, day and weekday of the lucky day, `July 31 (Fri)`
+    pub lucky_date_label: String,
     pub lucky_time: String,
     pub lucky_power_of_2: u8,
     pub lucky_ascii: char,
@@ -31,12 +164,30 @@ pub struct OmikujiResult {
     pub lucky_percent: u8,
     pub lucky_latitude: i8,
     pub lucky_longitude: i16,
+    pub lucky_item: String,
+    pub lucky_action: String,
     pub luck_scores: Vec<LuckScore>,
+    pub lang: Lang,
+    pub topics: Vec<TopicVerdict>,
+    pub advice: String,
+    pub poem: Poem,
     pub entropy_check: String,
+    pub art: String,
+    pub art_options: ArtOptions,
+    pub art_walk: Walk,
+    pub field_strips: FieldStrips,
     pub fingerprint: String,
+    #[cfg_attr(feature = "graphql", graphql(name = "fingerprint_sha256"))]
+    pub fingerprint_sha256: String,
+    pub fingerprint_emoji: String,
+    pub fingerprint_words: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub effort: Option<EffortParams>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<Signature>,
 }
 
-impl OmikujiResult {
+impl Fortune {
     pub fn from_hash(hash: &HashBits, year: u32, seed: &str) -> Self {
         let lucky_number = hash.lucky_number();
         let lucky_hex_val = hash.lucky_hex();
@@ -66,17 +217,33 @@ impl OmikujiResult {
             lucky_bits_val & 0xF
         );
 
-        let base_date = NaiveDate::from_ymd_opt(year as i32, 1, 1).unwrap();
-        let lucky_date = base_date + chrono::Duration::days((lucky_day_num - 1) as i64);
-        let lucky_day = format!("{} ({} / 365)", lucky_date.format("%Y-%m-%d"), lucky_day_num);
+        // Years past chrono's range (library callers only) keep the day number alone
+        let lucky_date = date::day_of_year(year, lucky_day_num);
+        let lucky_day = match lucky_date {
+            Some(date) => format!("{} ({} / 365)", date.format("%Y-%m-%d"), lucky_day_num),
+            None => format!("{} / 365", lucky_day_num),
+        };
         let lucky_time = format!("{:02}:{:02}", lucky_hour, lucky_minute);
 
         let luck_scores = calculate_luck_scores(&scores);
+        let overall = overall_score(&luck_scores, &CategoryWeights::default());
+        let grade = Grade::from_bits(hash.grade_bits(), &GradeWeights::default());
+        let top_score = luck_scores.iter().map(|s| s.score).max().unwrap_or(0);
         let entropy_check = format!("0x{:03X}", entropy);
         let fingerprint = hash.hex_string();
+        let fingerprint_sha256 = hash.openssh_fingerprint();
+        let fingerprint_emoji = hash.emoji_fingerprint();
+        let fingerprint_words = hash.pgp_words();
+        let art_walk = hash.art_walk(art::DEFAULT_WIDTH, art::DEFAULT_HEIGHT);
 
         Self {
             year,
+            grade,
+            overall_score: overall,
+            star_rating: star_rating(overall),
+            rarity_one_in: rarity_one_in(grade, &GradeWeights::default(), top_score),
+            layout: Layout::V1,
+            algo: Algo::Sha256,
             seed: hash_seed_for_display(seed),
             lucky_number,
             lucky_hex,
@@ -84,6 +251,8 @@ impl OmikujiResult {
             lucky_bits,
             lucky_day,
             lucky_day_number: lucky_day_num,
+            lucky_date: lucky_date.map(|date| date.format("%Y-%m-%d").to_string()).unwrap_or_default(),
+            lucky_date_label: lucky_date.map(date::label).unwrap_or_default(),
             lucky_time,
             lucky_power_of_2,
             lucky_ascii,
@@ -94,24 +263,200 @@ impl OmikujiResult {
             lucky_percent,
             lucky_latitude,
             lucky_longitude,
+            lucky_item: lucky_item(hash.lucky_item(), Lang::En).to_string(),
+            lucky_action: lucky_action(hash.lucky_action(), Lang::En).to_string(),
             luck_scores,
+            lang: Lang::En,
+            topics: draw_topics(hash, Lang::En),
+            advice: Grammar::embedded(Lang::En).generate(&hash.advice_entropy()),
+            poem: poems::select(hash.poem_index()).clone(),
             entropy_check,
+            art: hash.art(&ArtOptions::default()),
+            art_options: ArtOptions::default(),
+            art_walk,
+            field_strips: FieldStrips::from_hash(hash),
             fingerprint,
+            fingerprint_sha256,
+            fingerprint_emoji,
+            fingerprint_words,
+            effort: None,
+            signature: None,
         }
     }
 
-    pub fn format_text(&self, short: bool, show_seed: bool) -> String {
+    /// Re-draw the grade with custom weights
+    pub fn with_grade_weights(mut self, hash: &HashBits, weights: &GradeWeights) -> Self {
+        self.grade = Grade::from_bits(hash.grade_bits(), weights);
+        self.rarity_one_in = rarity_one_in(self.grade, weights, self.top_score());
+        self
+    }
+
+    fn top_score(&self) -> u8 {
+        self.luck_scores.iter().map(|s| s.score).max().unwrap_or(0)
+    }
+
+    /// Recompute the overall score with custom category weights
+    pub fn with_category_weights(mut self, weights: &CategoryWeights) -> Self {
+        self.overall_score = overall_score(&self.luck_scores, weights);
+        self.star_rating = star_rating(self.overall_score);
+        self
+    }
+
+    /// Replace the display labels of relabeled categories
+    pub fn with_category_labels(mut self, labels: &HashMap<LuckType, String>) -> Self {
+        for score in self.luck_scores.iter_mut() {
+            if let Some(label) = labels.get(&score.luck_type) {
+                score.label = label.clone().into();
+            }
+        }
+        self
+    }
+
+    /// Render human-readable sections (topics, lucky item/action, advice) in the given language
+    pub fn with_lang(mut self, hash: &HashBits, lang: Lang) -> Self {
+        if lang == self.lang {
+            return self;
+        }
+        self.lang = lang;
+        self.topics = draw_topics(hash, lang);
+        self.lucky_item = lucky_item(hash.lucky_item(), lang).to_string();
+        self.lucky_action = lucky_action(hash.lucky_action(), lang).to_string();
+        self.advice = Grammar::embedded(lang).generate(&hash.advice_entropy());
+        self
+    }
+
+    /// Compose the advice text with a custom grammar
+    pub fn with_advice_grammar(mut self, hash: &HashBits, grammar: &Grammar) -> Self {
+        self.advice = grammar.generate(&hash.advice_entropy());
+        self
+    }
+
+    /// Add the entries of a user corpus to the lucky item and action, topic phrase,
+    /// poem and advice tables, and draw them again
+    pub fn with_corpus(mut self, hash: &HashBits, corpus: &Corpus) -> Self {
+        let lang = self.lang;
+        if let Some(item) = corpus.lucky_item(hash, lang) {
+            self.lucky_item = item;
+        }
+        if let Some(action) = corpus.lucky_action(hash, lang) {
+            self.lucky_action = action;
+        }
+        for (i, verdict) in self.topics.iter_mut().enumerate() {
+            if let Some(text) = corpus.topic_phrase(hash, i, lang) {
+                verdict.text = text.into();
+            }
+        }
+        if let Some(poem) = corpus.poem(hash) {
+            self.poem = poem;
+        }
+        if let Some(grammar) = corpus.advice(lang) {
+            self.advice = grammar.generate(&hash.advice_entropy());
+        }
+        self
+    }
+
+    /// Redraw the art with another board size, glyph palette or mode
+    pub fn with_art(mut self, hash: &HashBits, options: &ArtOptions) -> Self {
+        if *options == self.art_options {
+            return self;
+        }
+        self.art_walk = hash.art_walk(options.width, options.height);
+        self.art = hash.art(options);
+        self.art_options = *options;
+        self
+    }
+
+    /// Switch bit layout; v2 replaces the raw entropy bits with a field checksum
+    pub fn with_layout(mut self, hash: &HashBits, layout: Layout) -> Self {
+        if layout == Layout::V1 && self.layout == Layout::V1 {
+            return self;
+        }
+        self.layout = layout;
+        self.entropy_check = match layout {
+            Layout::V1 => format!("0x{:03X}", hash.entropy_check()),
+            Layout::V2 => format!("0x{:03X}", field_checksum(&serde_json::to_value(&self).unwrap())),
+        };
+        self
+    }
+
+    /// Drop the seed-derived device id so only fingerprints are ever shown
+    pub fn anonymized(mut self) -> Self {
+        self.seed.clear();
+        self
+    }
+
+    /// Re-encode the fingerprint field (hex by default)
+    pub fn with_fingerprint_format(mut self, hash: &HashBits, format: FingerprintFormat) -> Self {
+        self.fingerprint = hash.fingerprint(format);
+        self
+    }
+
+    /// Record the hash function the digest was derived with
+    pub fn with_algo(mut self, algo: Algo) -> Self {
+        self.algo = algo;
+        self
+    }
+
+    /// Record the Argon2 effort parameters used to stretch the seed
+    pub fn with_effort(mut self, params: EffortParams) -> Self {
+        self.effort = Some(params);
+        self
+    }
+
+    /// Attach an Ed25519 signature over the canonical JSON of this result
+    pub fn with_signature(mut self, key: &ed25519_dalek::SigningKey) -> Self {
+        self.signature = None;
+        let document = serde_json::to_value(&self).unwrap();
+        self.signature = Some(sign::sign(key, &document));
+        self
+    }
+
+    /// The year for display, with the era year in Japanese: `2026 (令和8年)`
+    pub fn year_label(&self) -> String {
+        match (self.lang, wareki::format_ja(self.year)) {
+            (Lang::Ja, Some(era)) => format!("{} ({})", self.year, era),
+            _ => self.year.to_string(),
+        }
+    }
+
+    /// The lucky day for display: `July 31 (Fri), day 212`
+    pub fn lucky_day_text(&self) -> String {
+        format!("{}, day {}", self.lucky_date_label, self.lucky_day_number)
+    }
+
+    pub fn format_text(&self, options: &TextOptions) -> String {
         let mut output = String::new();
 
-        output.push_str(&format!("🎍 Hash-Omikuji {} 🎍\n\n", self.year));
+        if options.banner {
+            let palette = options.theme.palette();
+            let eto = eto::of_year(self.year);
+            output.push_str(&format!("{}\n", eto.banner(self.lang, options.ascii, options.color.then_some(&palette))));
+        }
+        // Kanji numerals number the slip by its lucky number, as on a shrine's printed slips
+        let kanji = options.kanji_numerals && self.lang == Lang::Ja;
+        let (year_label, slip, score) = if kanji {
+            let era = wareki::format_ja(self.year).map(|era| kansuji::in_text(&era));
+            (
+                era.map_or(self.year.to_string(), |era| format!("{} ({})", self.year, era)),
+                format!("{} ", kansuji::slip_number(self.lucky_number as u64)),
+                kansuji::points(self.overall_score as u64),
+            )
+        } else {
+            (self.year_label(), String::new(), self.overall_score.to_string())
+        };
+        output.push_str(&format!("🎍 Hash-Omikuji {} 🎍\n\n", year_label));
+        let strip = |strip: &str| if options.wide { format!("  [{}]", strip) } else { String::new() };
+        output.push_str(&format!("{}{} ({:#}){}\n", slip, self.grade, self.grade, strip(&self.field_strips.grade)));
+        output.push_str(&format!("Overall Score     : {} {}\n", score, self.star_rating));
+        output.push_str(&format!("Rarity            : {}\n\n", format_rarity(self.rarity_one_in)));
 
-        output.push_str(&format!("Lucky Number      : {}\n", self.lucky_number));
+        output.push_str(&format!("Lucky Number      : {}{}\n", self.lucky_number, strip(&self.field_strips.lucky_number)));
         output.push_str(&format!("Lucky Hex         : {}\n", self.lucky_hex));
         output.push_str(&format!("Lucky Color       : {}\n", self.lucky_color));
         output.push_str(&format!("Lucky Bits        : {}\n", self.lucky_bits));
         output.push('\n');
 
-        output.push_str(&format!("Lucky Day         : {}\n", self.lucky_day));
+        output.push_str(&format!("Lucky Day         : {}{}\n", self.lucky_day_text(), strip(&self.field_strips.lucky_day)));
         output.push_str(&format!("Lucky Time        : {}\n", self.lucky_time));
         output.push('\n');
 
@@ -123,30 +468,108 @@ impl OmikujiResult {
         output.push_str(&format!("Lucky Element     : {}\n", self.lucky_element));
         output.push_str(&format!("Lucky Percent     : {}%\n", self.lucky_percent));
         output.push_str(&format!("Lucky Location    : {}°, {}°\n", self.lucky_latitude, self.lucky_longitude));
+        output.push_str(&format!("Lucky Item        : {}\n", self.lucky_item));
+        output.push_str(&format!("Lucky Action      : {}\n", self.lucky_action));
         output.push('\n');
 
-        output.push_str("Luck Scores :\n");
+        output.push_str(&format!("Luck Scores : {}\n", sparkline(&self.luck_scores)));
         let mut sorted_scores: Vec<_> = self.luck_scores.iter().collect();
-        sorted_scores.sort_by(|a, b| b.score.cmp(&a.score));
+        sorted_scores.sort_by_key(|s| std::cmp::Reverse(s.score));
 
-        let display_count = if short { 5 } else { sorted_scores.len() };
+        let display_count = if options.short { 5 } else { sorted_scores.len() };
         for score in sorted_scores.iter().take(display_count) {
+            if options.chart {
+                output.push_str(&format!("{:18}: {} {:3}\n", score.label, score_bar(score.score, 20), score.score));
+            } else {
+                output.push_str(&format!(
+                    "{:18}: {:3} ({})\n",
+                    score.label,
+                    score.score,
+                    score.rank.as_str()
+                ));
+            }
+        }
+        if let Some(protocol) = options.graphics {
+            if let Ok(image) = graphics::encode(&card::radar_canvas(&self.luck_scores, &options.theme.palette()), protocol) {
+                output.push_str(&image);
+            }
+        }
+        output.push('\n');
+        let heading = match self.lang {
+            Lang::Ja => "運勢 :",
+            Lang::En => "Topics :",
+        };
+        output.push_str(heading);
+        output.push('\n');
+        for verdict in &self.topics {
+            let pad = 18usize.saturating_sub(display_width(&verdict.name));
+            output.push_str(&format!("{}{}: {}\n", verdict.name, " ".repeat(pad), verdict.text));
+        }
+
+        let heading = match self.lang {
+            Lang::Ja => "御言葉",
+            Lang::En => "Advice",
+        };
+        output.push_str(&format!("\n{} :\n{}\n", heading, self.advice));
+
+        let heading = match self.lang {
+            Lang::Ja => "和歌",
+            Lang::En => "Poem",
+        };
+        let author = match self.lang {
+            Lang::Ja => &self.poem.author,
+            Lang::En => &self.poem.author_romaji,
+        };
+        output.push_str(&format!(
+            "\n{} :\n{}\n{}\n\"{}\" — {}\n",
+            heading, self.poem.text, self.poem.romaji, self.poem.gloss, author
+        ));
+
+        if options.show_seed {
+            let seed_prefix = if self.seed.is_empty() { String::new() } else { format!("{} | ", self.seed) };
             output.push_str(&format!(
-                "{:18}: {:3} ({})\n",
-                score.luck_type.name(),
-                score.score,
-                score.rank.as_str()
+                "\n{}{}\n{}\n{}\n",
+                seed_prefix,
+                self.entropy_check,
+                self.fingerprint_sha256,
+                self.fingerprint_words
             ));
         }
-        if show_seed {
+
+        let image = options.graphics.filter(|_| matches!(self.art_options.mode, ArtMode::Randomart | ArtMode::Emoji)).and_then(|protocol| {
+            graphics::encode(&card::art_canvas(&self.art_walk, &options.theme.palette()), protocol).ok()
+        });
+        if let Some(image) = image {
+            output.push_str(&format!("\n{}", image));
+        } else if self.art_options.mode == ArtMode::Emoji {
+            output.push_str(&format!("\n{}\n", self.art));
+        } else {
+            let art = if options.color && self.art_options.mode == ArtMode::Randomart {
+                art::heatmap(&self.art, &self.art_walk, &options.theme.palette().ramp)
+            } else {
+                self.art.clone()
+            };
+            output.push_str(&format!("\n{}", art::framed(&art, self.year, self.grade, options.ascii)));
+        }
+        if options.legend {
+            output.push_str(&format!("{}\n", art::legend(&self.art_options)));
+        }
+        output.push_str(&format!("{}\n", self.fingerprint_emoji));
+        output.push_str(&format!("[raw hash: {}]\n", self.fingerprint));
+        if let Some(ref effort) = self.effort {
             output.push_str(&format!(
-                "\n{} | {}\n",
-                self.seed,
-                self.entropy_check
+                "[effort: {} level {} (m={}KiB t={} p={} x{})]\n",
+                effort.algorithm,
+                effort.level,
+                effort.memory_kib,
+                effort.iterations,
+                effort.parallelism,
+                effort.rounds
             ));
         }
-
-        output.push_str(&format!("\n[raw hash: {}]\n", self.fingerprint));
+        if let Some(ref signature) = self.signature {
+            output.push_str(&format!("[{}: {}]\n", signature.algorithm, signature.value));
+        }
 
         output
     }
@@ -154,15 +577,55 @@ impl OmikujiResult {
     pub fn format_json(&self) -> String {
         serde_json::to_string_pretty(self).unwrap()
     }
+
+    /// GitHub-flavored markdown: headline, lucky fields and luck scores as tables, the art in a code block
+    pub fn format_markdown(&self) -> String {
+        let mut output = format!("## 🎍 Hash-Omikuji {} 🎍\n\n", self.year_label());
+        output.push_str(&format!(
+            "**{}** ({:#}) · Overall Score {} {} · {}\n\n",
+            self.grade,
+            self.grade,
+            self.overall_score,
+            self.star_rating,
+            format_rarity(self.rarity_one_in)
+        ));
+        output.push_str("| Lucky | |\n|---|---|\n");
+        let fields = [
+            ("Number", self.lucky_number.to_string()),
+            ("Hex", format!("`{}`", self.lucky_hex)),
+            ("Color", format!("`{}`", self.lucky_color)),
+            ("Day", self.lucky_day_text()),
+            ("Time", self.lucky_time.clone()),
+            ("Emoji", self.lucky_emoji.clone()),
+            ("Direction", self.lucky_direction.clone()),
+            ("Item", self.lucky_item.clone()),
+            ("Action", self.lucky_action.clone()),
+        ];
+        for (name, value) in fields {
+            output.push_str(&format!("| {} | {} |\n", name, value));
+        }
+
+        output.push_str("\n| Luck | Score | Rank |\n|---|---:|---|\n");
+        let mut sorted_scores: Vec<_> = self.luck_scores.iter().collect();
+        sorted_scores.sort_by_key(|s| std::cmp::Reverse(s.score));
+        for score in sorted_scores {
+            output.push_str(&format!("| {} | {} | {} |\n", score.label, score.score, score.rank.as_str()));
+        }
+
+        output.push_str(&format!("\n> {}\n\n", self.advice));
+        output.push_str(&format!("```\n{}```\n\n", art::framed(&self.art, self.year, self.grade, false)));
+        output.push_str(&format!("`{}`\n", self.fingerprint));
+        output
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    fn create_test_result() -> OmikujiResult {
+    fn create_test_result() -> Fortune {
         let hash = HashBits::from_seed(2026, "test-user");
-        OmikujiResult::from_hash(&hash, 2026, "test-user")
+        Fortune::from_hash(&hash, 2026, "test-user")
     }
 
     #[test]
@@ -218,7 +681,7 @@ mod tests {
     #[test]
     fn test_lucky_day_number_range() {
         let result = create_test_result();
-        assert!(result.lucky_day_number >= 1 && result.lucky_day_number <= 365);
+        assert!((1..=365).contains(&result.lucky_day_number));
     }
 
     #[test]
@@ -247,18 +710,299 @@ mod tests {
         assert_eq!(result.fingerprint.len(), 64);
     }
 
+    #[test]
+    fn test_fingerprint_format_override() {
+        let hash = HashBits::from_seed(2026, "test-user");
+        let result = Fortune::from_hash(&hash, 2026, "test-user")
+            .with_fingerprint_format(&hash, FingerprintFormat::Base64url);
+        assert_eq!(result.fingerprint.len(), 43);
+        assert!(result.format_text(&TextOptions::default()).contains(&result.fingerprint));
+    }
+
+    #[test]
+    fn test_fingerprint_emoji() {
+        let result = create_test_result();
+        assert_eq!(result.fingerprint_emoji.split(' ').count(), 8);
+        assert!(result.format_text(&TextOptions::default()).contains(&result.fingerprint_emoji));
+    }
+
+    #[test]
+    fn test_signed_json_verifies() {
+        let key = ed25519_dalek::SigningKey::from_bytes(&[1u8; 32]);
+        let result = create_test_result().with_signature(&key);
+        let document: serde_json::Value = serde_json::from_str(&result.format_json()).unwrap();
+        assert!(sign::verify(&document, Some(&key.verifying_key())).is_ok());
+        assert!(result.format_text(&TextOptions::default()).contains("[ed25519: "));
+    }
+
+    #[test]
+    fn test_effort_recorded() {
+        let result = create_test_result();
+        assert!(!result.format_json().contains("\"effort\""));
+        let result = result.with_effort(EffortParams::for_level(2));
+        assert!(result.format_json().contains("\"argon2id\""));
+        assert!(result.format_text(&TextOptions::default()).contains("[effort: argon2id level 2"));
+    }
+
+    #[test]
+    fn test_layout_v2_checksum_verifies() {
+        let hash = HashBits::from_seed(2026, "test-user");
+        let result = Fortune::from_hash(&hash, 2026, "test-user").with_layout(&hash, Layout::V2);
+        let document: Value = serde_json::from_str(&result.format_json()).unwrap();
+        assert_eq!(document["layout"], "v2");
+        assert!(verify_checksum(&document).is_ok());
+    }
+
+    #[test]
+    fn test_layout_v2_checksum_detects_transcription_error() {
+        let hash = HashBits::from_seed(2026, "test-user");
+        let result = Fortune::from_hash(&hash, 2026, "test-user").with_layout(&hash, Layout::V2);
+        let mut document: Value = serde_json::from_str(&result.format_json()).unwrap();
+        document["lucky_number"] = serde_json::json!(result.lucky_number.wrapping_add(1));
+        assert!(verify_checksum(&document).is_err());
+    }
+
+    #[test]
+    fn test_layout_v1_keeps_raw_entropy() {
+        let hash = HashBits::from_seed(2026, "test-user");
+        let result = Fortune::from_hash(&hash, 2026, "test-user").with_layout(&hash, Layout::V1);
+        assert_eq!(result.entropy_check, format!("0x{:03X}", hash.entropy_check()));
+    }
+
+    #[test]
+    fn test_grade_headlines_outputs() {
+        let result = create_test_result();
+        let text = result.format_text(&TextOptions::default());
+        let first_lines: Vec<&str> = text.lines().take(3).collect();
+        assert!(first_lines[2].starts_with(result.grade.kanji()));
+        let json = result.format_json();
+        assert!(json.find("\"grade\"").unwrap() < json.find("\"lucky_number\"").unwrap());
+    }
+
+    #[test]
+    fn test_grade_weights_override() {
+        let hash = HashBits::from_seed(2026, "test-user");
+        let weights: GradeWeights = "0,0,0,0,0,0,1".parse().unwrap();
+        let result = Fortune::from_hash(&hash, 2026, "test-user").with_grade_weights(&hash, &weights);
+        assert_eq!(result.grade, Grade::Daikyo);
+    }
+
+    #[test]
+    fn test_overall_score_and_stars() {
+        let result = create_test_result();
+        assert!(result.overall_score <= 100);
+        assert_eq!(result.star_rating.chars().count(), 5);
+        assert!(result.format_text(&TextOptions::default()).contains(&result.star_rating));
+        assert!(result.format_json().contains("\"overall_score\""));
+    }
+
+    #[test]
+    fn test_category_weights_override() {
+        let result = create_test_result();
+        let mut weights = [0u32; 16];
+        weights[0] = 1;
+        let life_score = result.luck_scores[0].score;
+        let result = result.with_category_weights(&CategoryWeights(weights));
+        assert_eq!(result.overall_score, life_score);
+    }
+
+    #[test]
+    fn test_rarity_shown() {
+        let result = create_test_result();
+        assert!(result.rarity_one_in >= 1);
+        assert!(result.format_text(&TextOptions::default()).contains("Rarity            : roughly 1 in "));
+        assert!(result.format_json().contains("\"rarity_one_in\""));
+    }
+
+    #[test]
+    fn test_category_labels_override() {
+        let labels = HashMap::from([(LuckType::Study, "OSS contributions".to_string())]);
+        let result = create_test_result().with_category_labels(&labels);
+        let text = result.format_text(&TextOptions::default());
+        assert!(text.contains("OSS contributions"));
+        assert!(!text.contains("Study Luck"));
+        assert!(result.format_json().contains("\"label\": \"OSS contributions\""));
+    }
+
+    #[test]
+    fn test_topics_section() {
+        let hash = HashBits::from_seed(2026, "test-user");
+        let result = Fortune::from_hash(&hash, 2026, "test-user");
+        assert_eq!(result.topics.len(), 12);
+        assert!(result.format_text(&TextOptions::default()).contains("Topics :\nWish              : "));
+
+        let result = result.with_lang(&hash, Lang::Ja);
+        let text = result.format_text(&TextOptions::default());
+        assert!(text.contains("運勢 :\n願望              : "));
+        assert!(text.starts_with("🎍 Hash-Omikuji 2026 (令和8年) 🎍\n"));
+        assert!(result.format_json().contains("\"lang\": \"ja\""));
+    }
+
+    #[test]
+    fn test_lucky_item_and_action() {
+        let hash = HashBits::from_seed(2026, "test-user");
+        let result = Fortune::from_hash(&hash, 2026, "test-user");
+        let text = result.format_text(&TextOptions::default());
+        assert!(text.contains(&format!("Lucky Item        : {}\n", result.lucky_item)));
+        assert!(text.contains(&format!("Lucky Action      : {}\n", result.lucky_action)));
+
+        let ja = Fortune::from_hash(&hash, 2026, "test-user").with_lang(&hash, Lang::Ja);
+        assert_ne!(ja.lucky_item, result.lucky_item);
+        assert_eq!(ja.lucky_number, result.lucky_number);
+    }
+
+    #[test]
+    fn test_advice_text() {
+        let hash = HashBits::from_seed(2026, "test-user");
+        let result = Fortune::from_hash(&hash, 2026, "test-user");
+        assert!(!result.advice.is_empty());
+        assert!(result.format_text(&TextOptions::default()).contains(&format!("Advice :\n{}\n", result.advice)));
+
+        let grammar = Grammar::parse("templates = [\"fixed advice.\"]\n").unwrap();
+        let result = result.with_advice_grammar(&hash, &grammar);
+        assert_eq!(result.advice, "Fixed advice.");
+    }
+
+    #[test]
+    fn test_poem_section() {
+        let result = create_test_result();
+        let text = result.format_text(&TextOptions::default());
+        assert!(text.contains(&format!("Poem :\n{}\n{}\n", result.poem.text, result.poem.romaji)));
+        assert!(text.contains(&*result.poem.author_romaji));
+        assert!(result.format_json().contains("\"gloss\""));
+    }
+
+    #[test]
+    fn test_score_chart() {
+        let result = create_test_result();
+        let text = result.format_text(&TextOptions::default());
+        assert!(text.contains(&format!("Luck Scores : {}\n", sparkline(&result.luck_scores))));
+
+        let chart = result.format_text(&TextOptions { chart: true, ..Default::default() });
+        let best = result.luck_scores.iter().max_by_key(|s| s.score).unwrap();
+        assert!(chart.contains(&format!("{:18}: {} {:3}\n", best.label, score_bar(best.score, 20), best.score)));
+        assert!(!chart.contains("(Excellent)") && !chart.contains("(Bad)"));
+    }
+
+    #[test]
+    fn test_art_above_emoji_fingerprint() {
+        let hash = HashBits::from_seed(2026, "test-user");
+        let result = Fortune::from_hash(&hash, 2026, "test-user");
+        assert_eq!(result.art.chars().count(), 16);
+        let framed = art::framed(&result.art, 2026, result.grade, false);
+        assert!(result.format_text(&TextOptions::default()).contains(&format!("\n{}{}\n", framed, result.fingerprint_emoji)));
+
+        let options = ArtOptions { width: 17, height: 9, ..Default::default() };
+        let result = result.with_art(&hash, &options);
+        assert_eq!(result.art.lines().count(), 9);
+
+        let maze = result.with_art(&hash, &ArtOptions { mode: ArtMode::Maze, ..options });
+        let text = maze.format_text(&TextOptions { color: true, ..Default::default() });
+        assert!(text.contains(&art::framed(&maze.art, 2026, maze.grade, false)));
+
+        let result = maze.with_art(&hash, &ArtOptions { mode: ArtMode::Emoji, ..options });
+        let text = result.format_text(&TextOptions { color: true, ..Default::default() });
+        assert!(text.contains(&format!("\n{}\n{}\n", result.art, result.fingerprint_emoji)));
+        assert!(!text.contains("+-[hash-omikuji"));
+    }
+
+    #[test]
+    fn test_art_heatmap_only_with_color() {
+        let result = create_test_result();
+        assert!(!result.format_text(&TextOptions::default()).contains("\x1b["));
+        let colored = result.format_text(&TextOptions { color: true, ..Default::default() });
+        assert!(colored.contains("\x1b[48;2;"));
+    }
+
+    #[test]
+    fn test_graphics_replace_text_art() {
+        let result = create_test_result();
+        let text = result.format_text(&TextOptions { graphics: Some(Protocol::Kitty), ..Default::default() });
+        assert_eq!(text.matches("\x1b_Gf=100,a=T").count(), 2);
+        assert!(!text.contains("+-[hash-omikuji"));
+
+        let text = result.format_text(&TextOptions { graphics: Some(Protocol::Sixel), ..Default::default() });
+        assert_eq!(text.matches("\x1bPq").count(), 2);
+    }
+
+    #[test]
+    fn test_wide_layout_strips() {
+        let result = create_test_result();
+        assert!(!result.format_text(&TextOptions::default()).contains(&format!("[{}]", result.field_strips.lucky_number)));
+
+        let text = result.format_text(&TextOptions { wide: true, ..Default::default() });
+        let number_line = format!("Lucky Number      : {}  [{}]\n", result.lucky_number, result.field_strips.lucky_number);
+        assert!(text.contains(&number_line));
+        assert!(text.contains(&format!("  [{}]\n", result.field_strips.lucky_day)));
+        assert!(text.contains(&format!("({:#})  [{}]\n", result.grade, result.field_strips.grade)));
+        assert_eq!(result.field_strips.grade.chars().count(), art::STRIP_WIDTH);
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let hash = HashBits::from_seed(2026, "test-user");
+        let result = create_test_result()
+            .with_art(&hash, &ArtOptions { mode: ArtMode::Maze, height: 9, ..Default::default() })
+            .with_effort(EffortParams::for_level(1))
+            .with_layout(&hash, Layout::V2);
+        let parsed: Fortune = serde_json::from_str(&result.format_json()).unwrap();
+        assert_eq!(parsed, result);
+        assert_eq!(parsed.format_text(&TextOptions::default()), result.format_text(&TextOptions::default()));
+    }
+
+    #[test]
+    fn test_art_metadata_and_legend() {
+        let result = create_test_result();
+        let json: Value = serde_json::from_str(&result.format_json()).unwrap();
+        assert_eq!(json["art_walk"]["steps"], 256);
+        assert_eq!(json["art_walk"]["visits"].as_array().unwrap().len(), 16);
+        assert_eq!(json["art_options"]["mode"], "randomart");
+
+        let legend = art::legend(&result.art_options);
+        assert!(!result.format_text(&TextOptions::default()).contains(&legend));
+        let text = result.format_text(&TextOptions { legend: true, ..Default::default() });
+        assert!(text.contains(&format!("+\n{}\n{}\n", legend, result.fingerprint_emoji)));
+    }
+
+    #[test]
+    fn test_eto_banner() {
+        let result = create_test_result();
+        let plain = result.format_text(&TextOptions::default());
+        let text = result.format_text(&TextOptions { banner: true, ..Default::default() });
+        let banner = eto::of_year(result.year).banner(Lang::En, false, None);
+        assert_eq!(text, format!("{}\n{}", banner, plain));
+
+        let ascii = result.format_text(&TextOptions { banner: true, ascii: true, ..Default::default() });
+        assert!(ascii.lines().nth(1).unwrap().ends_with("Year of the Horse (uma)"));
+    }
+
+    #[test]
+    fn test_kanji_numerals() {
+        let hash = HashBits::from_seed(2026, "test-user");
+        let result = Fortune::from_hash(&hash, 2026, "test-user");
+        let options = TextOptions { kanji_numerals: true, ..Default::default() };
+        // English output keeps its digits
+        assert_eq!(result.format_text(&options), result.format_text(&TextOptions::default()));
+
+        let ja = result.with_lang(&hash, Lang::Ja);
+        let text = ja.format_text(&options);
+        assert!(text.starts_with("🎍 Hash-Omikuji 2026 (令和八年) 🎍\n"));
+        assert!(text.contains(&format!("\n{} {} (", kansuji::slip_number(ja.lucky_number as u64), ja.grade)));
+        assert!(text.contains(&format!("Overall Score     : {} ", kansuji::points(ja.overall_score as u64))));
+    }
+
     #[test]
     fn test_format_text_contains_header() {
         let result = create_test_result();
-        let text = result.format_text(false, false);
+        let text = result.format_text(&TextOptions::default());
         assert!(text.contains("Hash-Omikuji 2026"));
     }
 
     #[test]
     fn test_format_text_short_mode() {
         let result = create_test_result();
-        let text_full = result.format_text(false, false);
-        let text_short = result.format_text(true, false);
+        let text_full = result.format_text(&TextOptions::default());
+        let text_short = result.format_text(&TextOptions { short: true, ..Default::default() });
         // Short mode should be shorter or equal
         assert!(text_short.len() <= text_full.len());
     }
@@ -266,11 +1010,24 @@ mod tests {
     #[test]
     fn test_format_text_show_seed() {
         let result = create_test_result();
-        let text_with_seed = result.format_text(false, true);
-        let text_without_seed = result.format_text(false, false);
+        let text_with_seed = result.format_text(&TextOptions { show_seed: true, ..Default::default() });
+        let text_without_seed = result.format_text(&TextOptions::default());
         assert!(text_with_seed.contains("device:"));
         assert!(text_with_seed.contains("|"));
         assert!(!text_without_seed.contains("device:"));
+        assert!(text_with_seed.contains("SHA256:"));
+        assert!(!text_without_seed.contains("SHA256:"));
+        let result = create_test_result();
+        assert!(text_with_seed.contains(&result.fingerprint_words));
+    }
+
+    #[test]
+    fn test_anonymized_hides_seed() {
+        let result = create_test_result().anonymized();
+        let text = result.format_text(&TextOptions { show_seed: true, ..Default::default() });
+        assert!(!text.contains("device:"));
+        assert!(text.contains("SHA256:"));
+        assert!(!result.format_json().contains("\"seed\""));
     }
 
     #[test]
@@ -302,7 +1059,7 @@ mod tests {
     #[test]
     fn test_lucky_ascii() {
         let result = create_test_result();
-        assert!(result.lucky_ascii >= ' ' && result.lucky_ascii <= '~');
+        assert!((' '..='~').contains(&result.lucky_ascii));
     }
 
     #[test]
@@ -345,22 +1102,28 @@ mod tests {
     #[test]
     fn test_lucky_latitude() {
         let result = create_test_result();
-        assert!(result.lucky_latitude >= -90 && result.lucky_latitude <= 90);
+        assert!((-90..=90).contains(&result.lucky_latitude));
     }
 
     #[test]
     fn test_lucky_longitude() {
         let result = create_test_result();
-        assert!(result.lucky_longitude >= -180 && result.lucky_longitude <= 180);
+        assert!((-180..=180).contains(&result.lucky_longitude));
     }
 
     #[test]
     fn test_snapshot_text_output() {
         let result = create_test_result();
-        let text = result.format_text(false, true);
+        let text = result.format_text(&TextOptions { show_seed: true, ..Default::default() });
         insta::assert_snapshot!(text);
     }
 
+    #[test]
+    fn test_snapshot_markdown_output() {
+        let result = create_test_result();
+        insta::assert_snapshot!(result.format_markdown());
+    }
+
     #[test]
     fn test_snapshot_json_output() {
         let result = create_test_result();
@@ -0,0 +1,169 @@
+//! Embedded waka corpus: poems from the Hyakunin Isshu (public domain, 7th-13th c.)
+//! with romaji and a short English gloss. Order is part of the output format.
+
+use alloc::borrow::Cow;
+use serde::{Deserialize, Serialize};
+
+/// A poem, from the embedded corpus or a user corpus (see `corpus`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "serve", derive(utoipa::ToSchema))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject), graphql(rename_fields = "snake_case"))]
+#[cfg_attr(feature = "std", derive(schemars::JsonSchema))]
+pub struct Poem {
+    pub text: Cow<'static, str>,
+    pub romaji: Cow<'static, str>,
+    pub gloss: Cow<'static, str>,
+    pub author: Cow<'static, str>,
+    pub author_romaji: Cow<'static, str>,
+}
+
+pub const POEMS: [Poem; 16] = [
+    Poem {
+        text: Cow::Borrowed("秋の田の かりほの庵の 苫をあらみ わが衣手は 露にぬれつつ"),
+        romaji: Cow::Borrowed("Aki no ta no / kariho no io no / toma o arami / waga koromode wa / tsuyu ni nuretsutsu"),
+        gloss: Cow::Borrowed("The rough thatch of the harvest hut lets the dew through; my sleeves are wet with it."),
+        author: Cow::Borrowed("天智天皇"),
+        author_romaji: Cow::Borrowed("Emperor Tenji"),
+    },
+    Poem {
+        text: Cow::Borrowed("春過ぎて 夏来にけらし 白妙の 衣ほすてふ 天の香具山"),
+        romaji: Cow::Borrowed("Haru sugite / natsu kinikerashi / shirotae no / koromo hosu chō / ama no Kaguyama"),
+        gloss: Cow::Borrowed("Spring has passed and summer come, it seems: white robes are drying on heavenly Mount Kagu."),
+        author: Cow::Borrowed("持統天皇"),
+        author_romaji: Cow::Borrowed("Empress Jitō"),
+    },
+    Poem {
+        text: Cow::Borrowed("あしびきの 山鳥の尾の しだり尾の ながながし夜を ひとりかも寝む"),
+        romaji: Cow::Borrowed("Ashibiki no / yamadori no o no / shidario no / naganagashi yo o / hitori ka mo nen"),
+        gloss: Cow::Borrowed("Long as the trailing tail of the mountain pheasant, this long night must I sleep alone?"),
+        author: Cow::Borrowed("柿本人麻呂"),
+        author_romaji: Cow::Borrowed("Kakinomoto no Hitomaro"),
+    },
+    Poem {
+        text: Cow::Borrowed("田子の浦に うち出でてみれば 白妙の 富士の高嶺に 雪は降りつつ"),
+        romaji: Cow::Borrowed("Tago no ura ni / uchiidete mireba / shirotae no / Fuji no takane ni / yuki wa furitsutsu"),
+        gloss: Cow::Borrowed("Coming out on Tago Bay I look: on Fuji's lofty peak the white snow keeps falling."),
+        author: Cow::Borrowed("山部赤人"),
+        author_romaji: Cow::Borrowed("Yamabe no Akahito"),
+    },
+    Poem {
+        text: Cow::Borrowed("天の原 ふりさけ見れば 春日なる 三笠の山に 出でし月かも"),
+        romaji: Cow::Borrowed("Ama no hara / furisake mireba / Kasuga naru / Mikasa no yama ni / ideshi tsuki ka mo"),
+        gloss: Cow::Borrowed("Gazing across the plain of heaven: is that the moon that rose over Mount Mikasa at home?"),
+        author: Cow::Borrowed("安倍仲麿"),
+        author_romaji: Cow::Borrowed("Abe no Nakamaro"),
+    },
+    Poem {
+        text: Cow::Borrowed("花の色は 移りにけりな いたづらに わが身世にふる ながめせしまに"),
+        romaji: Cow::Borrowed("Hana no iro wa / utsurinikeri na / itazura ni / waga mi yo ni furu / nagame seshi ma ni"),
+        gloss: Cow::Borrowed("The blossoms' color has faded in vain while I gazed out at the long rains of this world."),
+        author: Cow::Borrowed("小野小町"),
+        author_romaji: Cow::Borrowed("Ono no Komachi"),
+    },
+    Poem {
+        text: Cow::Borrowed("これやこの 行くも帰るも 別れては 知るも知らぬも 逢坂の関"),
+        romaji: Cow::Borrowed("Kore ya kono / yuku mo kaeru mo / wakarete wa / shiru mo shiranu mo / Ausaka no seki"),
+        gloss: Cow::Borrowed("So this is where those going and returning part, friends and strangers alike: the Barrier of Meeting Hill."),
+        author: Cow::Borrowed("蝉丸"),
+        author_romaji: Cow::Borrowed("Semimaru"),
+    },
+    Poem {
+        text: Cow::Borrowed("久方の 光のどけき 春の日に しづ心なく 花の散るらむ"),
+        romaji: Cow::Borrowed("Hisakata no / hikari nodokeki / haru no hi ni / shizugokoro naku / hana no chiruramu"),
+        gloss: Cow::Borrowed("On a spring day of such calm light, why do the blossoms scatter with restless hearts?"),
+        author: Cow::Borrowed("紀友則"),
+        author_romaji: Cow::Borrowed("Ki no Tomonori"),
+    },
+    Poem {
+        text: Cow::Borrowed("人はいさ 心も知らず ふるさとは 花ぞ昔の 香ににほひける"),
+        romaji: Cow::Borrowed("Hito wa isa / kokoro mo shirazu / furusato wa / hana zo mukashi no / ka ni nioikeru"),
+        gloss: Cow::Borrowed("People's hearts I cannot know, but in my old village the blossoms smell as sweet as long ago."),
+        author: Cow::Borrowed("紀貫之"),
+        author_romaji: Cow::Borrowed("Ki no Tsurayuki"),
+    },
+    Poem {
+        text: Cow::Borrowed("夏の夜は まだ宵ながら 明けぬるを 雲のいづこに 月宿るらむ"),
+        romaji: Cow::Borrowed("Natsu no yo wa / mada yoi nagara / akenuru o / kumo no izuko ni / tsuki yadoruramu"),
+        gloss: Cow::Borrowed("The summer night has dawned while still evening; where among the clouds does the moon lodge?"),
+        author: Cow::Borrowed("清原深養父"),
+        author_romaji: Cow::Borrowed("Kiyohara no Fukayabu"),
+    },
+    Poem {
+        text: Cow::Borrowed("白露に 風の吹きしく 秋の野は つらぬきとめぬ 玉ぞ散りける"),
+        romaji: Cow::Borrowed("Shiratsuyu ni / kaze no fukishiku / aki no no wa / tsuranuki tomenu / tama zo chirikeru"),
+        gloss: Cow::Borrowed("On the autumn moor where wind blows over white dew, unstrung jewels scatter everywhere."),
+        author: Cow::Borrowed("文屋朝康"),
+        author_romaji: Cow::Borrowed("Bun'ya no Asayasu"),
+    },
+    Poem {
+        text: Cow::Borrowed("村雨の 露もまだひぬ まきの葉に 霧立ちのぼる 秋の夕暮れ"),
+        romaji: Cow::Borrowed("Murasame no / tsuyu mo mada hinu / maki no ha ni / kiri tachinoboru / aki no yūgure"),
+        gloss: Cow::Borrowed("On cypress leaves still wet from a passing shower, mist rises: autumn dusk."),
+        author: Cow::Borrowed("寂蓮法師"),
+        author_romaji: Cow::Borrowed("Priest Jakuren"),
+    },
+    Poem {
+        text: Cow::Borrowed("朝ぼらけ 有明の月と 見るまでに 吉野の里に 降れる白雪"),
+        romaji: Cow::Borrowed("Asaborake / ariake no tsuki to / miru made ni / Yoshino no sato ni / fureru shirayuki"),
+        gloss: Cow::Borrowed("At daybreak the snow on Yoshino village shines so white I took it for the lingering moon."),
+        author: Cow::Borrowed("坂上是則"),
+        author_romaji: Cow::Borrowed("Sakanoue no Korenori"),
+    },
+    Poem {
+        text: Cow::Borrowed("月見れば 千々にものこそ 悲しけれ わが身ひとつの 秋にはあらねど"),
+        romaji: Cow::Borrowed("Tsuki mireba / chiji ni mono koso / kanashikere / waga mi hitotsu no / aki ni wa aranedo"),
+        gloss: Cow::Borrowed("Looking at the moon, a thousand things grieve me, though autumn does not come to me alone."),
+        author: Cow::Borrowed("大江千里"),
+        author_romaji: Cow::Borrowed("Ōe no Chisato"),
+    },
+    Poem {
+        text: Cow::Borrowed("君がため 春の野に出でて 若菜つむ わが衣手に 雪は降りつつ"),
+        romaji: Cow::Borrowed("Kimi ga tame / haru no no ni idete / wakana tsumu / waga koromode ni / yuki wa furitsutsu"),
+        gloss: Cow::Borrowed("For your sake I picked young greens in the spring fields, and on my sleeves the snow kept falling."),
+        author: Cow::Borrowed("光孝天皇"),
+        author_romaji: Cow::Borrowed("Emperor Kōkō"),
+    },
+    Poem {
+        text: Cow::Borrowed("瀬をはやみ 岩にせかるる 滝川の われても末に 逢はむとぞ思ふ"),
+        romaji: Cow::Borrowed("Se o hayami / iwa ni sekaruru / takigawa no / waretemo sue ni / awan to zo omou"),
+        gloss: Cow::Borrowed("Like a swift stream split by a rock, though we part now, in the end we will meet again."),
+        author: Cow::Borrowed("崇徳院"),
+        author_romaji: Cow::Borrowed("Retired Emperor Sutoku"),
+    },
+];
+
+pub fn select(index: u8) -> &'static Poem {
+    &POEMS[index as usize % POEMS.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_roundtrip() {
+        let json = serde_json::to_string(&POEMS[3]).unwrap();
+        assert_eq!(serde_json::from_str::<Poem>(&json).unwrap(), POEMS[3]);
+        assert!(serde_json::from_str::<Poem>(r#"{"text":"not a waka"}"#).is_err());
+
+        // Poems outside the embedded corpus deserialize as they are
+        let json = r#"{"text":"古池や","romaji":"Furuike ya","gloss":"The old pond","author":"松尾芭蕉","author_romaji":"Matsuo Bashō"}"#;
+        assert_eq!(serde_json::from_str::<Poem>(json).unwrap().author, "松尾芭蕉");
+    }
+
+    #[test]
+    fn test_corpus_complete() {
+        for poem in POEMS.iter() {
+            assert_eq!(poem.text.split(' ').count(), 5, "{}", poem.text);
+            assert_eq!(poem.romaji.split(" / ").count(), 5, "{}", poem.romaji);
+            assert!(!poem.gloss.is_empty());
+            assert!(!poem.author.is_empty() && !poem.author_romaji.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_select_wraps() {
+        assert_eq!(select(0), &POEMS[0]);
+        assert_eq!(select(POEMS.len() as u8), &POEMS[0]);
+    }
+}
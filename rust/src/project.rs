@@ -0,0 +1,148 @@
+//! Fortune for a Cargo project (`cargo omikuji`): seeded from the package name and
+//! version or the git HEAD, with the luck categories renamed for software projects.
+
+use crate::luck::{Grade, LuckType};
+use crate::output::Fortune;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Project-flavored names for the 16 categories, in `LuckType::ALL` order
+pub const PROJECT_LABELS: [&str; 16] = [
+    "Project Luck",
+    "CI Luck",
+    "Sponsorship Luck",
+    "Release Luck",
+    "Contributor Luck",
+    "Dependency Luck",
+    "Ecosystem Luck",
+    "Review Luck",
+    "Docs Luck",
+    "Refactor Luck",
+    "Adoption Luck",
+    "Maintainer Luck",
+    "Debug Luck",
+    "Build Cache Luck",
+    "Star Luck",
+    "Breaking-Change Luck",
+];
+
+pub fn project_labels() -> HashMap<LuckType, String> {
+    LuckType::ALL.iter().zip(PROJECT_LABELS).map(|(&t, label)| (t, label.to_string())).collect()
+}
+
+/// Name and version of a Cargo package
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Package {
+    pub name: String,
+    pub version: String,
+}
+
+impl Package {
+    /// Read `[package]` (or `[workspace.package]` in a virtual workspace, named after its directory)
+    pub fn from_manifest(path: &Path) -> Result<Self, String> {
+        let text = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let manifest: toml::Table = text.parse().map_err(|e| format!("Invalid manifest {}: {}", path.display(), e))?;
+        let section = |key: &str| manifest.get(key).and_then(|v| v.as_table());
+        let package = section("package").or_else(|| section("workspace").and_then(|w| w.get("package")?.as_table()));
+        let field = |key: &str| package.and_then(|p| p.get(key)).and_then(|v| v.as_str()).map(str::to_string);
+        let name = field("name")
+            .or_else(|| path.parent()?.file_name().map(|n| n.to_string_lossy().to_string()))
+            .ok_or_else(|| format!("{} has no package name", path.display()))?;
+        let version = field("version").unwrap_or_else(|| "0.0.0".to_string());
+        Ok(Self { name, version })
+    }
+
+    /// Seed for the draw: `name@version`, or `name@<commit>` when pinned to the git HEAD
+    pub fn seed(&self, head: Option<&str>) -> String {
+        format!("{}@{}", self.name, head.unwrap_or(&self.version))
+    }
+}
+
+/// Nearest Cargo.toml at or above `dir`
+pub fn find_manifest(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors().map(|d| d.join("Cargo.toml")).find(|p| p.is_file())
+}
+
+/// Commit hash of HEAD in the repository containing `dir`
+pub fn git_head(dir: &Path) -> Result<String, String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .current_dir(dir)
+        .output()
+        .map_err(|e| format!("Failed to run git: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("Not a git repository: {}", dir.display()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// 吉 or better counts as a lucky year
+pub fn is_lucky(grade: Grade) -> bool {
+    grade >= Grade::Kichi
+}
+
+/// Short report: verdict, overall score and every category, best first
+pub fn format_report(fortune: &Fortune, package: &Package) -> String {
+    let mut output = format!("🎍 cargo omikuji {}: {} {} 🎍\n\n", fortune.year, package.name, package.version);
+    let verdict = if is_lucky(fortune.grade) { "a lucky year" } else { "a year to tread carefully" };
+    output.push_str(&format!("{} ({:#}) - {} faces {}\n", fortune.grade, fortune.grade, package.name, verdict));
+    output.push_str(&format!("Overall Score        : {} {}\n\n", fortune.overall_score, fortune.star_rating));
+    let mut scores: Vec<_> = fortune.luck_scores.iter().collect();
+    scores.sort_by_key(|s| std::cmp::Reverse(s.score));
+    for score in scores {
+        output.push_str(&format!("{:21}: {:3} ({})\n", score.label, score.score, score.rank.as_str()));
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_manifest(name: &str, contents: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("hash-omikuji-project-{}-{}", name, std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("Cargo.toml");
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_package_from_manifest() {
+        let path = write_manifest("pkg", "[package]\nname = \"demo\"\nversion = \"1.2.3\"\n");
+        let package = Package::from_manifest(&path).unwrap();
+        assert_eq!(package, Package { name: "demo".to_string(), version: "1.2.3".to_string() });
+        assert_eq!(package.seed(None), "demo@1.2.3");
+        assert_eq!(package.seed(Some("abc123")), "demo@abc123");
+        assert_eq!(find_manifest(path.parent().unwrap()), Some(path.clone()));
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_virtual_workspace_manifest() {
+        let path = write_manifest("ws", "[workspace]\nmembers = []\n\n[workspace.package]\nversion = \"0.3.0\"\n");
+        let package = Package::from_manifest(&path).unwrap();
+        assert!(package.name.starts_with("hash-omikuji-project-ws-"));
+        assert_eq!(package.version, "0.3.0");
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_report_uses_project_labels() {
+        let package = Package { name: "demo".to_string(), version: "1.2.3".to_string() };
+        let fortune = Fortune::builder().year(2026).seed(&package.seed(None)).draw().with_category_labels(&project_labels());
+        let report = format_report(&fortune, &package);
+        assert!(report.starts_with("🎍 cargo omikuji 2026: demo 1.2.3 🎍"));
+        assert!(report.contains("CI Luck") && report.contains("Release Luck"));
+        assert!(!report.contains("WiFi Luck"));
+        assert_eq!(report.lines().filter(|l| l.contains(" Luck ")).count(), 16);
+    }
+
+    #[test]
+    fn test_is_lucky() {
+        assert!(is_lucky(Grade::Daikichi) && is_lucky(Grade::Kichi));
+        assert!(!is_lucky(Grade::Suekichi) && !is_lucky(Grade::Daikyo));
+    }
+}
@@ -0,0 +1,120 @@
+//! `prompt`: a tiny shell prompt segment (today's grade and emoji), cached per day.
+
+use crate::hash::{Algo, HashBits};
+use crate::luck::{Grade, GradeWeights};
+use chrono::NaiveDate;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Grade and emoji of the daily fortune
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Segment {
+    pub grade: Grade,
+    pub emoji: String,
+}
+
+impl Segment {
+    /// Today's fortune within the year drawn from `hash`
+    pub fn daily(hash: &HashBits, date: NaiveDate, weights: &GradeWeights) -> Self {
+        use chrono::Datelike;
+        let daily = hash.daily(date.ordinal());
+        Self {
+            grade: Grade::from_bits(daily.grade_bits(), weights),
+            emoji: daily.lucky_emoji().to_string(),
+        }
+    }
+
+    /// `中吉 🎉`, in the grade's 256-color foreground unless `color` is off
+    pub fn render(&self, color: bool) -> String {
+        let text = format!("{} {}", self.grade.kanji(), self.emoji);
+        if color {
+            format!("\x1b[38;5;{}m{}\x1b[0m", grade_color(self.grade), text)
+        } else {
+            text
+        }
+    }
+}
+
+/// Red for 大吉 through gray and purple for the 凶 grades
+pub fn grade_color(grade: Grade) -> u8 {
+    match grade {
+        Grade::Daikichi => 196,
+        Grade::Chukichi => 208,
+        Grade::Shokichi => 220,
+        Grade::Kichi => 114,
+        Grade::Suekichi => 117,
+        Grade::Kyo => 245,
+        Grade::Daikyo => 93,
+    }
+}
+
+/// Default cache: `~/.cache/hash-omikuji/prompt` (platform cache dir)
+pub fn cache_path() -> Option<PathBuf> {
+    dirs::cache_dir().map(|dir| dir.join("hash-omikuji").join("prompt"))
+}
+
+/// Cache key for a seed and the settings that change the draw; the seed itself is never written to disk
+pub fn cache_key(seed: &str, year: u32, algo: Algo, weights: &GradeWeights) -> String {
+    let digest = Sha256::new().chain_update(format!("{}-{}-{:?}-{:?}", year, seed, algo, weights.0)).finalize();
+    crate::fingerprint::hex(&digest[..8])
+}
+
+/// The cached segment if it was written today for the same key
+pub fn load_cached(path: &Path, date: NaiveDate, key: &str) -> Option<Segment> {
+    let text = fs::read_to_string(path).ok()?;
+    let mut fields = text.trim_end().split('\t');
+    let (cached_date, cached_key) = (fields.next()?, fields.next()?);
+    if cached_date != date.to_string() || cached_key != key {
+        return None;
+    }
+    let grade = fields.next()?.parse().ok()?;
+    Some(Segment { grade, emoji: fields.next()?.to_string() })
+}
+
+/// Best effort: a prompt must never fail because the cache is not writable
+pub fn store(path: &Path, date: NaiveDate, key: &str, segment: &Segment) {
+    if let Some(dir) = path.parent() {
+        let _ = fs::create_dir_all(dir);
+    }
+    let _ = fs::write(path, format!("{}\t{}\t{}\t{}\n", date, key, segment.grade.romaji(), segment.emoji));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(text: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(text, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_render() {
+        let segment = Segment { grade: Grade::Daikichi, emoji: "🎉".to_string() };
+        assert_eq!(segment.render(false), "大吉 🎉");
+        assert_eq!(segment.render(true), "\x1b[38;5;196m大吉 🎉\x1b[0m");
+    }
+
+    #[test]
+    fn test_daily_changes_by_day() {
+        let hash = HashBits::from_seed(2026, "alice");
+        let weights = GradeWeights::default();
+        let days: Vec<_> = (1..=30).map(|d| Segment::daily(&hash, date(&format!("2026-01-{:02}", d)), &weights)).collect();
+        assert_eq!(days[0], Segment::daily(&hash, date("2026-01-01"), &weights));
+        assert!(days.iter().any(|s| s.grade != days[0].grade));
+    }
+
+    #[test]
+    fn test_cache_roundtrip() {
+        let path = std::env::temp_dir().join(format!("hash-omikuji-prompt-{}", std::process::id()));
+        let segment = Segment { grade: Grade::Suekichi, emoji: "😪".to_string() };
+        let weights = GradeWeights::default();
+        let key = cache_key("alice", 2026, Algo::Sha256, &weights);
+        store(&path, date("2026-01-01"), &key, &segment);
+        assert_eq!(load_cached(&path, date("2026-01-01"), &key), Some(segment));
+        assert_eq!(load_cached(&path, date("2026-01-02"), &key), None);
+        assert_eq!(load_cached(&path, date("2026-01-01"), &cache_key("bob", 2026, Algo::Sha256, &weights)), None);
+        assert_ne!(key, cache_key("alice", 2026, Algo::Blake3, &weights));
+        fs::remove_file(&path).unwrap();
+    }
+}
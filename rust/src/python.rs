@@ -0,0 +1,31 @@
+//! `hash_omikuji` Python extension module (PyO3), built with maturin.
+
+use crate::art::framed;
+use crate::output::Fortune;
+use pyo3::prelude::*;
+
+/// Full fortune as a dict, the same document as `--json`
+#[pyfunction]
+fn draw<'py>(py: Python<'py>, year: u32, seed: &str) -> PyResult<Bound<'py, PyAny>> {
+    let fortune = Fortune::builder().year(year).seed(seed).draw();
+    py.import("json")?.call_method1("loads", (fortune.format_json(),))
+}
+
+/// The framed randomart (current year unless `year` is given)
+#[pyfunction]
+#[pyo3(signature = (seed, year = None))]
+fn art(seed: &str, year: Option<u32>) -> String {
+    let mut builder = Fortune::builder().seed(seed);
+    if let Some(year) = year {
+        builder = builder.year(year);
+    }
+    let fortune = builder.draw();
+    framed(&fortune.art, fortune.year, fortune.grade, false)
+}
+
+#[pymodule]
+fn hash_omikuji(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(draw, m)?)?;
+    m.add_function(wrap_pyfunction!(art, m)?)?;
+    Ok(())
+}
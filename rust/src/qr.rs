@@ -0,0 +1,133 @@
+//! `--qr`: the fortune as a QR code, drawn with half blocks in the terminal or as a PNG.
+
+use crate::card::Canvas;
+use crate::output::Fortune;
+use qrcode::{Color, EcLevel, QrCode};
+use serde_json::{Map, Value};
+
+/// What the QR code carries
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum QrContent {
+    /// Canonical JSON of the headline fields
+    #[default]
+    Json,
+}
+
+/// Fields in the JSON payload; the full document is too large to scan off a screen
+const JSON_FIELDS: [&str; 13] = [
+    "year",
+    "grade",
+    "overall_score",
+    "lucky_number",
+    "lucky_hex",
+    "lucky_color",
+    "lucky_day",
+    "lucky_time",
+    "lucky_emoji",
+    "lucky_item",
+    "layout",
+    "algo",
+    "fingerprint",
+];
+
+/// Light border around the code, in modules
+const QUIET_ZONE: usize = 2;
+
+/// Pixels per module in the PNG
+const PNG_SCALE: usize = 8;
+
+/// Text encoded in the QR code
+pub fn payload(fortune: &Fortune, content: QrContent) -> String {
+    match content {
+        QrContent::Json => {
+            let document = serde_json::to_value(fortune).unwrap();
+            let fields: Map<String, Value> =
+                JSON_FIELDS.iter().filter_map(|&f| Some((f.to_string(), document.get(f)?.clone()))).collect();
+            crate::sign::canonical_json(&Value::Object(fields))
+        }
+    }
+}
+
+pub fn encode(payload: &str) -> Result<QrCode, String> {
+    QrCode::with_error_correction_level(payload, EcLevel::L).map_err(|e| format!("Cannot encode as a QR code: {}", e))
+}
+
+/// Whether the module at (x, y), quiet zone included, is dark
+fn is_dark(code: &QrCode, colors: &[Color], x: usize, y: usize) -> bool {
+    let width = code.width();
+    let (x, y) = (x.wrapping_sub(QUIET_ZONE), y.wrapping_sub(QUIET_ZONE));
+    x < width && y < width && colors[y * width + x] == Color::Dark
+}
+
+/// Two module rows per line. Light modules are drawn as blocks, so the code reads
+/// dark-on-light on a terminal with light text on a dark background.
+pub fn render_terminal(code: &QrCode) -> String {
+    let colors = code.to_colors();
+    let size = code.width() + 2 * QUIET_ZONE;
+    let mut output = String::new();
+    for y in (0..size).step_by(2) {
+        for x in 0..size {
+            let top = !is_dark(code, &colors, x, y);
+            let bottom = y + 1 < size && !is_dark(code, &colors, x, y + 1);
+            output.push(match (top, bottom) {
+                (true, true) => '█',
+                (true, false) => '▀',
+                (false, true) => '▄',
+                (false, false) => ' ',
+            });
+        }
+        output.push('\n');
+    }
+    output
+}
+
+pub fn render_png(code: &QrCode) -> Result<Vec<u8>, String> {
+    let colors = code.to_colors();
+    let size = code.width() + 2 * QUIET_ZONE;
+    let mut canvas = Canvas::new(size * PNG_SCALE, size * PNG_SCALE, (255, 255, 255));
+    for y in 0..size {
+        for x in 0..size {
+            if is_dark(code, &colors, x, y) {
+                canvas.fill_rect(x * PNG_SCALE, y * PNG_SCALE, PNG_SCALE, PNG_SCALE, (0, 0, 0));
+            }
+        }
+    }
+    canvas.encode_png()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_payload() {
+        let fortune = Fortune::builder().year(2026).seed("alice").draw();
+        let payload = payload(&fortune, QrContent::Json);
+        let document: Value = serde_json::from_str(&payload).unwrap();
+        assert_eq!(document["lucky_number"], fortune.lucky_number);
+        assert_eq!(document.as_object().unwrap().len(), JSON_FIELDS.len());
+        assert!(payload.starts_with("{\"algo\":"));
+    }
+
+    #[test]
+    fn test_terminal_fits_80_columns() {
+        let fortune = Fortune::builder().year(2026).seed("alice").draw();
+        let code = encode(&payload(&fortune, QrContent::Json)).unwrap();
+        let text = render_terminal(&code);
+        let width = code.width() + 2 * QUIET_ZONE;
+        assert!(width <= 80);
+        assert!(text.lines().all(|line| line.chars().count() == width));
+        assert_eq!(text.lines().count(), width.div_ceil(2));
+        // Quiet zone: the first line is all light
+        assert!(text.lines().next().unwrap().chars().all(|c| c == '█'));
+    }
+
+    #[test]
+    fn test_png() {
+        let code = encode("hash-omikuji").unwrap();
+        let bytes = render_png(&code).unwrap();
+        let info = png::Decoder::new(bytes.as_slice()).read_info().unwrap().info().clone();
+        assert_eq!(info.width as usize, (code.width() + 2 * QUIET_ZONE) * PNG_SCALE);
+    }
+}
@@ -0,0 +1,95 @@
+//! `schedule`: a crontab line or a systemd timer and service that draw at 00:00 on
+//! January 1st and post the fortune to the webhook.
+
+/// The scheduled draw: the binary, its arguments and the zone midnight is taken in
+pub struct Job {
+    pub exe: String,
+    pub args: Vec<String>,
+    /// IANA zone (`UTC` for --utc); None for the machine's zone
+    pub tz: Option<String>,
+}
+
+/// Single-quote for the shell, with `%` escaped for cron
+fn cron_quote(arg: &str) -> String {
+    let quoted = if !arg.is_empty() && arg.chars().all(|c| c.is_ascii_alphanumeric() || "-_./:@=+,".contains(c)) {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', "'\\''"))
+    };
+    quoted.replace('%', "\\%")
+}
+
+/// Double-quote for systemd's ExecStart, with `%` specifiers escaped
+fn systemd_quote(arg: &str) -> String {
+    format!("\"{}\"", arg.replace('\\', "\\\\").replace('"', "\\\"").replace('%', "%%"))
+}
+
+/// A crontab entry; CRON_TZ (cronie, busybox) sets the zone of the lines after it
+pub fn crontab(job: &Job) -> String {
+    let command: Vec<String> = std::iter::once(&job.exe).chain(&job.args).map(|arg| cron_quote(arg)).collect();
+    let mut output = String::from("# hash-omikuji: draw at 00:00 on January 1st and post it (add with `crontab -e`)\n");
+    if let Some(ref tz) = job.tz {
+        output.push_str(&format!("CRON_TZ={}\n", tz));
+    }
+    output.push_str(&format!("0 0 1 1 * {}\n", command.join(" ")));
+    output
+}
+
+/// A user service and timer pair, to be saved under ~/.config/systemd/user
+pub fn systemd(job: &Job) -> String {
+    let command: Vec<String> = std::iter::once(&job.exe).chain(&job.args).map(|arg| systemd_quote(arg)).collect();
+    let calendar = match job.tz {
+        Some(ref tz) => format!("*-01-01 00:00:00 {}", tz),
+        None => "*-01-01 00:00:00".to_string(),
+    };
+    format!(
+        "# ~/.config/systemd/user/hash-omikuji.service\n\
+         [Unit]\n\
+         Description=Draw the New Year hash-omikuji and post it\n\
+         \n\
+         [Service]\n\
+         Type=oneshot\n\
+         ExecStart={}\n\
+         \n\
+         # ~/.config/systemd/user/hash-omikuji.timer\n\
+         [Unit]\n\
+         Description=hash-omikuji at midnight on January 1st\n\
+         \n\
+         [Timer]\n\
+         OnCalendar={}\n\
+         Persistent=true\n\
+         \n\
+         [Install]\n\
+         WantedBy=timers.target\n\
+         \n\
+         # Enable with: systemctl --user enable --now hash-omikuji.timer\n",
+        command.join(" "),
+        calendar
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_units() {
+        let job = Job {
+            exe: "/usr/local/bin/hash-omikuji".to_string(),
+            args: ["--tz", "Asia/Tokyo", "--post-url", "https://example.org/hook?a=1&b=50%"].map(String::from).to_vec(),
+            tz: Some("Asia/Tokyo".to_string()),
+        };
+        let cron = crontab(&job);
+        assert!(cron.contains("CRON_TZ=Asia/Tokyo\n"));
+        assert!(cron.ends_with("0 0 1 1 * /usr/local/bin/hash-omikuji --tz Asia/Tokyo --post-url 'https://example.org/hook?a=1&b=50\\%'\n"));
+
+        let units = systemd(&job);
+        assert!(units.contains("ExecStart=\"/usr/local/bin/hash-omikuji\" \"--tz\" \"Asia/Tokyo\" \"--post-url\" \"https://example.org/hook?a=1&b=50%%\"\n"));
+        assert!(units.contains("OnCalendar=*-01-01 00:00:00 Asia/Tokyo\n"));
+
+        let local = Job { tz: None, ..job };
+        assert!(!crontab(&local).contains("CRON_TZ"));
+        assert!(systemd(&local).contains("OnCalendar=*-01-01 00:00:00\n"));
+        assert_eq!(cron_quote("it's"), "'it'\\''s'");
+    }
+}
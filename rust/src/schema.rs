@@ -0,0 +1,53 @@
+//! `schema`: JSON Schema (draft-07) of the `--json` document, derived from [`Fortune`].
+//! There is one schema per bit layout; its `$id` carries the layout version.
+
+use crate::hash::Layout;
+use crate::output::Fortune;
+use serde_json::{json, Value};
+
+/// `$id` of the schema for `layout`
+pub fn schema_id(layout: Layout) -> String {
+    format!("https://github.com/elzup/hash-omikuji/schema/fortune-{}.json", layout_name(layout))
+}
+
+fn layout_name(layout: Layout) -> String {
+    serde_json::to_value(layout).ok().and_then(|v| v.as_str().map(String::from)).unwrap_or_default()
+}
+
+/// Schema of a fortune drawn with `layout`; its `layout` property is pinned to that version
+pub fn fortune_schema(layout: Layout) -> Value {
+    let mut schema = serde_json::to_value(schemars::schema_for!(Fortune)).expect("schema serializes");
+    schema["$id"] = json!(schema_id(layout));
+    schema["title"] = json!(format!("hash-omikuji fortune (layout {})", layout_name(layout)));
+    schema["properties"]["layout"] = json!({ "const": layout_name(layout) });
+    schema
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fortune_schema() {
+        let schema = fortune_schema(Layout::V2);
+        assert_eq!(schema["$id"], "https://github.com/elzup/hash-omikuji/schema/fortune-v2.json");
+        assert_eq!(schema["properties"]["layout"]["const"], "v2");
+        let required = schema["required"].as_array().unwrap();
+        assert!(required.contains(&json!("grade")));
+        assert!(!required.contains(&json!("signature")));
+        assert!(schema["definitions"]["Grade"].is_object());
+    }
+
+    #[test]
+    fn test_fortune_document_matches_schema_fields() {
+        let schema = fortune_schema(Layout::V1);
+        let document = serde_json::to_value(Fortune::builder().year(2026).seed("alice").draw()).unwrap();
+        let properties = schema["properties"].as_object().unwrap();
+        for field in document.as_object().unwrap().keys() {
+            assert!(properties.contains_key(field), "{}", field);
+        }
+        for field in schema["required"].as_array().unwrap() {
+            assert!(document.get(field.as_str().unwrap()).is_some(), "{}", field);
+        }
+    }
+}
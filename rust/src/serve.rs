@@ -0,0 +1,622 @@
+//! `serve`: HTTP API drawing fortunes on request (`/fortune`, `/art`, `/batch`), checking
+//! shared results (`/verify`), exporting a team's luck as Prometheus gauges (`/metrics`),
+//! and describing itself at `/openapi.json`. With `--tenants`, every drawing endpoint
+//! draws in the universe of the tenant picked by the request's API key or host name.
+//! Draws run on a bounded pool of blocking threads, requests beyond the concurrency
+//! limit or past the timeout are answered 503, and SIGINT/SIGTERM let requests in
+//! flight finish before the server exits.
+
+use crate::art::{self, ArtMode};
+use crate::builder::FortuneBuilder;
+use crate::date::YEARS;
+use crate::metrics;
+use crate::output::{seed_fingerprint, Fortune};
+use crate::sign::{self, Verification};
+use crate::tenants::Tenants;
+use axum::extract::{FromRequestParts, Query, Request, State};
+use axum::http::request::Parts;
+use axum::http::{header, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Semaphore;
+use utoipa::{IntoParams, OpenApi, ToSchema};
+use zeroize::Zeroizing;
+
+pub const DEFAULT_BIND: &str = "127.0.0.1:8080";
+
+/// Most seeds accepted by one `/batch` request
+pub const MAX_BATCH: usize = 1000;
+
+/// Header carrying a tenant's API key
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// How much work the server takes on at once
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+    /// Draws running at once, each on its own blocking thread; more wait for a free one
+    pub workers: usize,
+    /// Requests handled at once; more are answered 503 right away
+    pub concurrency: usize,
+    /// Longest a request may take before it is answered 503
+    pub timeout: Duration,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            workers: std::thread::available_parallelism().map_or(1, |n| n.get()),
+            concurrency: 256,
+            timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Draw settings shared by every request, the seeds exported by /metrics, the tenants
+/// and the limits
+#[derive(Clone)]
+pub struct AppState {
+    builder: FortuneBuilder,
+    seeds: Arc<Vec<Zeroizing<String>>>,
+    tenants: Arc<Tenants>,
+    workers: Arc<Semaphore>,
+    requests: Arc<Semaphore>,
+    limits: Limits,
+}
+
+impl AppState {
+    pub fn new(builder: FortuneBuilder, seeds: Vec<String>) -> Self {
+        let seeds = Arc::new(seeds.into_iter().map(Zeroizing::new).collect());
+        let limits = Limits::default();
+        Self {
+            builder,
+            seeds,
+            tenants: Arc::new(Tenants::default()),
+            workers: Arc::new(Semaphore::new(limits.workers)),
+            requests: Arc::new(Semaphore::new(limits.concurrency)),
+            limits,
+        }
+    }
+
+    pub fn with_tenants(mut self, tenants: Tenants) -> Self {
+        self.tenants = Arc::new(tenants);
+        self
+    }
+
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.workers = Arc::new(Semaphore::new(limits.workers.max(1)));
+        self.requests = Arc::new(Semaphore::new(limits.concurrency));
+        self.limits = limits;
+        self
+    }
+
+    pub fn limits(&self) -> Limits {
+        self.limits
+    }
+
+    /// Settings of the tenant picked by `api_key` or `host`; everything is served
+    /// unsalted when no tenants are configured, and nothing when none matches
+    pub(crate) fn tenant(&self, host: Option<&str>, api_key: Option<&str>) -> Result<AppState, String> {
+        if self.tenants.is_empty() {
+            return Ok(self.clone());
+        }
+        match self.tenants.select(host, api_key) {
+            Some(tenant) => Ok(Self { builder: self.builder.clone().salt(&tenant.salt), ..self.clone() }),
+            None if api_key.is_some() => Err("Unknown API key".to_string()),
+            None => Err(format!("Unknown tenant: send an {} header or use a tenant's host name", API_KEY_HEADER)),
+        }
+    }
+
+    /// Fortune of `seed` in `year` (default: the builder's year)
+    pub(crate) fn draw(&self, seed: &str, year: Option<u32>) -> Fortune {
+        let builder = self.builder.clone().seed(seed);
+        match year {
+            Some(year) => builder.year(year).draw(),
+            None => builder.draw(),
+        }
+    }
+
+    /// Run CPU-bound `job` on a blocking thread once a worker is free, keeping the
+    /// async threads free for I/O. The worker stays taken until `job` returns, even
+    /// when the request gives up waiting for it.
+    pub(crate) async fn run_blocking<T: Send + 'static>(&self, job: impl FnOnce(&AppState) -> T + Send + 'static) -> T {
+        let permit = self.workers.clone().acquire_owned().await.expect("the worker pool is never closed");
+        let state = self.clone();
+        tokio::task::spawn_blocking(move || {
+            let _permit = permit;
+            job(&state)
+        })
+        .await
+        .unwrap_or_else(|e| std::panic::resume_unwind(e.into_panic()))
+    }
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "hash-omikuji", description = "SHA-256 based deterministic fortune telling"),
+    paths(fortune, art, batch, verify, metrics),
+    components(schemas(Fortune, Verification, BatchRequest, ErrorBody))
+)]
+pub struct ApiDoc;
+
+pub fn router(state: AppState) -> Router {
+    let router = Router::new()
+        .route("/fortune", get(fortune))
+        .route("/art", get(art))
+        .route("/batch", post(batch))
+        .route("/verify", post(verify))
+        .route("/metrics", get(metrics))
+        .route("/openapi.json", get(openapi));
+    #[cfg(feature = "graphql")]
+    let router = router.route("/graphql", get(crate::graphql::describe).post(crate::graphql::execute));
+    router.layer(middleware::from_fn_with_state(state.clone(), limit)).with_state(state)
+}
+
+/// 503 with a Retry-After, for load the server sheds
+fn unavailable(message: String) -> Response {
+    let mut response = error(StatusCode::SERVICE_UNAVAILABLE, message);
+    response.headers_mut().insert(header::RETRY_AFTER, header::HeaderValue::from_static("1"));
+    response
+}
+
+/// Turn requests away beyond the concurrency limit and cut off those past the timeout
+async fn limit(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    let Ok(_permit) = state.requests.clone().try_acquire_owned() else {
+        return unavailable("Too many requests in progress".to_string());
+    };
+    match tokio::time::timeout(state.limits.timeout, next.run(request)).await {
+        Ok(response) => response,
+        Err(_) => unavailable(format!("Timed out after {:?}", state.limits.timeout)),
+    }
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct FortuneQuery {
+    /// Seed to draw for
+    seed: String,
+    /// Year of the draw (default: the current year)
+    year: Option<u32>,
+}
+
+#[derive(IntoParams)]
+#[into_params(parameter_in = Header)]
+#[allow(dead_code)]
+struct TenantHeader {
+    /// API key of the tenant to draw for (with `--tenants`; otherwise the host name decides)
+    #[param(rename = "X-API-Key")]
+    x_api_key: Option<String>,
+}
+
+/// Seeds to draw for in one request
+#[derive(Deserialize, ToSchema)]
+pub struct BatchRequest {
+    pub seeds: Vec<String>,
+    /// Year of the draws (default: the current year)
+    pub year: Option<u32>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ErrorBody {
+    pub error: String,
+}
+
+fn error(status: StatusCode, message: String) -> Response {
+    (status, Json(ErrorBody { error: message })).into_response()
+}
+
+/// Refuse a requested year that cannot be drawn
+pub(crate) fn check_year(year: Option<u32>) -> Result<(), String> {
+    match year {
+        Some(year) if !YEARS.contains(&year) => Err(format!("Year {} is out of range ({}-{})", year, YEARS.start(), YEARS.end())),
+        _ => Ok(()),
+    }
+}
+
+/// Draw settings of the request's tenant, from its `X-API-Key` and `Host` headers
+pub struct Tenant(pub AppState);
+
+impl FromRequestParts<AppState> for Tenant {
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, Response> {
+        let value = |name| parts.headers.get(name).and_then(|v| v.to_str().ok());
+        match state.tenant(value(header::HOST.as_str()), value(API_KEY_HEADER)) {
+            Ok(state) => Ok(Tenant(state)),
+            Err(message) => Err(error(StatusCode::NOT_FOUND, message)),
+        }
+    }
+}
+
+/// The `--json` document for a seed
+#[utoipa::path(
+    get,
+    path = "/fortune",
+    params(FortuneQuery, TenantHeader),
+    responses((status = 200, body = Fortune), (status = 400, body = ErrorBody), (status = 404, body = ErrorBody))
+)]
+async fn fortune(Tenant(state): Tenant, Query(query): Query<FortuneQuery>) -> Response {
+    if let Err(message) = check_year(query.year) {
+        return error(StatusCode::BAD_REQUEST, message);
+    }
+    let seed = Zeroizing::new(query.seed);
+    Json(state.run_blocking(move |state| state.draw(&seed, query.year)).await).into_response()
+}
+
+/// The framed randomart (or emoji tiles) for a seed
+#[utoipa::path(
+    get,
+    path = "/art",
+    params(FortuneQuery, TenantHeader),
+    responses((status = 200, body = String, content_type = "text/plain"), (status = 400, body = ErrorBody), (status = 404, body = ErrorBody))
+)]
+async fn art(Tenant(state): Tenant, Query(query): Query<FortuneQuery>) -> Response {
+    if let Err(message) = check_year(query.year) {
+        return error(StatusCode::BAD_REQUEST, message);
+    }
+    let seed = Zeroizing::new(query.seed);
+    let fortune = state.run_blocking(move |state| state.draw(&seed, query.year)).await;
+    if fortune.art_options.mode == ArtMode::Emoji {
+        format!("{}\n", fortune.art).into_response()
+    } else {
+        art::framed(&fortune.art, fortune.year, fortune.grade, false).into_response()
+    }
+}
+
+/// Fortunes for many seeds, in request order
+#[utoipa::path(
+    post,
+    path = "/batch",
+    params(TenantHeader),
+    request_body = BatchRequest,
+    responses((status = 200, body = Vec<Fortune>), (status = 400, body = ErrorBody), (status = 404, body = ErrorBody))
+)]
+async fn batch(Tenant(state): Tenant, Json(request): Json<BatchRequest>) -> Response {
+    if request.seeds.len() > MAX_BATCH {
+        return error(StatusCode::BAD_REQUEST, format!("At most {} seeds per batch", MAX_BATCH));
+    }
+    if let Err(message) = check_year(request.year) {
+        return error(StatusCode::BAD_REQUEST, message);
+    }
+    let seeds: Vec<_> = request.seeds.into_iter().map(Zeroizing::new).collect();
+    let fortunes = state.run_blocking(move |state| seeds.iter().map(|seed| state.draw(seed, request.year)).collect::<Vec<_>>()).await;
+    Json(fortunes).into_response()
+}
+
+/// Check a JSON fortune's layout v2 checksum and/or signature, like the `verify` subcommand
+#[utoipa::path(
+    post,
+    path = "/verify",
+    request_body(content = Object, description = "A JSON fortune"),
+    responses((status = 200, body = Verification), (status = 422, body = ErrorBody))
+)]
+async fn verify(Json(document): Json<Value>) -> Response {
+    match sign::verify_document(&document, None) {
+        Ok(verification) => Json(verification).into_response(),
+        Err(message) => error(StatusCode::UNPROCESSABLE_ENTITY, message),
+    }
+}
+
+/// This year's fortune of every configured seed as Prometheus gauges
+#[utoipa::path(
+    get,
+    path = "/metrics",
+    params(TenantHeader),
+    responses((status = 200, body = String, content_type = "text/plain"), (status = 404, body = ErrorBody))
+)]
+async fn metrics(Tenant(state): Tenant) -> impl IntoResponse {
+    let samples: Vec<_> = state
+        .run_blocking(|state| state.seeds.iter().map(|seed| (seed_fingerprint(seed), state.builder.clone().seed(seed).draw())).collect())
+        .await;
+    ([(header::CONTENT_TYPE, "text/plain; version=0.0.4")], metrics::exposition(&samples))
+}
+
+async fn openapi() -> Json<utoipa::openapi::OpenApi> {
+    Json(ApiDoc::openapi())
+}
+
+/// First descriptor passed by systemd socket activation (sd_listen_fds(3))
+const SD_LISTEN_FDS_START: i32 = 3;
+
+/// Socket the API is served on
+pub enum Listener {
+    Tcp(tokio::net::TcpListener),
+    #[cfg(unix)]
+    Unix(tokio::net::UnixListener),
+}
+
+impl fmt::Display for Listener {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Listener::Tcp(listener) => match listener.local_addr() {
+                Ok(address) => write!(f, "http://{}", address),
+                Err(_) => write!(f, "a TCP socket"),
+            },
+            #[cfg(unix)]
+            Listener::Unix(listener) => match listener.local_addr().ok().and_then(|a| a.as_pathname().map(|p| p.display().to_string())) {
+                Some(path) => write!(f, "unix:{}", path),
+                None => write!(f, "a unix socket"),
+            },
+        }
+    }
+}
+
+/// Whether LISTEN_PID and LISTEN_FDS hand sockets to the process `pid`
+fn socket_activated(listen_pid: Option<&str>, listen_fds: Option<&str>, pid: u32) -> bool {
+    let pid_matches = listen_pid.and_then(|v| v.parse::<u32>().ok()) == Some(pid);
+    pid_matches && listen_fds.and_then(|v| v.parse::<u32>().ok()).unwrap_or(0) >= 1
+}
+
+/// The socket systemd passed in, TCP or unix
+#[cfg(unix)]
+fn inherited() -> Result<Listener, String> {
+    use std::os::fd::{FromRawFd, IntoRawFd};
+    let error = |e: std::io::Error| format!("Unusable socket from systemd: {}", e);
+    // SAFETY: with LISTEN_PID naming this process, descriptor 3 is a listening socket owned by us
+    let tcp = unsafe { std::net::TcpListener::from_raw_fd(SD_LISTEN_FDS_START) };
+    // local_addr fails for address families other than IPv4/IPv6
+    if tcp.local_addr().is_ok() {
+        tcp.set_nonblocking(true).map_err(error)?;
+        return tokio::net::TcpListener::from_std(tcp).map(Listener::Tcp).map_err(error);
+    }
+    // SAFETY: into_raw_fd hands over the same listening socket, which is now owned only here
+    let unix = unsafe { std::os::unix::net::UnixListener::from_raw_fd(tcp.into_raw_fd()) };
+    unix.set_nonblocking(true).map_err(error)?;
+    tokio::net::UnixListener::from_std(unix).map(Listener::Unix).map_err(error)
+}
+
+/// Open the socket to serve on: the one from systemd socket activation if there is one,
+/// otherwise `bind` (`host:port`, or `unix:/path` for a unix domain socket)
+pub async fn listen(bind: &str) -> Result<Listener, String> {
+    #[cfg(unix)]
+    {
+        let (pid, fds) = (std::env::var("LISTEN_PID").ok(), std::env::var("LISTEN_FDS").ok());
+        if socket_activated(pid.as_deref(), fds.as_deref(), std::process::id()) {
+            return inherited();
+        }
+        if let Some(path) = bind.strip_prefix("unix:") {
+            use std::os::unix::fs::FileTypeExt;
+            // A socket left behind by an earlier run would make bind fail; one that still
+            // accepts connections belongs to a running server and is left alone
+            if std::fs::symlink_metadata(path).is_ok_and(|m| m.file_type().is_socket()) {
+                match std::os::unix::net::UnixStream::connect(path) {
+                    Ok(_) => return Err(format!("Failed to bind {}: a server is already listening there", bind)),
+                    Err(e) if e.kind() == std::io::ErrorKind::ConnectionRefused => {
+                        std::fs::remove_file(path).map_err(|e| format!("Failed to remove stale socket {}: {}", path, e))?;
+                    }
+                    Err(e) => return Err(format!("Failed to bind {}: {}", bind, e)),
+                }
+            }
+            return tokio::net::UnixListener::bind(path).map(Listener::Unix).map_err(|e| format!("Failed to bind {}: {}", bind, e));
+        }
+    }
+    tokio::net::TcpListener::bind(bind).await.map(Listener::Tcp).map_err(|e| format!("Failed to bind {}: {}", bind, e))
+}
+
+/// Resolves on SIGINT (Ctrl-C) or, on unix, SIGTERM (systemd stop, Kubernetes)
+pub(crate) async fn shutdown_signal() {
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(_) => std::future::pending().await,
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+    tokio::select! {
+        _ = tokio::signal::ctrl_c() => {}
+        _ = terminate => {}
+    }
+    eprintln!("Shutting down: finishing the requests in flight");
+}
+
+/// Serve until SIGINT or SIGTERM, then stop accepting and finish the requests in flight
+pub async fn run(listener: Listener, state: AppState) -> Result<(), String> {
+    let app = router(state);
+    match listener {
+        Listener::Tcp(listener) => axum::serve(listener, app).with_graceful_shutdown(shutdown_signal()).await,
+        #[cfg(unix)]
+        Listener::Unix(listener) => axum::serve(listener, app).with_graceful_shutdown(shutdown_signal()).await,
+    }
+    .map_err(|e| format!("Server error: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::{to_bytes, Body};
+    use axum::http::{Request, StatusCode};
+    use tower::ServiceExt;
+
+    async fn send(request: Request<Body>) -> (StatusCode, String) {
+        let state = AppState::new(Fortune::builder().year(2026), vec!["alice".to_string(), "bob".to_string()]);
+        send_to(state, request).await
+    }
+
+    async fn send_to(state: AppState, request: Request<Body>) -> (StatusCode, String) {
+        let response = router(state).oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    async fn get(uri: &str) -> (StatusCode, String) {
+        send(Request::get(uri).body(Body::empty()).unwrap()).await
+    }
+
+    async fn post(uri: &str, body: &str) -> (StatusCode, String) {
+        let request = Request::post(uri).header(header::CONTENT_TYPE, "application/json").body(Body::from(body.to_string()));
+        send(request.unwrap()).await
+    }
+
+    #[tokio::test]
+    async fn test_fortune_endpoint() {
+        let (status, body) = get("/fortune?seed=alice&year=2025").await;
+        assert_eq!(status, StatusCode::OK);
+        let fortune: Fortune = serde_json::from_str(&body).unwrap();
+        assert_eq!(fortune, Fortune::builder().year(2025).seed("alice").draw());
+        assert_eq!(get("/fortune").await.0, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_year_out_of_range() {
+        assert_eq!(get("/fortune?seed=alice&year=9999").await.0, StatusCode::OK);
+        for uri in ["/fortune?seed=alice&year=0", "/fortune?seed=alice&year=10000", "/art?seed=alice&year=10000"] {
+            let (status, body) = get(uri).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST, "{}", uri);
+            assert!(body.contains("out of range"), "{}", body);
+        }
+        let (status, body) = post("/batch", r#"{"seeds":["alice"],"year":4294967295}"#).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(body.contains("out of range"), "{}", body);
+    }
+
+    #[tokio::test]
+    async fn test_metrics_endpoint() {
+        let (status, body) = get("/metrics").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body.lines().filter(|l| l.starts_with("omikuji_overall_score{")).count(), 2);
+        assert!(body.contains(&seed_fingerprint("bob")));
+    }
+
+    #[tokio::test]
+    async fn test_art_endpoint() {
+        let (status, body) = get("/art?seed=alice").await;
+        assert_eq!(status, StatusCode::OK);
+        let fortune = Fortune::builder().year(2026).seed("alice").draw();
+        assert_eq!(body, art::framed(&fortune.art, 2026, fortune.grade, false));
+    }
+
+    #[tokio::test]
+    async fn test_batch_endpoint() {
+        let (status, body) = post("/batch", r#"{"seeds":["alice","bob"],"year":2025}"#).await;
+        assert_eq!(status, StatusCode::OK);
+        let fortunes: Vec<Fortune> = serde_json::from_str(&body).unwrap();
+        assert_eq!(fortunes.len(), 2);
+        assert_eq!(fortunes[1], Fortune::builder().year(2025).seed("bob").draw());
+
+        let seeds = serde_json::to_string(&vec!["x"; MAX_BATCH + 1]).unwrap();
+        let (status, body) = post("/batch", &format!(r#"{{"seeds":{}}}"#, seeds)).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(body.contains("\"error\""));
+    }
+
+    #[tokio::test]
+    async fn test_verify_endpoint() {
+        let fortune = Fortune::builder().year(2026).layout(crate::hash::Layout::V2).seed("alice").draw();
+        let (status, body) = post("/verify", &fortune.format_json()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(serde_json::from_str::<Value>(&body).unwrap()["checksum"], true);
+
+        let mut tampered: Value = serde_json::from_str(&fortune.format_json()).unwrap();
+        tampered["lucky_number"] = (fortune.lucky_number + 1).into();
+        assert_eq!(post("/verify", &tampered.to_string()).await.0, StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_openapi_document() {
+        let (status, body) = get("/openapi.json").await;
+        assert_eq!(status, StatusCode::OK);
+        let document: Value = serde_json::from_str(&body).unwrap();
+        for path in ["/fortune", "/art", "/batch", "/verify", "/metrics"] {
+            assert!(document["paths"][path].is_object(), "{}", path);
+        }
+        assert!(document["components"]["schemas"]["Fortune"]["properties"]["grade"].is_object());
+    }
+
+    #[tokio::test]
+    async fn test_tenants() {
+        let tenants = Tenants::parse("[[tenant]]\nname = \"a\"\nsalt = \"s\"\nhosts = [\"a.example\"]\napi_keys = [\"k\"]\n");
+        let state = AppState::new(Fortune::builder().year(2026), Vec::new()).with_tenants(tenants.unwrap());
+        let draw = |request: Request<Body>| {
+            let app = router(state.clone());
+            async move {
+                let response = app.oneshot(request).await.unwrap();
+                let status = response.status();
+                (status, to_bytes(response.into_body(), usize::MAX).await.unwrap())
+            }
+        };
+        let salted = Fortune::builder().year(2026).salt("s").seed("alice").draw();
+
+        let (status, body) = draw(Request::get("/fortune?seed=alice").header(API_KEY_HEADER, "k").body(Body::empty()).unwrap()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(serde_json::from_slice::<Fortune>(&body).unwrap(), salted);
+        let (status, body) = draw(Request::get("/fortune?seed=alice").header(header::HOST, "a.example:8080").body(Body::empty()).unwrap()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(serde_json::from_slice::<Fortune>(&body).unwrap(), salted);
+
+        let (status, _) = draw(Request::get("/fortune?seed=alice").header(header::HOST, "b.example").body(Body::empty()).unwrap()).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        let (status, _) = draw(Request::get("/metrics").header(API_KEY_HEADER, "wrong").body(Body::empty()).unwrap()).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_limits() {
+        let state = |limits| AppState::new(Fortune::builder().year(2026), Vec::new()).with_limits(limits);
+        let request = || Request::get("/fortune?seed=alice").body(Body::empty()).unwrap();
+        let limits = Limits { workers: 1, concurrency: 1, timeout: Duration::from_secs(30) };
+        assert_eq!(send_to(state(limits), request()).await.0, StatusCode::OK);
+
+        let (status, body) = send_to(state(Limits { concurrency: 0, ..limits }), request()).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert!(body.contains("Too many requests"), "{}", body);
+        // the only worker is taken, so the draw waits past the timeout
+        let busy = state(Limits { timeout: Duration::from_millis(20), ..limits });
+        let _worker = busy.workers.clone().acquire_owned().await.unwrap();
+        let (status, body) = send_to(busy, request()).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert!(body.contains("Timed out"), "{}", body);
+    }
+
+    #[test]
+    fn test_socket_activated() {
+        assert!(socket_activated(Some("42"), Some("1"), 42));
+        assert!(!socket_activated(Some("41"), Some("1"), 42));
+        assert!(!socket_activated(Some("42"), Some("0"), 42));
+        assert!(!socket_activated(None, None, 42));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_unix_socket() {
+        use std::io::{Read, Write};
+        let path = std::env::temp_dir().join(format!("hash-omikuji-serve-{}.sock", std::process::id()));
+        let bind = format!("unix:{}", path.display());
+        let listener = listen(&bind).await.unwrap();
+        assert_eq!(listener.to_string(), bind);
+        let state = AppState::new(Fortune::builder().year(2026), vec!["alice".to_string()]);
+        tokio::spawn(run(listener, state));
+
+        let client_path = path.clone();
+        let response = tokio::task::spawn_blocking(move || {
+            let mut stream = std::os::unix::net::UnixStream::connect(client_path).unwrap();
+            stream.write_all(b"GET /metrics HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n").unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        })
+        .await
+        .unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK"), "{}", response);
+        assert!(response.contains("omikuji_overall_score{"));
+
+        // the socket of a running server is kept, a stale one is replaced
+        assert!(listen(&bind).await.err().unwrap().contains("already listening"));
+        assert!(path.exists());
+        let stale = path.with_extension("stale.sock");
+        drop(std::os::unix::net::UnixListener::bind(&stale).unwrap());
+        assert!(listen(&format!("unix:{}", stale.display())).await.is_ok());
+        std::fs::remove_file(&stale).unwrap();
+        std::fs::remove_file(&path).unwrap();
+    }
+}
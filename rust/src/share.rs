@@ -0,0 +1,41 @@
+//! `--share-url`: a link to the web version that reproduces this draw. Everything
+//! goes in the fragment, so it never reaches the web server's logs; the seed itself
+//! is not included, only its fingerprint, which the page checks the visitor's seed against.
+
+use crate::hash::{Algo, Layout};
+use crate::output::Fortune;
+
+/// The web version (override with `share_base` in the config file)
+pub const DEFAULT_BASE: &str = "https://elzup.github.io/hash-omikuji/";
+
+/// `<base>#seed=<fingerprint>&year=<year>&layout=<v1|v2>`, plus `&algo=blake3` when not SHA-256
+pub fn url(base: &str, fortune: &Fortune) -> String {
+    let seed = fortune.seed.strip_prefix("device:").unwrap_or(&fortune.seed);
+    let layout = match fortune.layout {
+        Layout::V1 => "v1",
+        Layout::V2 => "v2",
+    };
+    let mut url = format!("{}#seed={}&year={}&layout={}", base.split('#').next().unwrap_or(base), seed, fortune.year, layout);
+    if fortune.algo == Algo::Blake3 {
+        url.push_str("&algo=blake3");
+    }
+    url
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::seed_fingerprint;
+
+    #[test]
+    fn test_url() {
+        let fortune = Fortune::builder().year(2026).seed("alice").layout(Layout::V2).draw();
+        let expected = format!("{}#seed={}&year=2026&layout=v2", DEFAULT_BASE, seed_fingerprint("alice"));
+        assert_eq!(url(DEFAULT_BASE, &fortune), expected);
+        assert!(!url(DEFAULT_BASE, &fortune).contains("alice"));
+
+        let fortune = Fortune::builder().year(2025).seed("alice").algo(Algo::Blake3).draw();
+        assert!(url("https://omikuji.example/#old", &fortune).starts_with("https://omikuji.example/#seed="));
+        assert!(url(DEFAULT_BASE, &fortune).ends_with("&year=2025&layout=v1&algo=blake3"));
+    }
+}
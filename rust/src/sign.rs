@@ -0,0 +1,236 @@
+use crate::fingerprint::{decode_hex, hex};
+use crate::output;
+use ed25519_dalek::{Signature as Ed25519Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const ALGORITHM: &str = "ed25519";
+
+/// Detached signature attached to a JSON fortune under the `signature` key
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema)]
+#[cfg_attr(feature = "serve", derive(utoipa::ToSchema))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject), graphql(rename_fields = "snake_case"))]
+pub struct Signature {
+    pub algorithm: String,
+    pub public_key: String,
+    pub value: String,
+}
+
+/// Compact JSON with keys sorted at every level (serde_json's default map is ordered),
+/// excluding the `signature` field itself
+pub fn canonical_json(document: &Value) -> String {
+    let mut document = document.clone();
+    if let Value::Object(ref mut map) = document {
+        map.remove("signature");
+    }
+    document.to_string()
+}
+
+pub fn public_key_path(secret_path: &Path) -> PathBuf {
+    let mut path = secret_path.as_os_str().to_owned();
+    path.push(".pub");
+    PathBuf::from(path)
+}
+
+/// Write a fresh key pair as hex: secret to `path`, public key to `path.pub`
+pub fn keygen(path: &Path) -> Result<String, String> {
+    let mut secret = [0u8; 32];
+    getrandom::getrandom(&mut secret).map_err(|e| format!("Failed to gather randomness: {}", e))?;
+    let key = SigningKey::from_bytes(&secret);
+    let public_hex = hex(key.verifying_key().as_bytes());
+
+    write_secret(path, &hex(&secret))?;
+    let pub_path = public_key_path(path);
+    fs::write(&pub_path, format!("{}\n", public_hex))
+        .map_err(|e| format!("Failed to write {}: {}", pub_path.display(), e))?;
+    Ok(public_hex)
+}
+
+#[cfg(unix)]
+fn write_secret(path: &Path, contents: &str) -> Result<(), String> {
+    use std::io::Write;
+    use std::os::unix::fs::OpenOptionsExt;
+    fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .open(path)
+        .and_then(|mut file| writeln!(file, "{}", contents))
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+#[cfg(not(unix))]
+fn write_secret(path: &Path, contents: &str) -> Result<(), String> {
+    if path.exists() {
+        return Err(format!("Refusing to overwrite {}", path.display()));
+    }
+    fs::write(path, format!("{}\n", contents))
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+fn read_key_bytes(path: &Path) -> Result<[u8; 32], String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    decode_hex(&text)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| format!("{} is not a 32-byte hex key", path.display()))
+}
+
+pub fn load_signing_key(path: &Path) -> Result<SigningKey, String> {
+    read_key_bytes(path).map(|bytes| SigningKey::from_bytes(&bytes))
+}
+
+pub fn load_verifying_key(path: &Path) -> Result<VerifyingKey, String> {
+    let bytes = read_key_bytes(path)?;
+    VerifyingKey::from_bytes(&bytes).map_err(|_| format!("{} is not a valid ed25519 public key", path.display()))
+}
+
+/// Public key from its hex encoding (the contents of a `.pub` file)
+pub fn parse_verifying_key(text: &str) -> Result<VerifyingKey, String> {
+    decode_hex(text)
+        .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+        .and_then(|bytes| VerifyingKey::from_bytes(&bytes).ok())
+        .ok_or_else(|| "Not a valid hex ed25519 public key".to_string())
+}
+
+pub fn sign(key: &SigningKey, document: &Value) -> Signature {
+    let signature = key.sign(canonical_json(document).as_bytes());
+    Signature {
+        algorithm: ALGORITHM.to_string(),
+        public_key: hex(key.verifying_key().as_bytes()),
+        value: hex(&signature.to_bytes()),
+    }
+}
+
+/// Check the embedded signature; if `expected` is given the signer must match it.
+/// Returns the signer's public key (hex) on success.
+pub fn verify(document: &Value, expected: Option<&VerifyingKey>) -> Result<String, String> {
+    let signature: Signature = document
+        .get("signature")
+        .cloned()
+        .ok_or("Document has no signature")
+        .and_then(|v| serde_json::from_value(v).map_err(|_| "Malformed signature field"))?;
+    if signature.algorithm != ALGORITHM {
+        return Err(format!("Unsupported signature algorithm: {}", signature.algorithm));
+    }
+
+    let key_bytes: [u8; 32] = decode_hex(&signature.public_key)
+        .and_then(|b| b.try_into().ok())
+        .ok_or("Malformed public key in signature")?;
+    let key = VerifyingKey::from_bytes(&key_bytes).map_err(|_| "Invalid public key in signature")?;
+    if let Some(expected) = expected {
+        if expected != &key {
+            return Err("Signed by a different key than expected".to_string());
+        }
+    }
+
+    let sig_bytes: [u8; 64] = decode_hex(&signature.value)
+        .and_then(|b| b.try_into().ok())
+        .ok_or("Malformed signature value")?;
+    key.verify(canonical_json(document).as_bytes(), &Ed25519Signature::from_bytes(&sig_bytes))
+        .map_err(|_| "Signature does not match document".to_string())?;
+    Ok(signature.public_key)
+}
+
+/// Outcome of [`verify_document`]
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
+#[cfg_attr(feature = "serve", derive(utoipa::ToSchema))]
+pub struct Verification {
+    /// The document carried a layout v2 checksum and it matched
+    pub checksum: bool,
+    /// Public key (hex) of the valid signature, if the document was signed
+    pub signer: Option<String>,
+}
+
+/// Check whatever a JSON fortune carries: its layout v2 checksum and/or its signature.
+/// With `expected`, a signature by that key is required.
+pub fn verify_document(document: &Value, expected: Option<&VerifyingKey>) -> Result<Verification, String> {
+    let has_checksum = document.get("layout").and_then(|v| v.as_str()) == Some("v2");
+    let has_signature = document.get("signature").is_some();
+    if !has_checksum && !has_signature && expected.is_none() {
+        return Err("Nothing to verify: document is unsigned and has no layout v2 checksum".to_string());
+    }
+    let mut verification = Verification::default();
+    if has_checksum {
+        output::verify_checksum(document)?;
+        verification.checksum = true;
+    }
+    if has_signature || expected.is_some() {
+        verification.signer = Some(verify(document, expected)?);
+    }
+    Ok(verification)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn test_key() -> SigningKey {
+        SigningKey::from_bytes(&[7u8; 32])
+    }
+
+    fn signed_document() -> Value {
+        let mut document = json!({ "year": 2026, "lucky_number": 95 });
+        let signature = sign(&test_key(), &document);
+        document["signature"] = serde_json::to_value(signature).unwrap();
+        document
+    }
+
+    #[test]
+    fn test_canonical_json_sorted_and_unsigned() {
+        let document = json!({ "b": 1, "a": { "d": 2, "c": 3 }, "signature": {} });
+        assert_eq!(canonical_json(&document), r#"{"a":{"c":3,"d":2},"b":1}"#);
+    }
+
+    #[test]
+    fn test_sign_and_verify() {
+        let document = signed_document();
+        let signer = verify(&document, Some(&test_key().verifying_key())).unwrap();
+        assert_eq!(signer, hex(test_key().verifying_key().as_bytes()));
+    }
+
+    #[test]
+    fn test_verify_detects_tampering() {
+        let mut document = signed_document();
+        document["lucky_number"] = json!(96);
+        assert!(verify(&document, None).is_err());
+    }
+
+    #[test]
+    fn test_verify_rejects_unexpected_signer() {
+        let document = signed_document();
+        let other = SigningKey::from_bytes(&[8u8; 32]).verifying_key();
+        assert!(verify(&document, Some(&other)).is_err());
+    }
+
+    #[test]
+    fn test_verify_requires_signature() {
+        assert!(verify(&json!({ "year": 2026 }), None).is_err());
+    }
+
+    #[test]
+    fn test_verify_document() {
+        let key = test_key().verifying_key();
+        let verification = verify_document(&signed_document(), Some(&key)).unwrap();
+        assert_eq!(verification, Verification { checksum: false, signer: Some(hex(key.as_bytes())) });
+        assert!(verify_document(&json!({ "year": 2026 }), None).is_err());
+        assert_eq!(parse_verifying_key(&format!("{}\n", hex(key.as_bytes()))).unwrap(), key);
+        assert!(parse_verifying_key("abcd").is_err());
+    }
+
+    #[test]
+    fn test_keygen_roundtrip() {
+        let dir = std::env::temp_dir().join(format!("hash-omikuji-keygen-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("key");
+        let _ = fs::remove_file(&path);
+        let public_hex = keygen(&path).unwrap();
+        let key = load_signing_key(&path).unwrap();
+        assert_eq!(hex(key.verifying_key().as_bytes()), public_hex);
+        assert_eq!(load_verifying_key(&public_key_path(&path)).unwrap(), key.verifying_key());
+        assert!(keygen(&path).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,93 @@
+use crate::hash::{Algo, SeedHasher};
+use crate::luck::{Grade, GradeWeights};
+use std::fmt::Write;
+use std::ops::Range;
+
+/// Grade counts from drawing `samples` fortunes over synthetic seeds, in `Grade::ALL` order
+pub fn grade_counts(year: u32, samples: u64, weights: &GradeWeights) -> [u64; 7] {
+    grade_counts_range(year, 0..samples, weights)
+}
+
+/// Grade counts of the synthetic seeds numbered `range`, hashed without allocating per seed
+pub fn grade_counts_range(year: u32, range: Range<u64>, weights: &GradeWeights) -> [u64; 7] {
+    let mut hasher = SeedHasher::new(year, Algo::Sha256);
+    let mut seed = String::new();
+    let mut counts = [0u64; 7];
+    for i in range {
+        seed.clear();
+        write!(seed, "simulate-{}", i).unwrap();
+        let grade = Grade::from_bits(hasher.hash(&seed).grade_bits(), weights);
+        counts[6 - grade.rank() as usize] += 1;
+    }
+    counts
+}
+
+/// [`grade_counts`] split over the threads of `pool`
+#[cfg(feature = "cli")]
+pub fn grade_counts_parallel(year: u32, samples: u64, weights: &GradeWeights, pool: &rayon::ThreadPool) -> [u64; 7] {
+    use rayon::prelude::*;
+    let chunks = pool.current_num_threads() as u64 * 4;
+    let size = samples.div_ceil(chunks).max(1);
+    pool.install(|| {
+        (0..samples.div_ceil(size))
+            .into_par_iter()
+            .map(|chunk| grade_counts_range(year, chunk * size..((chunk + 1) * size).min(samples), weights))
+            .reduce(|| [0; 7], |a, b| std::array::from_fn(|i| a[i] + b[i]))
+    })
+}
+
+/// Table of realized vs configured share per grade
+pub fn format_report(counts: &[u64; 7], weights: &GradeWeights) -> String {
+    let samples: u64 = counts.iter().sum();
+    let total_weight: u64 = weights.0.iter().map(|&w| w as u64).sum();
+    let mut output = format!("Samples : {}\n\n", samples);
+    output.push_str("Grade   Count      Realized  Expected  Diff\n");
+    for (i, grade) in Grade::ALL.iter().enumerate() {
+        let name = grade.to_string();
+        let pad = 8 - name.chars().count() * 2;
+        let realized = counts[i] as f64 * 100.0 / samples.max(1) as f64;
+        let expected = weights.0[i] as f64 * 100.0 / total_weight as f64;
+        output.push_str(&format!(
+            "{}{}{:<10} {:7.2}%  {:7.2}%  {:+.2}\n",
+            name,
+            " ".repeat(pad),
+            counts[i],
+            realized,
+            expected,
+            realized - expected
+        ));
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grade_counts() {
+        let counts = grade_counts(2026, 500, &GradeWeights::default());
+        assert_eq!(counts.iter().sum::<u64>(), 500);
+
+        let only_daikichi = GradeWeights([1, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(grade_counts(2026, 50, &only_daikichi), [50, 0, 0, 0, 0, 0, 0]);
+    }
+
+    #[cfg(feature = "cli")]
+    #[test]
+    fn test_parallel_counts_match() {
+        let weights = GradeWeights::default();
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(3).build().unwrap();
+        assert_eq!(grade_counts_parallel(2026, 1001, &weights, &pool), grade_counts(2026, 1001, &weights));
+        assert_eq!(grade_counts_parallel(2026, 0, &weights, &pool), [0; 7]);
+    }
+
+    #[test]
+    fn test_format_report() {
+        let weights = GradeWeights([1, 1, 0, 0, 0, 0, 0]);
+        let report = format_report(&[3, 1, 0, 0, 0, 0, 0], &weights);
+        assert!(report.starts_with("Samples : 4\n"));
+        assert!(report.contains("大吉    3            75.00%    50.00%  +25.00\n"));
+        assert!(report.contains("大凶    0             0.00%     0.00%  +0.00\n"));
+    }
+}
@@ -0,0 +1,148 @@
+//! `--output`: documents written through a buffer to a temporary file next to the
+//! target and renamed over it once complete, so a reader (a web server, a calendar
+//! sync, a cron job) never sees a half-written HTML, SVG, ICS or feed file.
+
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Stdout, Write};
+use std::path::{Path, PathBuf};
+
+/// A file that replaces `path` on [`commit`](Self::commit); dropped uncommitted, it
+/// leaves `path` untouched and removes the temporary file
+pub struct AtomicFile {
+    path: PathBuf,
+    temp: PathBuf,
+    writer: Option<BufWriter<File>>,
+}
+
+impl AtomicFile {
+    pub fn create(path: &Path) -> Result<Self, String> {
+        let name = path.file_name().ok_or_else(|| format!("Not a file path: {}", path.display()))?;
+        let temp = path.with_file_name(format!(".{}.{}.tmp", name.to_string_lossy(), std::process::id()));
+        let file = File::create(&temp).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        Ok(Self { path: path.to_path_buf(), temp, writer: Some(BufWriter::new(file)) })
+    }
+
+    /// Flush, sync and rename the file into place; on failure the temporary file is
+    /// removed (when `self` drops) and `path` is left untouched
+    pub fn commit(mut self) -> Result<(), String> {
+        let error = |e: io::Error| format!("Failed to write {}: {}", self.path.display(), e);
+        let writer = self.writer.as_mut().expect("an uncommitted file has a writer");
+        writer.flush().map_err(error)?;
+        writer.get_ref().sync_all().map_err(error)?;
+        fs::rename(&self.temp, &self.path).map_err(error)?;
+        self.writer = None;
+        Ok(())
+    }
+}
+
+impl Write for AtomicFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.writer.as_mut().expect("an uncommitted file has a writer").write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.as_mut().expect("an uncommitted file has a writer").flush()
+    }
+}
+
+impl Drop for AtomicFile {
+    fn drop(&mut self) {
+        // Still holding the writer: not renamed into place
+        if let Some(writer) = self.writer.take() {
+            drop(writer.into_parts());
+            let _ = fs::remove_file(&self.temp);
+        }
+    }
+}
+
+/// Write `contents` to `path` atomically
+pub fn write(path: &Path, contents: impl AsRef<[u8]>) -> Result<(), String> {
+    let mut file = AtomicFile::create(path)?;
+    file.write_all(contents.as_ref()).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    file.commit()
+}
+
+/// Where a command's document goes: stdout, or the `--output` file
+pub enum Sink {
+    Stdout(BufWriter<Stdout>),
+    File(AtomicFile),
+}
+
+impl Sink {
+    pub fn open(path: Option<&Path>) -> Result<Self, String> {
+        match path {
+            Some(path) => AtomicFile::create(path).map(Sink::File),
+            None => Ok(Sink::Stdout(BufWriter::new(io::stdout()))),
+        }
+    }
+
+    /// Flush stdout, or put the file in place
+    pub fn finish(self) -> Result<(), String> {
+        match self {
+            Sink::Stdout(mut stdout) => stdout.flush().map_err(|e| format!("Failed to write the output: {}", e)),
+            Sink::File(file) => file.commit(),
+        }
+    }
+}
+
+impl Write for Sink {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Sink::Stdout(stdout) => stdout.write(buf),
+            Sink::File(file) => file.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Sink::Stdout(stdout) => stdout.flush(),
+            Sink::File(file) => file.flush(),
+        }
+    }
+}
+
+/// Print `document`, or write it to `path` atomically
+pub fn emit(path: Option<&Path>, document: &str) -> Result<(), String> {
+    match path {
+        Some(path) => write(path, document),
+        None => {
+            print!("{}", document);
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_atomic_replace() {
+        let dir = std::env::temp_dir().join(format!("hash-omikuji-sink-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("feed.xml");
+        fs::write(&path, "old").unwrap();
+
+        let mut file = AtomicFile::create(&path).unwrap();
+        file.write_all(b"new").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "old");
+        file.commit().unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+
+        let mut abandoned = AtomicFile::create(&path).unwrap();
+        abandoned.write_all(b"partial").unwrap();
+        drop(abandoned);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+
+        // A failed rename (onto a non-empty directory) leaves no temporary file behind
+        let blocked = dir.join("blocked");
+        fs::create_dir_all(blocked.join("inside")).unwrap();
+        let mut file = AtomicFile::create(&blocked).unwrap();
+        file.write_all(b"new").unwrap();
+        assert!(file.commit().is_err());
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 2);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
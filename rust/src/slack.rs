@@ -0,0 +1,72 @@
+//! `--output-format slack`: a Block Kit payload for an incoming webhook.
+
+use crate::art;
+use crate::output::Fortune;
+use serde_json::{json, Value};
+
+/// Slack mrkdwn treats `&`, `<` and `>` as control characters
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+fn field(name: &str, value: &str) -> Value {
+    json!({ "type": "mrkdwn", "text": format!("*{}*\n{}", name, escape(value)) })
+}
+
+/// Header with the grade, the headline fields, the advice and the framed art in a code block.
+/// `text` is the notification fallback.
+pub fn payload(fortune: &Fortune) -> Value {
+    let headline = format!("Hash-Omikuji {}: {} ({:#})", fortune.year, fortune.grade, fortune.grade);
+    let mut best: Vec<_> = fortune.luck_scores.iter().collect();
+    best.sort_by_key(|s| std::cmp::Reverse(s.score));
+    let fields = vec![
+        field("Overall Score", &format!("{} {}", fortune.overall_score, fortune.star_rating)),
+        field("Lucky Number", &fortune.lucky_number.to_string()),
+        field("Lucky Color", &fortune.lucky_color),
+        field("Lucky Day", &fortune.lucky_day_text()),
+        field("Lucky Item", &fortune.lucky_item),
+        field("Lucky Action", &fortune.lucky_action),
+        field("Lucky Emoji", &fortune.lucky_emoji),
+        field("Best Luck", &best.first().map_or(String::new(), |s| format!("{} {}", s.label, s.score))),
+    ];
+    json!({
+        "text": headline,
+        "blocks": [
+            { "type": "header", "text": { "type": "plain_text", "text": format!("🎍 {} 🎍", headline), "emoji": true } },
+            { "type": "section", "fields": fields },
+            { "type": "section", "text": { "type": "mrkdwn", "text": format!("> {}", escape(&fortune.advice)) } },
+            {
+                "type": "section",
+                "text": {
+                    "type": "mrkdwn",
+                    "text": format!("```\n{}```", escape(&art::framed(&fortune.art, fortune.year, fortune.grade, false)))
+                }
+            },
+            { "type": "context", "elements": [{ "type": "mrkdwn", "text": format!("`{}`", fortune.fingerprint) }] },
+        ],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_payload_blocks() {
+        let fortune = Fortune::builder().year(2026).seed("alice").draw();
+        let payload = payload(&fortune);
+        let blocks = payload["blocks"].as_array().unwrap();
+        let types: Vec<_> = blocks.iter().map(|b| b["type"].as_str().unwrap()).collect();
+        assert_eq!(types, ["header", "section", "section", "section", "context"]);
+        assert!(blocks[0]["text"]["text"].as_str().unwrap().contains(&fortune.grade.to_string()));
+        // Block Kit allows at most 10 fields per section
+        assert!(blocks[1]["fields"].as_array().unwrap().len() <= 10);
+        assert!(blocks[3]["text"]["text"].as_str().unwrap().starts_with("```\n+-[hash-omikuji 2026]"));
+        assert_eq!(payload["text"], format!("Hash-Omikuji 2026: {} ({:#})", fortune.grade, fortune.grade));
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape("<b> & c"), "&lt;b&gt; &amp; c");
+    }
+}
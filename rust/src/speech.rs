@@ -0,0 +1,144 @@
+//! Spoken output for `--output-format speech` and `ssml`: the headline fields as a short
+//! natural-language paragraph for screen readers and `espeak`, or as SSML for
+//! smart-speaker routines and TTS services.
+
+use crate::lang::Lang;
+use crate::output::Fortune;
+use chrono::NaiveDate;
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// The opening sentence split around the grade, which SSML emphasizes
+fn headline(fortune: &Fortune) -> (String, &'static str, &'static str) {
+    match fortune.lang {
+        Lang::Ja => (format!("{}年のあなたの運勢は", fortune.year), fortune.grade.kanji(), "です。"),
+        Lang::En => (format!("Your fortune for {} is ", fortune.year), fortune.grade.english(), "."),
+    }
+}
+
+/// Everything after the headline, one sentence each
+fn details(fortune: &Fortune) -> Vec<String> {
+    let date = NaiveDate::parse_from_str(&fortune.lucky_date, "%Y-%m-%d").ok();
+    let mut sentences = Vec::new();
+    match fortune.lang {
+        Lang::Ja => {
+            sentences.push(format!("総合点は100点中{}点です。", fortune.overall_score));
+            sentences.push(format!("ラッキーナンバーは{}です。", fortune.lucky_number));
+            if let Some(date) = date {
+                let (hour, minute) = fortune.lucky_time.split_once(':').unwrap_or((&fortune.lucky_time, "00"));
+                sentences.push(format!(
+                    "ラッキーデーは{}、ラッキータイムは{}時{}分です。",
+                    date.format("%-m月%-d日"),
+                    hour.parse::<u8>().unwrap_or(0),
+                    minute
+                ));
+            }
+            // Category labels are English, so the strongest luck is left to the English paragraph
+            sentences.push(format!("ラッキーアイテムは{}、ラッキーアクションは「{}」です。", fortune.lucky_item, fortune.lucky_action));
+        }
+        Lang::En => {
+            sentences.push(format!("Your overall score is {} out of 100.", fortune.overall_score));
+            sentences.push(format!("Your lucky number is {}.", fortune.lucky_number));
+            if let Some(date) = date {
+                sentences.push(format!(
+                    "Your lucky day is {}, and your lucky time is {}.",
+                    date.format("%A, %B %-d"),
+                    fortune.lucky_time
+                ));
+            }
+            sentences.push(format!("Your lucky item is the {}.", fortune.lucky_item));
+            sentences.push(format!("To invite luck, {}.", lowercase_first(&fortune.lucky_action)));
+            if let Some(best) = fortune.luck_scores.iter().max_by_key(|s| s.score) {
+                sentences.push(format!("Your strongest luck is {}, at {}.", best.label, best.score));
+            }
+        }
+    }
+    sentences.push(fortune.advice.clone());
+    sentences
+}
+
+fn lowercase_first(text: &str) -> String {
+    let mut chars = text.chars();
+    chars.next().map(|c| c.to_lowercase().chain(chars).collect()).unwrap_or_default()
+}
+
+/// One paragraph of plain sentences, ending in a newline
+pub fn paragraph(fortune: &Fortune) -> String {
+    let (before, grade, after) = headline(fortune);
+    let separator = match fortune.lang {
+        Lang::Ja => "",
+        Lang::En => " ",
+    };
+    let mut sentences = vec![format!("{}{}{}", before, grade, after)];
+    sentences.extend(details(fortune));
+    format!("{}\n", sentences.join(separator))
+}
+
+/// SSML 1.1 document: the headline with the grade emphasized, a pause, then the details
+pub fn ssml(fortune: &Fortune) -> String {
+    let (before, grade, after) = headline(fortune);
+    let lang = match fortune.lang {
+        Lang::Ja => "ja-JP",
+        Lang::En => "en-US",
+    };
+    let mut output = format!("<speak version=\"1.1\" xmlns=\"http://www.w3.org/2001/10/synthesis\" xml:lang=\"{}\">\n", lang);
+    output.push_str(&format!(
+        "  <p><s>{}<emphasis level=\"strong\">{}</emphasis>{}</s></p>\n",
+        escape(&before),
+        grade,
+        after
+    ));
+    output.push_str("  <break time=\"700ms\"/>\n  <p>\n");
+    for sentence in details(fortune) {
+        output.push_str(&format!("    <s>{}</s>\n", escape(&sentence)));
+    }
+    output.push_str("  </p>\n</speak>\n");
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::HashBits;
+
+    fn fortune(lang: Lang) -> Fortune {
+        let hash = HashBits::from_seed(2026, "test-user");
+        Fortune::from_hash(&hash, 2026, "test-user").with_lang(&hash, lang)
+    }
+
+    #[test]
+    fn test_paragraph() {
+        let fortune = fortune(Lang::En);
+        let text = paragraph(&fortune);
+        assert!(text.starts_with(&format!("Your fortune for 2026 is {}. Your overall score is ", fortune.grade.english())));
+        assert!(text.contains(&format!("Your lucky number is {}.", fortune.lucky_number)));
+        assert!(text.contains(&format!("and your lucky time is {}.", fortune.lucky_time)));
+        assert!(text.ends_with(&format!("{}\n", fortune.advice)));
+        assert_eq!(text.lines().count(), 1);
+
+        let fortune = self::fortune(Lang::Ja);
+        let text = paragraph(&fortune);
+        assert!(text.starts_with(&format!("2026年のあなたの運勢は{}です。総合点は", fortune.grade.kanji())));
+        assert!(!text.contains("Your"));
+    }
+
+    #[test]
+    fn test_ssml() {
+        let mut fortune = fortune(Lang::En);
+        fortune.advice = "Ship it & rest <now>.".to_string();
+        let ssml = ssml(&fortune);
+        assert!(ssml.starts_with("<speak version=\"1.1\" xmlns=\"http://www.w3.org/2001/10/synthesis\" xml:lang=\"en-US\">\n"));
+        assert!(ssml.contains(&format!("<emphasis level=\"strong\">{}</emphasis>.</s>", fortune.grade.english())));
+        assert!(ssml.contains("<s>Ship it &amp; rest &lt;now&gt;.</s>"));
+        assert!(ssml.ends_with("</speak>\n"));
+        assert!(self::ssml(&self::fortune(Lang::Ja)).contains("xml:lang=\"ja-JP\""));
+    }
+
+    #[test]
+    fn test_lowercase_first() {
+        assert_eq!(lowercase_first("Take a walk"), "take a walk");
+        assert_eq!(lowercase_first(""), "");
+    }
+}
@@ -0,0 +1,139 @@
+//! Streaks of consecutive years drawn, from the history. Only real draws count: a
+//! `--force-year` or `--date` preview is recorded as forced and never extends or keeps a streak,
+//! and neither does an entry drawn outside the year it is for.
+
+use crate::history::HistoryEntry;
+use crate::lang::Lang;
+use chrono::Datelike;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Where the latest streak stands in the current year
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    /// This year is drawn
+    Current,
+    /// Last year is drawn but this year is not yet; drawing keeps the streak
+    Pending,
+    /// A year was missed
+    Broken,
+}
+
+/// A run of consecutive drawn years
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Streak {
+    pub first: u32,
+    pub last: u32,
+    pub status: Status,
+}
+
+/// Years with a real draw: not forced, and drawn during the year itself
+pub fn drawn_years(entries: &[HistoryEntry]) -> BTreeSet<u32> {
+    entries
+        .iter()
+        .filter(|entry| !entry.forced && entry.drawn_on.year() as u32 == entry.year)
+        .map(|entry| entry.year)
+        .collect()
+}
+
+/// Length of the streak ending at each drawn year
+pub fn lengths(years: &BTreeSet<u32>) -> BTreeMap<u32, u32> {
+    let mut lengths = BTreeMap::new();
+    for &year in years {
+        let length = year.checked_sub(1).and_then(|previous| lengths.get(&previous)).copied().unwrap_or(0) + 1;
+        lengths.insert(year, length);
+    }
+    lengths
+}
+
+/// The latest streak up to `this_year`; None without any real draw
+pub fn current(entries: &[HistoryEntry], this_year: u32) -> Option<Streak> {
+    let years = drawn_years(entries);
+    let lengths = lengths(&years);
+    let (&last, &length) = lengths.range(..=this_year).next_back()?;
+    let status = match this_year - last {
+        0 => Status::Current,
+        1 => Status::Pending,
+        _ => Status::Broken,
+    };
+    Some(Streak { first: last + 1 - length, last, status })
+}
+
+impl Streak {
+    pub fn length(&self) -> u32 {
+        self.last - self.first + 1
+    }
+
+    /// `🔥 5-year streak! (2022-2026)`, or a warning while the streak waits on this year
+    pub fn message(&self, lang: Lang) -> String {
+        let (length, first, last) = (self.length(), self.first, self.last);
+        match (self.status, lang) {
+            (Status::Current, Lang::Ja) if length == 1 => format!("🔥 連続記録スタート ({})", last),
+            (Status::Current, Lang::En) if length == 1 => format!("🔥 Streak started ({})", last),
+            (Status::Current, Lang::Ja) => format!("🔥 {}年連続！ ({}-{})", length, first, last),
+            (Status::Current, Lang::En) => format!("🔥 {}-year streak! ({}-{})", length, first, last),
+            (Status::Pending, Lang::Ja) => format!("⚠️ {}年も引いて{}年連続の記録をつなぎましょう ({}-{})", last + 1, length, first, last),
+            (Status::Pending, Lang::En) => format!("⚠️ Draw for {} to keep your {}-year streak going ({}-{})", last + 1, length, first, last),
+            (Status::Broken, Lang::Ja) => format!("{}年連続の記録は{}年で途切れました", length, last),
+            (Status::Broken, Lang::En) => format!("Your {}-year streak ended in {}", length, last),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::luck::Grade;
+    use chrono::NaiveDate;
+
+    fn entry(year: u32, forced: bool) -> HistoryEntry {
+        HistoryEntry {
+            year,
+            drawn_on: NaiveDate::from_ymd_opt(if forced { 2026 } else { year as i32 }, 1, 1).unwrap(),
+            forced,
+            grade: Grade::Kichi,
+            overall_score: 50,
+            lucky_day_number: 100,
+            fingerprint: "00".repeat(32),
+        }
+    }
+
+    #[test]
+    fn test_current_streak() {
+        let entries: Vec<_> = (2022..=2026).map(|year| entry(year, false)).collect();
+        let streak = current(&entries, 2026).unwrap();
+        assert_eq!((streak.first, streak.last, streak.status, streak.length()), (2022, 2026, Status::Current, 5));
+        assert_eq!(streak.message(Lang::En), "🔥 5-year streak! (2022-2026)");
+        assert_eq!(streak.message(Lang::Ja), "🔥 5年連続！ (2022-2026)");
+
+        assert_eq!(current(&entries, 2027).unwrap().status, Status::Pending);
+        assert_eq!(current(&entries, 2027).unwrap().message(Lang::En), "⚠️ Draw for 2027 to keep your 5-year streak going (2022-2026)");
+        assert_eq!(current(&entries, 2028).unwrap().message(Lang::En), "Your 5-year streak ended in 2026");
+        assert_eq!(current(&[], 2026), None);
+    }
+
+    #[test]
+    fn test_gap_restarts() {
+        let entries = [entry(2020, false), entry(2021, false), entry(2023, false), entry(2024, false), entry(2024, false)];
+        let streak = current(&entries, 2024).unwrap();
+        assert_eq!((streak.first, streak.length()), (2023, 2));
+        assert_eq!(lengths(&drawn_years(&entries)).values().copied().collect::<Vec<_>>(), vec![1, 2, 1, 2]);
+    }
+
+    #[test]
+    fn test_previews_do_not_count() {
+        // A forced 2025 preview does not bridge the gap, a forced 2027 one does not extend
+        let entries = [entry(2024, false), entry(2025, true), entry(2026, false), entry(2027, true)];
+        let streak = current(&entries, 2026).unwrap();
+        assert_eq!((streak.first, streak.last), (2026, 2026));
+        assert_eq!(streak.message(Lang::En), "🔥 Streak started (2026)");
+
+        // A real draw survives a later preview of the same year
+        let entries = [entry(2025, false), entry(2026, false), entry(2026, true)];
+        assert_eq!(current(&entries, 2026).unwrap().length(), 2);
+
+        // Backdated entries do not count either
+        let mut backdated = entry(2025, false);
+        backdated.drawn_on = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+        assert_eq!(current(&[backdated, entry(2026, false)], 2026).unwrap().length(), 1);
+    }
+}
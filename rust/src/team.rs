@@ -0,0 +1,32 @@
+//! Member lists (`team.txt`): one seed per line, shared by the commands that draw for a group.
+
+use crate::error::Error;
+use std::fs;
+use std::path::Path;
+
+/// One seed per line; blank lines and `#` comments are skipped
+pub fn read_seeds(path: &Path) -> Result<Vec<String>, Error> {
+    let text = fs::read_to_string(path).map_err(|e| Error::Io(format!("Failed to read {}: {}", path.display(), e)))?;
+    Ok(parse_seeds(&text))
+}
+
+pub fn parse_seeds(text: &str) -> Vec<String> {
+    text.lines().filter_map(parse_seed).map(str::to_string).collect()
+}
+
+/// The seed of one line, or None for a blank line or a comment
+pub fn parse_seed(line: &str) -> Option<&str> {
+    let line = line.trim();
+    (!line.is_empty() && !line.starts_with('#')).then_some(line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_seeds() {
+        assert_eq!(parse_seeds("# team\nalice\n\n  bob  \n"), ["alice", "bob"]);
+        assert!(parse_seeds("").is_empty());
+    }
+}
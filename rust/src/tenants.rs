@@ -0,0 +1,121 @@
+//! `serve --tenants`: several communities on one deployment, each drawing with its own
+//! secret salt so nobody can predict another tenant's fortunes. A request belongs to the
+//! tenant whose API key it sends in `X-API-Key`, otherwise to the one serving its host name.
+
+use crate::error::Error;
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use zeroize::Zeroizing;
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Tenant {
+    pub name: String,
+    /// Secret mixed into every draw; see [`FortuneBuilder::salt`](crate::builder::FortuneBuilder::salt)
+    pub salt: Zeroizing<String>,
+    /// Host names (without port) served as this tenant
+    #[serde(default)]
+    pub hosts: Vec<String>,
+    #[serde(default)]
+    pub api_keys: Vec<Zeroizing<String>>,
+}
+
+/// The `[[tenant]]` tables of a tenants file
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Tenants {
+    #[serde(default, rename = "tenant")]
+    tenants: Vec<Tenant>,
+}
+
+impl Tenants {
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let text = fs::read_to_string(path).map_err(|e| Error::Io(format!("Failed to read {}: {}", path.display(), e)))?;
+        Self::parse(&text).map_err(|e| Error::Format(format!("Invalid tenants file {}: {}", path.display(), e)))
+    }
+
+    /// Parse and check that names, hosts and API keys are unique and every salt is set
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let tenants: Tenants = toml::from_str(text).map_err(|e| e.message().to_string())?;
+        let (mut names, mut hosts, mut keys) = (HashSet::new(), HashSet::new(), HashSet::new());
+        for tenant in &tenants.tenants {
+            if !names.insert(tenant.name.as_str()) {
+                return Err(format!("Duplicate tenant '{}'", tenant.name));
+            }
+            if tenant.salt.is_empty() {
+                return Err(format!("Tenant '{}' has an empty salt", tenant.name));
+            }
+            if let Some(host) = tenant.hosts.iter().find(|host| !hosts.insert(host.to_ascii_lowercase())) {
+                return Err(format!("Host '{}' belongs to more than one tenant", host));
+            }
+            if tenant.api_keys.iter().any(|key| !keys.insert(key.as_str())) {
+                return Err(format!("Tenant '{}' reuses an API key", tenant.name));
+            }
+        }
+        Ok(tenants)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tenants.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.tenants.len()
+    }
+
+    /// The tenant owning `api_key`, or else serving `host` (a Host header; any port is ignored)
+    pub fn select(&self, host: Option<&str>, api_key: Option<&str>) -> Option<&Tenant> {
+        if let Some(key) = api_key {
+            return self.tenants.iter().find(|t| t.api_keys.iter().any(|k| k.as_str() == key));
+        }
+        let host = host?;
+        let name = match host.rsplit_once(':') {
+            Some((name, port)) if !name.ends_with(':') && port.bytes().all(|b| b.is_ascii_digit()) => name,
+            _ => host,
+        };
+        self.tenants.iter().find(|t| t.hosts.iter().any(|h| h.eq_ignore_ascii_case(name)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"
+[[tenant]]
+name = "rustaceans"
+salt = "crab-secret"
+hosts = ["omikuji.rust.example"]
+api_keys = ["rk-1", "rk-2"]
+
+[[tenant]]
+name = "gophers"
+salt = "gopher-secret"
+hosts = ["omikuji.go.example"]
+"#;
+
+    #[test]
+    fn test_select() {
+        let tenants = Tenants::parse(SAMPLE).unwrap();
+        assert_eq!(tenants.len(), 2);
+        let name = |host, key| tenants.select(host, key).map(|t| t.name.as_str());
+        assert_eq!(name(Some("omikuji.go.example"), None), Some("gophers"));
+        assert_eq!(name(Some("OMIKUJI.go.example:8080"), None), Some("gophers"));
+        assert_eq!(name(Some("omikuji.go.example"), Some("rk-2")), Some("rustaceans"));
+        assert_eq!(name(Some("omikuji.go.example"), Some("wrong")), None);
+        assert_eq!(name(Some("elsewhere.example"), None), None);
+        assert_eq!(name(None, None), None);
+    }
+
+    #[test]
+    fn test_parse_rejects_ambiguous_tenants() {
+        let duplicate_host = SAMPLE.replace("omikuji.go.example", "omikuji.rust.example");
+        assert!(Tenants::parse(&duplicate_host).unwrap_err().contains("more than one tenant"));
+        assert!(Tenants::parse(&SAMPLE.replace("gophers", "rustaceans")).unwrap_err().contains("Duplicate"));
+        assert!(Tenants::parse(&SAMPLE.replace("gopher-secret", "")).unwrap_err().contains("empty salt"));
+        assert!(Tenants::parse("[[tenant]]\nname = \"x\"\n").is_err());
+        assert!(Tenants::parse("").unwrap().is_empty());
+    }
+}
@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+
+/// Color theme for rendered artwork (SVG charts, art heatmap)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[serde(rename_all = "lowercase")]
+pub enum Theme {
+    /// Vermilion and gold on white, like a shrine gate
+    #[default]
+    Shrine,
+    /// Black ink on washi paper
+    Ink,
+    /// Cherry-blossom pinks
+    Sakura,
+}
+
+/// Colors of a theme as `#RRGGBB`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Palette {
+    pub background: &'static str,
+    pub foreground: &'static str,
+    pub accent: &'static str,
+    pub grid: &'static str,
+    /// Low-to-high color ramp for intensity scales
+    pub ramp: [&'static str; 5],
+}
+
+impl Theme {
+    pub fn palette(self) -> Palette {
+        match self {
+            Theme::Shrine => Palette {
+                background: "#FFFDF8",
+                foreground: "#2B2B2B",
+                accent: "#D7261E",
+                grid: "#E6D5B8",
+                ramp: ["#FFF4D6", "#F9C74F", "#F08A24", "#D7261E", "#7A0E0A"],
+            },
+            Theme::Ink => Palette {
+                background: "#F5F1E8",
+                foreground: "#1A1A1A",
+                accent: "#333333",
+                grid: "#CFC8BA",
+                ramp: ["#E8E3D8", "#B5AFA3", "#807A70", "#4D4943", "#1A1A1A"],
+            },
+            Theme::Sakura => Palette {
+                background: "#FFF7FA",
+                foreground: "#4A2C3A",
+                accent: "#E05A8A",
+                grid: "#F4CADB",
+                ramp: ["#FDE7EF", "#F9B8CF", "#F28AB0", "#E05A8A", "#9E2A5B"],
+            },
+        }
+    }
+}
+
+/// `#RRGGBB` to components; malformed input reads as black
+pub fn rgb(hex: &str) -> (u8, u8, u8) {
+    let channel = |i: usize| hex.get(i..i + 2).and_then(|c| u8::from_str_radix(c, 16).ok()).unwrap_or(0);
+    (channel(1), channel(3), channel(5))
+}
@@ -0,0 +1,223 @@
+use crate::hash::HashBits;
+use crate::lang::Lang;
+use serde::{Deserialize, Serialize};
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+
+/// Classic omikuji life topics, in slip order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "serve", derive(utoipa::ToSchema))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
+#[cfg_attr(feature = "std", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum Topic {
+    Wish,
+    Awaited,
+    Lost,
+    Travel,
+    Business,
+    Study,
+    Market,
+    Dispute,
+    Love,
+    Moving,
+    Childbirth,
+    Illness,
+}
+
+/// Verdict phrases per topic as (ja, en), best first. Each topic has
+/// `PHRASES_PER_TOPIC` entries selected by 2 hash bits.
+const PHRASES: [[(&str, &str); PHRASES_PER_TOPIC]; 12] = [
+    [
+        ("思うままに叶う", "Will come true as you hope."),
+        ("時間はかかるが叶う", "Will come true, given time."),
+        ("人の助けで叶う", "Will come true with others' help."),
+        ("今は控えよ", "Hold back for now."),
+    ],
+    [
+        ("来る、喜びあり", "They will come, bringing joy."),
+        ("遅れるが来る", "Late, but they will come."),
+        ("便りあり", "A message will arrive instead."),
+        ("来ず", "They will not come."),
+    ],
+    [
+        ("必ず出る", "It will surely turn up."),
+        ("低い所を探せ", "Look somewhere low."),
+        ("時が経てば出る", "It will turn up in time."),
+        ("出にくい", "Hard to find."),
+    ],
+    [
+        ("吉、どこへ行くもよし", "Good; go wherever you like."),
+        ("東の方角よし", "Head east for luck."),
+        ("急がず行け", "Go, but do not rush."),
+        ("控えたほうがよい", "Better to stay home."),
+    ],
+    [
+        ("利益あり", "Profit is coming."),
+        ("焦らず続ければ利あり", "Keep steady and profit follows."),
+        ("小さく始めよ", "Start small."),
+        ("損あり、慎め", "Losses ahead; be careful."),
+    ],
+    [
+        ("努力が実る", "Your efforts will pay off."),
+        ("基礎を固めよ", "Strengthen the basics."),
+        ("師の言葉を聞け", "Listen to your teacher."),
+        ("気を散らすな", "Avoid distractions."),
+    ],
+    [
+        ("買いよし", "A good time to buy."),
+        ("売りよし", "A good time to sell."),
+        ("見送れ", "Wait and see."),
+        ("手を出すな", "Stay out of it."),
+    ],
+    [
+        ("勝つ", "You will win."),
+        ("話し合いで収まる", "Talking it out settles it."),
+        ("人に任せよ", "Let someone else mediate."),
+        ("争うな、負ける", "Don't fight; you would lose."),
+    ],
+    [
+        ("想いが通じる", "Your feelings will be returned."),
+        ("誠実であれ", "Be sincere and it will go well."),
+        ("焦りは禁物", "Don't rush things."),
+        ("今は自分を磨け", "Focus on yourself for now."),
+    ],
+    [
+        ("吉、移ってよし", "Good; move ahead."),
+        ("春を待て", "Wait until spring."),
+        ("よく調べてから", "Research well first."),
+        ("今は動くな", "Don't move now."),
+    ],
+    [
+        ("安産", "An easy delivery."),
+        ("安心せよ", "Set your mind at ease."),
+        ("用心すれば安し", "Care brings safety."),
+        ("医師の言葉に従え", "Follow your doctor's advice."),
+    ],
+    [
+        ("快方に向かう", "Recovery is near."),
+        ("信じれば治る", "Have faith and you will heal."),
+        ("養生せよ", "Rest and take care."),
+        ("長引く、医者を頼れ", "It may linger; rely on a doctor."),
+    ],
+];
+
+pub const PHRASES_PER_TOPIC: usize = 4;
+
+impl Topic {
+    pub const ALL: [Topic; 12] = [
+        Topic::Wish,
+        Topic::Awaited,
+        Topic::Lost,
+        Topic::Travel,
+        Topic::Business,
+        Topic::Study,
+        Topic::Market,
+        Topic::Dispute,
+        Topic::Love,
+        Topic::Moving,
+        Topic::Childbirth,
+        Topic::Illness,
+    ];
+
+    pub fn name(&self, lang: Lang) -> &'static str {
+        let (ja, en) = match self {
+            Topic::Wish => ("願望", "Wish"),
+            Topic::Awaited => ("待人", "Awaited Person"),
+            Topic::Lost => ("失物", "Lost Article"),
+            Topic::Travel => ("旅行", "Travel"),
+            Topic::Business => ("商売", "Business"),
+            Topic::Study => ("学問", "Study"),
+            Topic::Market => ("相場", "Market"),
+            Topic::Dispute => ("争事", "Disputes"),
+            Topic::Love => ("恋愛", "Love"),
+            Topic::Moving => ("転居", "Moving"),
+            Topic::Childbirth => ("出産", "Childbirth"),
+            Topic::Illness => ("病気", "Illness"),
+        };
+        match lang {
+            Lang::Ja => ja,
+            Lang::En => en,
+        }
+    }
+
+    pub fn phrase(&self, selector: u8, lang: Lang) -> &'static str {
+        let index = Topic::ALL.iter().position(|t| t == self).unwrap_or(0);
+        let (ja, en) = PHRASES[index][selector as usize % PHRASES_PER_TOPIC];
+        match lang {
+            Lang::Ja => ja,
+            Lang::En => en,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "serve", derive(utoipa::ToSchema))]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject), graphql(rename_fields = "snake_case"))]
+#[cfg_attr(feature = "std", derive(schemars::JsonSchema))]
+pub struct TopicVerdict {
+    pub topic: Topic,
+    pub name: Cow<'static, str>,
+    pub text: Cow<'static, str>,
+}
+
+pub fn draw_topics(hash: &HashBits, lang: Lang) -> Vec<TopicVerdict> {
+    Topic::ALL
+        .iter()
+        .enumerate()
+        .map(|(i, &topic)| {
+            let selector = hash.topic_selector(i);
+            TopicVerdict {
+                topic,
+                name: Cow::Borrowed(topic.name(lang)),
+                text: Cow::Borrowed(topic.phrase(selector, lang)),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_draw_topics_count_and_order() {
+        let hash = HashBits::from_seed(2026, "test");
+        let topics = draw_topics(&hash, Lang::En);
+        assert_eq!(topics.len(), 12);
+        assert_eq!(topics[0].topic, Topic::Wish);
+        assert_eq!(topics[0].name, "Wish");
+    }
+
+    #[test]
+    fn test_draw_topics_deterministic() {
+        let a = draw_topics(&HashBits::from_seed(2026, "test"), Lang::Ja);
+        let b = draw_topics(&HashBits::from_seed(2026, "test"), Lang::Ja);
+        let texts = |v: &[TopicVerdict]| v.iter().map(|t| t.text.clone()).collect::<Vec<_>>();
+        assert_eq!(texts(&a), texts(&b));
+    }
+
+    #[test]
+    fn test_languages_pick_same_phrase() {
+        let hash = HashBits::from_seed(2026, "test");
+        let ja = draw_topics(&hash, Lang::Ja);
+        let en = draw_topics(&hash, Lang::En);
+        for (j, e) in ja.iter().zip(en.iter()) {
+            let index = Topic::ALL.iter().position(|&t| t == j.topic).unwrap();
+            let slot = PHRASES[index].iter().position(|&(p, _)| p == j.text).unwrap();
+            assert_eq!(PHRASES[index][slot].1, e.text);
+        }
+    }
+
+    #[test]
+    fn test_all_phrases_reachable() {
+        let mut seen = [[false; PHRASES_PER_TOPIC]; 12];
+        for i in 0..200 {
+            let hash = HashBits::from_seed(2026, &format!("test-{}", i));
+            for (t, _) in Topic::ALL.iter().enumerate() {
+                seen[t][hash.topic_selector(t) as usize] = true;
+            }
+        }
+        assert!(seen.iter().all(|row| row.iter().all(|&s| s)));
+    }
+}
@@ -0,0 +1,374 @@
+//! `tui`: the year's fortune in a full-screen terminal interface. The grade is revealed
+//! by a short animation of the box being shaken, then tabs show the overview, a chart
+//! of the luck scores, the topics, the art and the past draws of the history.
+
+use crate::art;
+use crate::history::{self, HistoryEntry};
+use crate::lang::Lang;
+use crate::luck::{format_rarity, Grade};
+use crate::output::Fortune;
+use crate::prompt::grade_color;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Alignment, Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Bar, BarChart, BarGroup, Block, Borders, Paragraph, Row, Table, Tabs, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+use std::time::Duration;
+
+/// Tab titles, in order; `1`-`5` jump to them
+pub const TABS: [&str; 5] = ["Overview", "Scores", "Topics", "Art", "History"];
+
+/// Frames of the reveal animation, about 1.5 seconds at [`TICK`]
+pub const REVEAL_FRAMES: u16 = 24;
+
+/// Redraw interval while waiting for keys
+const TICK: Duration = Duration::from_millis(60);
+
+const HELP: &str = "←/→ tab  1-5 jump  ↑/↓ scroll  q quit";
+
+/// What the interface shows and where the user is in it
+pub struct App<'a> {
+    fortune: &'a Fortune,
+    history: &'a [HistoryEntry],
+    ascii: bool,
+    /// Selected tab, an index into [`TABS`]
+    pub tab: usize,
+    /// Lines scrolled down in the selected tab
+    pub scroll: u16,
+    /// Frames of the reveal shown so far
+    pub frame: u16,
+    pub quit: bool,
+}
+
+impl<'a> App<'a> {
+    pub fn new(fortune: &'a Fortune, history: &'a [HistoryEntry], ascii: bool) -> Self {
+        Self { fortune, history, ascii, tab: 0, scroll: 0, frame: 0, quit: false }
+    }
+
+    pub fn revealed(&self) -> bool {
+        self.frame >= REVEAL_FRAMES
+    }
+
+    /// Advance the reveal animation by one frame
+    pub fn tick(&mut self) {
+        self.frame = self.frame.saturating_add(1).min(REVEAL_FRAMES);
+    }
+
+    /// Handle a key press; during the reveal any key but `q` skips to the result
+    pub fn key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            _ if !self.revealed() => self.frame = REVEAL_FRAMES,
+            KeyCode::Right | KeyCode::Tab | KeyCode::Char('l') => self.select((self.tab + 1) % TABS.len()),
+            KeyCode::Left | KeyCode::BackTab | KeyCode::Char('h') => self.select((self.tab + TABS.len() - 1) % TABS.len()),
+            KeyCode::Char(c @ '1'..='5') => self.select(c as usize - '1' as usize),
+            KeyCode::Down | KeyCode::Char('j') => self.scroll = self.scroll.saturating_add(1),
+            KeyCode::Up | KeyCode::Char('k') => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::Home | KeyCode::Char('g') => self.scroll = 0,
+            _ => {}
+        }
+    }
+
+    fn select(&mut self, tab: usize) {
+        self.tab = tab;
+        self.scroll = 0;
+    }
+
+    /// Grade on the slip while the box is shaken: the ladder spins, slowing down
+    /// until it stops on the drawn grade in the last frame
+    pub fn shown_grade(&self) -> Grade {
+        if self.revealed() {
+            return self.fortune.grade;
+        }
+        let remaining = (REVEAL_FRAMES - self.frame) as usize;
+        let offset = remaining * (remaining + 1) / 2 / 3;
+        let drawn = Grade::ALL.iter().position(|&g| g == self.fortune.grade).unwrap_or(0);
+        Grade::ALL[(drawn + offset) % Grade::ALL.len()]
+    }
+
+    pub fn draw(&self, frame: &mut Frame) {
+        if !self.revealed() {
+            self.draw_reveal(frame, frame.area());
+            return;
+        }
+        let [tabs, body, help] =
+            Layout::vertical([Constraint::Length(3), Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+        let title = format!(" 🎍 Hash-Omikuji {} 🎍 ", self.fortune.year_label());
+        frame.render_widget(
+            Tabs::new(TABS.iter().enumerate().map(|(i, name)| format!("{} {}", i + 1, name)))
+                .select(self.tab)
+                .highlight_style(Style::default().fg(grade_fg(self.fortune.grade)).add_modifier(Modifier::BOLD))
+                .block(Block::default().borders(Borders::ALL).title(title)),
+            tabs,
+        );
+        match self.tab {
+            0 => self.draw_overview(frame, body),
+            1 => self.draw_scores(frame, body),
+            2 => self.draw_topics(frame, body),
+            3 => self.draw_art(frame, body),
+            _ => self.draw_history(frame, body),
+        }
+        frame.render_widget(Paragraph::new(HELP).style(Style::default().fg(Color::DarkGray)), help);
+    }
+
+    fn draw_reveal(&self, frame: &mut Frame, area: Rect) {
+        let grade = self.shown_grade();
+        let shake = if self.frame.is_multiple_of(2) { "  " } else { "" };
+        let lines = vec![
+            Line::from(format!("Hash-Omikuji {}", self.fortune.year_label())),
+            Line::from(""),
+            Line::from(Span::styled(
+                format!("{}[ {} ]", shake, grade.kanji()),
+                Style::default().fg(grade_fg(grade)).add_modifier(Modifier::BOLD),
+            )),
+            Line::from(""),
+            Line::from(Span::styled("Shaking the box... (any key to skip)", Style::default().fg(Color::DarkGray))),
+        ];
+        let [_, middle, _] = Layout::vertical([Constraint::Fill(1), Constraint::Length(7), Constraint::Fill(1)]).areas(area);
+        frame.render_widget(
+            Paragraph::new(lines).alignment(Alignment::Center).block(Block::default().borders(Borders::ALL)),
+            middle,
+        );
+    }
+
+    fn draw_overview(&self, frame: &mut Frame, area: Rect) {
+        let fortune = self.fortune;
+        let field = |name: &str, value: String| Line::from(vec![Span::raw(format!("{:12}: ", name)), Span::raw(value)]);
+        let mut lines = vec![
+            Line::from(Span::styled(
+                format!("{} ({:#})", fortune.grade, fortune.grade),
+                Style::default().fg(grade_fg(fortune.grade)).add_modifier(Modifier::BOLD),
+            )),
+            field("Overall", format!("{} {}", fortune.overall_score, fortune.star_rating)),
+            field("Rarity", format_rarity(fortune.rarity_one_in)),
+            Line::from(""),
+            field("Number", fortune.lucky_number.to_string()),
+            field("Color", fortune.lucky_color.clone()),
+            field("Day", fortune.lucky_day_text()),
+            field("Time", fortune.lucky_time.clone()),
+            field("Emoji", fortune.lucky_emoji.clone()),
+            field("Direction", fortune.lucky_direction.clone()),
+            field("Item", fortune.lucky_item.clone()),
+            field("Action", fortune.lucky_action.clone()),
+            Line::from(""),
+            Line::from(Span::styled(heading(fortune.lang, "Advice", "御言葉"), Style::default().add_modifier(Modifier::BOLD))),
+            Line::from(fortune.advice.clone()),
+            Line::from(""),
+            Line::from(Span::styled(heading(fortune.lang, "Poem", "和歌"), Style::default().add_modifier(Modifier::BOLD))),
+        ];
+        lines.extend([&fortune.poem.text, &fortune.poem.romaji].map(|text| Line::from(text.as_ref())));
+        lines.push(Line::from(format!("\"{}\"", fortune.poem.gloss)));
+        frame.render_widget(self.scrolled(lines), area);
+    }
+
+    fn draw_scores(&self, frame: &mut Frame, area: Rect) {
+        let bars: Vec<Bar> = self
+            .fortune
+            .luck_scores
+            .iter()
+            .map(|score| {
+                Bar::default()
+                    .value(score.score as u64)
+                    .label(Line::from(format!("{:18}", score.label)))
+                    .text_value(format!("{} ({})", score.score, score.rank.as_str()))
+            })
+            .collect();
+        frame.render_widget(
+            BarChart::default()
+                .block(Block::default().borders(Borders::ALL).title(" Luck Scores "))
+                .direction(Direction::Horizontal)
+                .bar_width(1)
+                .bar_gap(0)
+                .max(100)
+                .bar_style(Style::default().fg(grade_fg(self.fortune.grade)))
+                .value_style(Style::default().add_modifier(Modifier::BOLD))
+                .data(BarGroup::default().bars(&bars)),
+            area,
+        );
+    }
+
+    fn draw_topics(&self, frame: &mut Frame, area: Rect) {
+        let lines: Vec<Line> = self
+            .fortune
+            .topics
+            .iter()
+            .map(|verdict| {
+                Line::from(vec![
+                    Span::styled(verdict.name.clone(), Style::default().add_modifier(Modifier::BOLD)),
+                    Span::raw(format!(": {}", verdict.text)),
+                ])
+            })
+            .collect();
+        frame.render_widget(self.scrolled(lines), area);
+    }
+
+    fn draw_art(&self, frame: &mut Frame, area: Rect) {
+        let fortune = self.fortune;
+        let framed = art::framed(&fortune.art, fortune.year, fortune.grade, self.ascii);
+        let mut lines: Vec<Line> = framed.lines().map(|line| Line::from(line.to_string())).collect();
+        lines.push(Line::from(""));
+        lines.push(Line::from(fortune.fingerprint_emoji.clone()));
+        lines.push(Line::from(Span::styled(fortune.fingerprint_sha256.clone(), Style::default().fg(Color::DarkGray))));
+        frame.render_widget(
+            Paragraph::new(lines)
+                .alignment(Alignment::Center)
+                .scroll((self.scroll, 0))
+                .block(Block::default().borders(Borders::ALL)),
+            area,
+        );
+    }
+
+    fn draw_history(&self, frame: &mut Frame, area: Rect) {
+        let block = Block::default().borders(Borders::ALL).title(" History ");
+        if self.history.is_empty() {
+            frame.render_widget(Paragraph::new("No draws recorded yet").block(block), area);
+            return;
+        }
+        let rows: Vec<Row> = history::by_year(self.history)
+            .into_values()
+            .rev()
+            .skip(self.scroll as usize)
+            .map(|entry| {
+                Row::new(vec![
+                    entry.year.to_string(),
+                    entry.drawn_on.to_string(),
+                    format!("{} ({:#})", entry.grade, entry.grade),
+                    entry.overall_score.to_string(),
+                    if entry.forced { "forced".to_string() } else { String::new() },
+                ])
+                .style(Style::default().fg(grade_fg(entry.grade)))
+            })
+            .collect();
+        let widths = [Constraint::Length(6), Constraint::Length(12), Constraint::Length(24), Constraint::Length(6), Constraint::Min(6)];
+        frame.render_widget(
+            Table::new(rows, widths)
+                .header(Row::new(["Year", "Drawn on", "Grade", "Score", ""]).style(Style::default().add_modifier(Modifier::BOLD)))
+                .block(block),
+            area,
+        );
+    }
+
+    fn scrolled<'t>(&self, lines: Vec<Line<'t>>) -> Paragraph<'t> {
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .scroll((self.scroll, 0))
+            .block(Block::default().borders(Borders::ALL))
+    }
+}
+
+fn grade_fg(grade: Grade) -> Color {
+    Color::Indexed(grade_color(grade))
+}
+
+fn heading(lang: Lang, en: &'static str, ja: &'static str) -> &'static str {
+    match lang {
+        Lang::Ja => ja,
+        Lang::En => en,
+    }
+}
+
+/// Take over the terminal until the user quits; it is restored on error too
+pub fn run(fortune: &Fortune, history: &[HistoryEntry], ascii: bool) -> Result<(), String> {
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, &mut App::new(fortune, history, ascii));
+    ratatui::restore();
+    result.map_err(|e| format!("Terminal error: {}", e))
+}
+
+fn event_loop(terminal: &mut DefaultTerminal, app: &mut App) -> std::io::Result<()> {
+    while !app.quit {
+        terminal.draw(|frame| app.draw(frame))?;
+        if event::poll(TICK)? {
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    app.key(key.code);
+                }
+            }
+        } else {
+            app.tick();
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::HashBits;
+    use chrono::NaiveDate;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    fn fortune() -> Fortune {
+        Fortune::from_hash(&HashBits::from_seed(2026, "test-user"), 2026, "test-user")
+    }
+
+    fn render(app: &App) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(100, 40)).unwrap();
+        terminal.draw(|frame| app.draw(frame)).unwrap();
+        let buffer = terminal.backend().buffer();
+        (0..buffer.area.height)
+            .map(|y| (0..buffer.area.width).map(|x| buffer[(x, y)].symbol()).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_reveal_lands_on_the_grade() {
+        let fortune = fortune();
+        let mut app = App::new(&fortune, &[], false);
+        assert!(render(&app).contains("Shaking the box"));
+        for _ in 0..REVEAL_FRAMES - 1 {
+            app.tick();
+            assert!(!app.revealed());
+        }
+        app.tick();
+        assert!(app.revealed());
+        assert_eq!(app.shown_grade(), fortune.grade);
+        assert!(render(&app).contains(&fortune.lucky_item));
+
+        let mut app = App::new(&fortune, &[], false);
+        app.key(KeyCode::Right);
+        assert!(app.revealed());
+        assert_eq!(app.tab, 0);
+    }
+
+    #[test]
+    fn test_tab_navigation() {
+        let fortune = fortune();
+        let mut app = App::new(&fortune, &[], false);
+        app.frame = REVEAL_FRAMES;
+        app.key(KeyCode::Left);
+        assert_eq!(app.tab, 4);
+        app.key(KeyCode::Right);
+        assert_eq!(app.tab, 0);
+        app.key(KeyCode::Char('3'));
+        app.key(KeyCode::Down);
+        assert_eq!((app.tab, app.scroll), (2, 1));
+        app.key(KeyCode::Tab);
+        assert_eq!((app.tab, app.scroll), (3, 0));
+        app.key(KeyCode::Char('q'));
+        assert!(app.quit);
+    }
+
+    #[test]
+    fn test_tabs_render() {
+        let fortune = fortune();
+        let drawn_on = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let history = [HistoryEntry { year: 2025, drawn_on, forced: false, ..HistoryEntry::from_result(&fortune, drawn_on, false) }];
+        let mut app = App::new(&fortune, &history, false);
+        app.frame = REVEAL_FRAMES;
+
+        app.key(KeyCode::Char('2'));
+        let best = fortune.luck_scores.iter().max_by_key(|s| s.score).unwrap();
+        assert!(render(&app).contains(&*best.label));
+        app.key(KeyCode::Char('3'));
+        assert!(render(&app).contains(&*fortune.topics[0].name));
+        app.key(KeyCode::Char('4'));
+        assert!(render(&app).contains("hash-omikuji"));
+        app.key(KeyCode::Char('5'));
+        assert!(render(&app).contains("2025-01-01"));
+        assert!(render(&App { tab: 4, frame: REVEAL_FRAMES, ..App::new(&fortune, &[], false) }).contains("No draws recorded yet"));
+    }
+}
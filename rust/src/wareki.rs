@@ -0,0 +1,113 @@
+//! Japanese era years (和暦): `--force-year R8` input, and `令和8年` next to the year in
+//! Japanese output. A new era is one more row in [`ERAS`].
+
+use chrono::NaiveDate;
+
+pub struct Era {
+    /// Kanji name, e.g. 令和
+    pub name: &'static str,
+    pub romaji: &'static str,
+    /// Initial used in abbreviations such as R8
+    pub letter: char,
+    /// First day of the era (Gregorian)
+    pub start: (i32, u32, u32),
+}
+
+/// Eras since the adoption of the Gregorian calendar, oldest first
+pub const ERAS: [Era; 5] = [
+    Era { name: "明治", romaji: "Meiji", letter: 'M', start: (1868, 10, 23) },
+    Era { name: "大正", romaji: "Taisho", letter: 'T', start: (1912, 7, 30) },
+    Era { name: "昭和", romaji: "Showa", letter: 'S', start: (1926, 12, 25) },
+    Era { name: "平成", romaji: "Heisei", letter: 'H', start: (1989, 1, 8) },
+    Era { name: "令和", romaji: "Reiwa", letter: 'R', start: (2019, 5, 1) },
+];
+
+impl Era {
+    fn start_date(&self) -> NaiveDate {
+        let (year, month, day) = self.start;
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn matches(&self, prefix: &str) -> bool {
+        prefix == self.name
+            || prefix.eq_ignore_ascii_case(self.romaji)
+            || (prefix.chars().count() == 1 && prefix.chars().all(|c| c.eq_ignore_ascii_case(&self.letter)))
+    }
+}
+
+/// The Gregorian year of an era year: `R8`, `r8`, `Reiwa 8`, `令和8年`, `令和元年`.
+/// The last year of an era overlaps the first of the next (H31 and R1 are both 2019).
+/// None past the supported years ([`date::YEARS`](crate::date::YEARS)).
+pub fn parse(text: &str) -> Option<u32> {
+    let text = text.trim();
+    let text = text.strip_suffix('年').unwrap_or(text);
+    let split = text.find(|c: char| c.is_ascii_digit() || c == '元')?;
+    let (prefix, number) = text.split_at(split);
+    let number: i32 = if number == "元" { 1 } else { number.parse().ok()? };
+    let index = ERAS.iter().position(|era| era.matches(prefix.trim()))?;
+    if number < 1 {
+        return None;
+    }
+    let year = ERAS[index].start.0.checked_add(number - 1)?;
+    match ERAS.get(index + 1) {
+        Some(next) if year > next.start.0 => None,
+        _ => u32::try_from(year).ok().filter(|year| crate::date::YEARS.contains(year)),
+    }
+}
+
+/// The era year on January 1st of `year` (the day of the draw), e.g. `令和8年`, or
+/// `平成31年` for 2019; None before 1868
+pub fn format_ja(year: u32) -> Option<String> {
+    let new_year = NaiveDate::from_ymd_opt(year as i32, 1, 1)?;
+    let era = match ERAS.iter().rev().find(|era| era.start_date() <= new_year) {
+        Some(era) => era,
+        // Meiji 1 is counted from the start of 1868
+        None if ERAS[0].start.0 == year as i32 => &ERAS[0],
+        None => return None,
+    };
+    let number = year as i32 - era.start.0 + 1;
+    Some(match number {
+        1 => format!("{}元年", era.name),
+        n => format!("{}{}年", era.name, n),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse("R8"), Some(2026));
+        assert_eq!(parse("r8"), Some(2026));
+        assert_eq!(parse("Reiwa 8"), Some(2026));
+        assert_eq!(parse("令和8年"), Some(2026));
+        assert_eq!(parse("令和元年"), Some(2019));
+        assert_eq!(parse("H31"), Some(2019));
+        assert_eq!(parse("H1"), Some(1989));
+        assert_eq!(parse("S64"), Some(1989));
+        assert_eq!(parse("T15"), Some(1926));
+        assert_eq!(parse("M45"), Some(1912));
+        assert_eq!(parse("M1"), Some(1868));
+        assert_eq!(parse("H32"), None);
+        assert_eq!(parse("R0"), None);
+        assert_eq!(parse("X8"), None);
+        assert_eq!(parse("2026"), None);
+        assert_eq!(parse("R7981"), Some(9999));
+        assert_eq!(parse("R7982"), None);
+        assert_eq!(parse("R2147483647"), None);
+        assert_eq!(parse("R99999999999"), None);
+    }
+
+    #[test]
+    fn test_format_ja() {
+        assert_eq!(format_ja(2026).as_deref(), Some("令和8年"));
+        // New Year's Day 2019 was still Heisei
+        assert_eq!(format_ja(2019).as_deref(), Some("平成31年"));
+        assert_eq!(format_ja(2020).as_deref(), Some("令和2年"));
+        assert_eq!(format_ja(1989).as_deref(), Some("昭和64年"));
+        assert_eq!(format_ja(1927).as_deref(), Some("昭和2年"));
+        assert_eq!(format_ja(1868).as_deref(), Some("明治元年"));
+        assert_eq!(format_ja(1867), None);
+    }
+}
@@ -0,0 +1,19 @@
+//! Browser bindings through wasm-bindgen, so the web version runs this exact implementation.
+
+use crate::art;
+use crate::output::Fortune;
+use wasm_bindgen::prelude::*;
+
+/// Full fortune as a plain JS object, the same document as `--json`
+#[wasm_bindgen]
+pub fn draw(year: u32, seed: &str) -> JsValue {
+    let fortune = Fortune::builder().year(year).seed(seed).draw();
+    js_sys::JSON::parse(&fortune.format_json()).unwrap_throw()
+}
+
+/// The framed randomart, as printed by the CLI
+#[wasm_bindgen]
+pub fn art(year: u32, seed: &str) -> String {
+    let fortune = Fortune::builder().year(year).seed(seed).draw();
+    art::framed(&fortune.art, year, fortune.grade, false)
+}
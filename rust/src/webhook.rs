@@ -0,0 +1,131 @@
+//! POST a payload to a webhook, retrying on rate limits and server errors.
+
+use std::collections::BTreeMap;
+#[cfg(not(target_os = "wasi"))]
+use std::thread;
+use std::time::Duration;
+
+pub const MAX_ATTEMPTS: u32 = 5;
+
+/// Longest wait honored from a server's retry hint
+const MAX_WAIT_SECS: f64 = 60.0;
+
+/// Wait before retry number `attempt`: the server's hint in seconds (Discord sends fractions),
+/// otherwise exponential backoff from one second
+pub fn retry_delay(attempt: u32, hint: Option<&str>) -> Duration {
+    hint.and_then(|s| s.trim().parse::<f64>().ok())
+        .filter(|s| s.is_finite() && *s >= 0.0)
+        .map(|s| Duration::from_secs_f64(s.min(MAX_WAIT_SECS)))
+        .unwrap_or_else(|| Duration::from_secs(1 << attempt.min(5)))
+}
+
+/// Scheme and host only: webhook URLs carry their token in the path
+pub fn redact(url: &str) -> String {
+    url.splitn(4, '/').take(3).collect::<Vec<_>>().join("/")
+}
+
+/// POST `body` with the given content type and extra headers. 429s, 5xx and connection failures are retried up to MAX_ATTEMPTS times;
+/// when the reply says the rate-limit bucket is empty, this waits for it to refill so a batch
+/// of posts stays under the limit.
+#[cfg(not(target_os = "wasi"))]
+pub fn post(url: &str, content_type: &str, body: &str, headers: &BTreeMap<String, String>) -> Result<(), String> {
+    let mut attempt = 0;
+    loop {
+        attempt += 1;
+        let retry = attempt < MAX_ATTEMPTS;
+        let request = headers.iter().fold(ureq::post(url).set("Content-Type", content_type), |r, (name, value)| r.set(name, value));
+        match request.send_string(body) {
+            Ok(response) => {
+                if response.header("X-RateLimit-Remaining") == Some("0") {
+                    thread::sleep(retry_delay(0, response.header("X-RateLimit-Reset-After")));
+                }
+                return Ok(());
+            }
+            Err(ureq::Error::Status(code, response)) if retry && (code == 429 || code >= 500) => {
+                thread::sleep(retry_delay(attempt - 1, response.header("Retry-After")));
+            }
+            Err(ureq::Error::Status(code, response)) => {
+                let reply = response.into_string().unwrap_or_default();
+                return Err(format!("{} answered {}: {}", redact(url), code, reply.trim()));
+            }
+            Err(ureq::Error::Transport(_)) if retry => thread::sleep(retry_delay(attempt - 1, None)),
+            Err(ureq::Error::Transport(e)) => {
+                return Err(format!("Failed to post to {}: {}", redact(url), e.kind()));
+            }
+        }
+    }
+}
+
+/// The WASI build has no HTTP client
+#[cfg(target_os = "wasi")]
+pub fn post(url: &str, _content_type: &str, _body: &str, _headers: &BTreeMap<String, String>) -> Result<(), String> {
+    Err(format!("Cannot post to {}: posting is not available in the WASI build", redact(url)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+
+    #[test]
+    fn test_retry_delay() {
+        assert_eq!(retry_delay(0, Some("0.25")), Duration::from_millis(250));
+        assert_eq!(retry_delay(0, Some("3600")), Duration::from_secs(60));
+        assert_eq!(retry_delay(0, None), Duration::from_secs(1));
+        assert_eq!(retry_delay(2, Some("soon")), Duration::from_secs(4));
+    }
+
+    #[test]
+    fn test_redact() {
+        assert_eq!(redact("https://discord.com/api/webhooks/1/secret"), "https://discord.com");
+        assert_eq!(redact("http://localhost:8080"), "http://localhost:8080");
+    }
+
+    /// Serve the given status lines in order, returning the request bodies
+    fn serve(responses: &'static [&'static str]) -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let mut bodies = Vec::new();
+            for response in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(value) = line.to_lowercase().strip_prefix("content-length:") {
+                        length = value.trim().parse().unwrap();
+                    }
+                    if line == "\r\n" {
+                        break;
+                    }
+                }
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).unwrap();
+                bodies.push(String::from_utf8(body).unwrap());
+                let reply = format!("HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n", response);
+                reader.into_inner().write_all(reply.as_bytes()).unwrap();
+            }
+            bodies
+        });
+        (url, handle)
+    }
+
+    #[test]
+    fn test_post_retries_rate_limit() {
+        let (url, server) = serve(&["429 Too Many Requests\r\nRetry-After: 0", "204 No Content"]);
+        post(&url, "application/json", "{\"a\":1}", &BTreeMap::new()).unwrap();
+        assert_eq!(server.join().unwrap(), ["{\"a\":1}", "{\"a\":1}"]);
+    }
+
+    #[test]
+    fn test_post_reports_client_error() {
+        let (url, server) = serve(&["400 Bad Request"]);
+        let error = post(&url, "application/json", "{}", &BTreeMap::new()).unwrap_err();
+        assert!(error.contains("answered 400"), "{}", error);
+        assert!(!error.contains("/hook"));
+        server.join().unwrap();
+    }
+}
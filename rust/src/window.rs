@@ -0,0 +1,154 @@
+//! Execution windows: the days a draw is allowed without `--force-year`. New Year's Day
+//! (and `--birthday`) are built in; the config file adds more with `[[windows]]`.
+
+use chrono::{Datelike, NaiveDate};
+use serde::Deserialize;
+use std::fmt;
+use std::str::FromStr;
+
+/// A day of the year (`MM-DD`), or of every month (`*-DD`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct MonthDay {
+    month: Option<u32>,
+    day: u32,
+}
+
+impl FromStr for MonthDay {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, String> {
+        let invalid = || format!("invalid day {:?} (expected MM-DD, or *-DD for every month)", text);
+        let (month, day) = text.trim().split_once('-').ok_or_else(invalid)?;
+        let day: u32 = day.parse().map_err(|_| invalid())?;
+        let month = match month {
+            "*" => None,
+            month => Some(month.parse().map_err(|_| invalid())?),
+        };
+        // 2000 is a leap year, so 02-29 is accepted
+        NaiveDate::from_ymd_opt(2000, month.unwrap_or(1), day).ok_or_else(invalid)?;
+        Ok(Self { month, day })
+    }
+}
+
+impl TryFrom<String> for MonthDay {
+    type Error = String;
+
+    fn try_from(text: String) -> Result<Self, String> {
+        text.parse()
+    }
+}
+
+impl fmt::Display for MonthDay {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.month {
+            Some(month) => write!(f, "{:02}-{:02}", month, self.day),
+            None => write!(f, "*-{:02}", self.day),
+        }
+    }
+}
+
+/// A configured window of allowed days
+///
+/// ```toml
+/// [[windows]]
+/// name = "First of the month"
+/// from = "*-01"
+/// warning = "Monthly draws use the year's seed; expect the same fortune."
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Window {
+    pub name: String,
+    pub from: MonthDay,
+    /// Last day, inclusive (default: `from`); may wrap past the end of the year or month
+    pub to: Option<MonthDay>,
+    /// Printed before a text draw in this window (default: none)
+    pub warning: Option<String>,
+}
+
+impl Window {
+    fn to(&self) -> MonthDay {
+        self.to.unwrap_or(self.from)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.from.month.is_some() != self.to().month.is_some() {
+            return Err(format!("Window '{}' mixes a yearly and a monthly day", self.name));
+        }
+        Ok(())
+    }
+
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        let (from, to) = (self.from, self.to());
+        let key = |day: MonthDay| (day.month.unwrap_or(0), day.day);
+        let today = match from.month {
+            Some(_) => (date.month(), date.day()),
+            None => (0, date.day()),
+        };
+        if key(from) <= key(to) {
+            key(from) <= today && today <= key(to)
+        } else {
+            today >= key(from) || today <= key(to)
+        }
+    }
+}
+
+impl fmt::Display for Window {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.to {
+            Some(to) if to != self.from => write!(f, "{} ({}..{})", self.name, self.from, to),
+            _ => write!(f, "{} ({})", self.name, self.from),
+        }
+    }
+}
+
+/// Why a draw date is allowed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Permit {
+    /// A window without a warning: New Year's Day, the birthday, a quiet config window
+    Open,
+    /// A config window with its own warning
+    Warned(String),
+    /// Outside every window, allowed by --force-year
+    Forced,
+}
+
+/// The first window containing `date`
+pub fn find(windows: &[Window], date: NaiveDate) -> Option<&Window> {
+    windows.iter().find(|window| window.contains(date))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(text: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(text, "%Y-%m-%d").unwrap()
+    }
+
+    fn window(from: &str, to: Option<&str>) -> Window {
+        Window { name: "test".to_string(), from: from.parse().unwrap(), to: to.map(|to| to.parse().unwrap()), warning: None }
+    }
+
+    #[test]
+    fn test_contains() {
+        let anniversary = window("04-01", None);
+        assert!(anniversary.contains(date("2026-04-01")));
+        assert!(!anniversary.contains(date("2026-04-02")));
+
+        let holidays = window("12-30", Some("01-03"));
+        assert!(holidays.contains(date("2026-12-31")));
+        assert!(holidays.contains(date("2027-01-02")));
+        assert!(!holidays.contains(date("2026-01-04")));
+
+        let monthly = window("*-01", None);
+        assert!(monthly.contains(date("2026-07-01")));
+        assert!(!monthly.contains(date("2026-07-02")));
+        assert_eq!(monthly.to_string(), "test (*-01)");
+
+        assert!(window("*-01", Some("03-01")).validate().is_err());
+        assert!("*-32".parse::<MonthDay>().is_err());
+        assert!("13-01".parse::<MonthDay>().is_err());
+    }
+}